@@ -1,7 +1,8 @@
 //! Push and Pull wrappers to maintain counts of messages in channels.
 
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 use crate::{Push, Pull};
 
@@ -144,3 +145,173 @@ impl<T, P: Pull<T>> Pull<T> for Puller<T, P> {
         result
     }
 }
+
+/// Messages and bytes moved by a worker on one channel.
+///
+/// Bytes are counted only for messages that are serialized, and exclude framing headers.
+/// Messages exchanged between workers of a process without serialization count no bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChannelStatistics {
+    /// The number of messages sent.
+    pub messages_sent: usize,
+    /// The number of bytes sent.
+    pub bytes_sent: usize,
+    /// The number of messages received.
+    pub messages_received: usize,
+    /// The number of bytes received.
+    pub bytes_received: usize,
+}
+
+impl ChannelStatistics {
+    /// Accounts for a sent message of `bytes` bytes.
+    #[inline]
+    pub fn sent(cell: &Cell<Self>, bytes: usize) {
+        let mut statistics = cell.get();
+        statistics.messages_sent += 1;
+        statistics.bytes_sent += bytes;
+        cell.set(statistics);
+    }
+    /// Accounts for a received message of `bytes` bytes.
+    #[inline]
+    pub fn received(cell: &Cell<Self>, bytes: usize) {
+        let mut statistics = cell.get();
+        statistics.messages_received += 1;
+        statistics.bytes_received += bytes;
+        cell.set(statistics);
+    }
+}
+
+impl std::ops::AddAssign for ChannelStatistics {
+    fn add_assign(&mut self, other: Self) {
+        self.messages_sent += other.messages_sent;
+        self.bytes_sent += other.bytes_sent;
+        self.messages_received += other.messages_received;
+        self.bytes_received += other.bytes_received;
+    }
+}
+
+/// Per-channel statistics maintained by an allocator, shared with the endpoints of its channels.
+///
+/// Statistics accumulate from the allocation of each channel, and remain after the channel is dropped.
+#[derive(Default)]
+pub struct Statistics {
+    channels: HashMap<usize, Rc<Cell<ChannelStatistics>>>,
+}
+
+impl Statistics {
+    /// The statistics of channel `identifier`, to be updated by its endpoints.
+    pub fn channel(&mut self, identifier: usize) -> Rc<Cell<ChannelStatistics>> {
+        Rc::clone(self.channels.entry(identifier).or_default())
+    }
+    /// A snapshot of the statistics of all channels.
+    pub fn snapshot(&self) -> HashMap<usize, ChannelStatistics> {
+        self.channels.iter().map(|(identifier, statistics)| (*identifier, statistics.get())).collect()
+    }
+}
+
+/// Wraps a pusher to count sent messages, without bytes.
+pub struct StatisticsPusher<T, P: Push<T>> {
+    pusher: P,
+    statistics: Rc<Cell<ChannelStatistics>>,
+    phantom: ::std::marker::PhantomData<T>,
+}
+
+impl<T, P: Push<T>> StatisticsPusher<T, P> {
+    /// Wraps a pusher with statistics.
+    pub fn new(pusher: P, statistics: Rc<Cell<ChannelStatistics>>) -> Self {
+        StatisticsPusher {
+            pusher,
+            statistics,
+            phantom: ::std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, P: Push<T>> Push<T> for StatisticsPusher<T, P> {
+    #[inline]
+    fn push(&mut self, element: &mut Option<T>) {
+        if element.is_some() {
+            ChannelStatistics::sent(&self.statistics, 0);
+        }
+        self.pusher.push(element)
+    }
+}
+
+/// Wraps a puller to count received messages, without bytes.
+pub struct StatisticsPuller<T, P: Pull<T>> {
+    puller: P,
+    statistics: Rc<Cell<ChannelStatistics>>,
+    phantom: ::std::marker::PhantomData<T>,
+}
+
+impl<T, P: Pull<T>> StatisticsPuller<T, P> {
+    /// Wraps a puller with statistics.
+    pub fn new(puller: P, statistics: Rc<Cell<ChannelStatistics>>) -> Self {
+        StatisticsPuller {
+            puller,
+            statistics,
+            phantom: ::std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, P: Pull<T>> Pull<T> for StatisticsPuller<T, P> {
+    #[inline]
+    fn pull(&mut self) -> &mut Option<T> {
+        let result = self.puller.pull();
+        if result.is_some() {
+            ChannelStatistics::received(&self.statistics, 0);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::allocator::{Allocate, AllocateBuilder, Record};
+    use crate::{Config, Push, Pull};
+    use super::ChannelStatistics;
+
+    /// Sends three records from worker 0 to worker 1 on channel 7, and returns each worker's statistics.
+    fn statistics(config: Config) -> Vec<ChannelStatistics> {
+        let (builders, _guard) = config.try_build().unwrap();
+        let guards = builders
+            .into_iter()
+            .map(|builder| {
+                std::thread::spawn(move || {
+                    let mut allocator = builder.build();
+                    let (mut pushers, mut puller) = allocator.allocate::<Record>(7);
+                    if allocator.index() == 0 {
+                        pushers[1].push_iter((0 .. 3).map(Record));
+                    }
+                    else {
+                        let mut received = 0;
+                        while received < 3 {
+                            allocator.receive();
+                            if puller.recv().is_some() { received += 1; }
+                        }
+                    }
+                    allocator.release();
+                    assert_eq!(allocator.statistics().len(), 1);
+                    allocator.statistics()[&7]
+                })
+            })
+            .collect::<Vec<_>>();
+        guards.into_iter().map(|guard| guard.join().unwrap()).collect()
+    }
+
+    #[test]
+    fn process_statistics() {
+        let sent = ChannelStatistics { messages_sent: 3, ..Default::default() };
+        let received = ChannelStatistics { messages_received: 3, ..Default::default() };
+        assert_eq!(statistics(Config::Process(2)), vec![sent, received]);
+    }
+
+    #[test]
+    fn process_binary_statistics() {
+        let sent = ChannelStatistics { messages_sent: 3, bytes_sent: 24, ..Default::default() };
+        let received = ChannelStatistics { messages_received: 3, bytes_received: 24, ..Default::default() };
+        assert_eq!(statistics(Config::ProcessBinary(2)), vec![sent, received]);
+    }
+}
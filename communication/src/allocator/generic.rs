@@ -3,12 +3,15 @@
 //! This type is useful in settings where it is difficult to write code generic in `A: Allocate`,
 //! for example closures whose type arguments must be specified.
 
+use std::any::Any;
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashMap;
 
 use crate::allocator::thread::ThreadBuilder;
 use crate::allocator::process::ProcessBuilder as TypedProcessBuilder;
-use crate::allocator::{Allocate, AllocateBuilder, Exchangeable, Thread, Process};
+use crate::allocator::{Allocate, AllocateBuilder, Endpoints, Exchangeable, Thread, Process};
+use crate::allocator::counters::ChannelStatistics;
 use crate::allocator::zero_copy::allocator_process::{ProcessBuilder, ProcessAllocator};
 use crate::allocator::zero_copy::allocator::{TcpBuilder, TcpAllocator};
 
@@ -55,6 +58,15 @@ impl Generic {
             Generic::ZeroCopy(z) => z.allocate(identifier),
         }
     }
+    /// Constructs several send endpoints and one receive endpoint, for types that cannot be serialized.
+    fn allocate_local<T: Send+Any>(&mut self, identifier: usize) -> Result<Endpoints<T>, String> {
+        match self {
+            Generic::Thread(t) => t.allocate_local(identifier),
+            Generic::Process(p) => p.allocate_local(identifier),
+            Generic::ProcessBinary(pb) => pb.allocate_local(identifier),
+            Generic::ZeroCopy(z) => z.allocate_local(identifier),
+        }
+    }
     /// Perform work before scheduling operators.
     fn receive(&mut self) {
         match self {
@@ -89,6 +101,35 @@ impl Allocate for Generic {
     fn allocate<T: Exchangeable>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<T>>>, Box<dyn Pull<T>>) {
         self.allocate(identifier)
     }
+    fn allocate_local<T: Send+Any>(&mut self, identifier: usize) -> Result<Endpoints<T>, String> {
+        self.allocate_local(identifier)
+    }
+    fn prioritize(&mut self, identifier: usize) {
+        match self {
+            Generic::Thread(t) => t.prioritize(identifier),
+            Generic::Process(p) => p.prioritize(identifier),
+            Generic::ProcessBinary(pb) => pb.prioritize(identifier),
+            Generic::ZeroCopy(z) => z.prioritize(identifier),
+        }
+    }
+
+    fn backlog(&self) -> usize {
+        match self {
+            Generic::Thread(t) => t.backlog(),
+            Generic::Process(p) => p.backlog(),
+            Generic::ProcessBinary(pb) => pb.backlog(),
+            Generic::ZeroCopy(z) => z.backlog(),
+        }
+    }
+
+    fn statistics(&self) -> HashMap<usize, ChannelStatistics> {
+        match self {
+            Generic::Thread(t) => t.statistics(),
+            Generic::Process(p) => p.statistics(),
+            Generic::ProcessBinary(pb) => pb.statistics(),
+            Generic::ZeroCopy(z) => z.statistics(),
+        }
+    }
 
     fn receive(&mut self) { self.receive(); }
     fn release(&mut self) { self.release(); }
@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::time::Duration;
 
 pub use self::thread::Thread;
@@ -38,6 +39,9 @@ use std::any::Any;
 pub trait Exchangeable : Send+Any+Bytesable { }
 impl<T: Send+Any+Bytesable> Exchangeable for T { }
 
+/// The send endpoints to each worker and the receive endpoint of a channel.
+pub type Endpoints<T> = (Vec<Box<dyn Push<T>>>, Box<dyn Pull<T>>);
+
 /// A type capable of allocating channels.
 ///
 /// There is some feature creep, in that this contains several convenience methods about the nature
@@ -49,6 +53,30 @@ pub trait Allocate {
     fn peers(&self) -> usize;
     /// Constructs several send endpoints and one receive endpoint.
     fn allocate<T: Exchangeable>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<T>>>, Box<dyn Pull<T>>);
+    /// Constructs several send endpoints and one receive endpoint, for types that cannot be serialized.
+    ///
+    /// Elements are moved between workers rather than serialized, which only allocators whose
+    /// workers exchange typed data in a shared address space support. Other allocators return
+    /// an error, which is the default.
+    fn allocate_local<T: Send+Any>(&mut self, identifier: usize) -> Result<Endpoints<T>, String> {
+        Err(format!("channel {} carries data that cannot be serialized, which this allocator does not support", identifier))
+    }
+    /// Indicates that messages on the channel `identifier` should be sent ahead of other messages.
+    ///
+    /// This is a hint, which allocators may ignore, and by default do. It is intended for channels
+    /// whose messages are small but whose latency matters, for example progress updates.
+    fn prioritize(&mut self, _identifier: usize) { }
+    /// The number of sent elements the allocator holds because their channels are full.
+    ///
+    /// Only allocators with bounded channels hold elements, and others report zero. The held
+    /// elements are not bounded, and the report is advisory: a worker can consult it to avoid
+    /// introducing more data while its peers fall behind, and should release the allocator until
+    /// it holds no elements before dropping it.
+    fn backlog(&self) -> usize { 0 }
+    /// A snapshot of the messages and bytes moved on each channel, keyed by channel identifier.
+    ///
+    /// Allocators that do not maintain statistics report no channels, which is the default.
+    fn statistics(&self) -> HashMap<usize, counters::ChannelStatistics> { HashMap::new() }
     /// A shared queue of communication events with channel identifier.
     ///
     /// It is expected that users of the channel allocator will regularly
@@ -97,3 +125,14 @@ pub trait Allocate {
         thread::Thread::new_from(identifier, self.events().clone())
     }
 }
+
+/// A record that serializes as its eight little-endian bytes, for tests that move data between workers.
+#[cfg(test)]
+pub(crate) struct Record(pub u64);
+
+#[cfg(test)]
+impl Bytesable for Record {
+    fn from_bytes(bytes: timely_bytes::arc::Bytes) -> Self { Record(u64::from_le_bytes(bytes[..8].try_into().unwrap())) }
+    fn length_in_bytes(&self) -> usize { 8 }
+    fn into_bytes<W: std::io::Write>(&self, writer: &mut W) { writer.write_all(&self.0.to_le_bytes()).unwrap(); }
+}
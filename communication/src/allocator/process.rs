@@ -5,11 +5,12 @@ use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use std::any::Any;
 use std::time::Duration;
-use std::collections::{HashMap};
+use std::collections::{HashMap, VecDeque};
 use crossbeam_channel::{Sender, Receiver};
 
 use crate::allocator::thread::{ThreadBuilder};
-use crate::allocator::{Allocate, AllocateBuilder, Thread};
+use crate::allocator::counters::{ChannelStatistics, Statistics, StatisticsPusher, StatisticsPuller};
+use crate::allocator::{Allocate, AllocateBuilder, Endpoints, Thread};
 use crate::{Push, Pull};
 use crate::buzzer::Buzzer;
 
@@ -27,6 +28,9 @@ pub struct ProcessBuilder {
 
     counters_send: Vec<Sender<usize>>,
     counters_recv: Receiver<usize>,
+
+    // The capacity of each channel, or `None` for unbounded channels.
+    capacity: Option<usize>,
 }
 
 impl AllocateBuilder for ProcessBuilder {
@@ -51,6 +55,10 @@ impl AllocateBuilder for ProcessBuilder {
             buzzers,
             counters_send: self.counters_send,
             counters_recv: self.counters_recv,
+            capacity: self.capacity,
+            backlogs: Vec::new(),
+            backlog: 0,
+            statistics: Statistics::default(),
         }
     }
 }
@@ -65,6 +73,12 @@ pub struct Process {
     buzzers: Vec<Buzzer>,
     counters_send: Vec<Sender<usize>>,
     counters_recv: Receiver<usize>,
+    capacity: Option<usize>,
+    // Elements pushed at full bounded channels, awaiting capacity.
+    backlogs: Vec<Box<dyn Backlogged>>,
+    // The number of elements in `backlogs` as of the last call to `release`.
+    backlog: usize,
+    statistics: Statistics,
 }
 
 impl Process {
@@ -72,6 +86,29 @@ impl Process {
     pub fn inner(&mut self) -> &mut Thread { &mut self.inner }
     /// Allocate a list of connected intra-process allocators.
     pub fn new_vector(peers: usize) -> Vec<ProcessBuilder> {
+        Self::new_vector_with_capacity(peers, None)
+    }
+
+    /// Allocate a list of connected intra-process allocators, whose channels hold at most `capacity` elements.
+    ///
+    /// With a capacity, a worker that pushes at a full channel does not block, but holds on to the
+    /// element until the channel has capacity. The worker moves held elements into channels when it
+    /// releases the allocator, and does not park for long while it holds elements. Blocking instead
+    /// could deadlock, as the receiving worker may itself be blocked pushing at the sending worker.
+    ///
+    /// The capacity bounds the elements in each channel, but not those a sending worker holds, which
+    /// are reported by [`Allocate::backlog`]. The report is advisory: a worker can consult it to avoid
+    /// introducing more data while its peers fall behind. `None` indicates unbounded channels.
+    ///
+    /// Dropping an allocator makes a last attempt to send the elements it holds, and discards those
+    /// that do not fit. Peers may need these elements, for example progress updates, to complete
+    /// their dataflows, and so a worker should release the allocator until it holds no elements
+    /// before dropping it, as timely workers do once their dataflows complete.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is `Some(0)`.
+    pub fn new_vector_with_capacity(peers: usize, capacity: Option<usize>) -> Vec<ProcessBuilder> {
+        assert_ne!(capacity, Some(0), "bounded channels require a non-zero capacity");
 
         let mut counters_send = Vec::with_capacity(peers);
         let mut counters_recv = Vec::with_capacity(peers);
@@ -101,16 +138,14 @@ impl Process {
                     channels: channels.clone(),
                     counters_send: counters_send.clone(),
                     counters_recv: recv,
+                    capacity,
                 }
             })
             .collect()
     }
-}
 
-impl Allocate for Process {
-    fn index(&self) -> usize { self.index }
-    fn peers(&self) -> usize { self.peers }
-    fn allocate<T: Any+Send>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<T>>>, Box<dyn Pull<T>>) {
+    /// Constructs several send endpoints and one receive endpoint, moving elements between workers.
+    fn allocate_moved<T: Any+Send>(&mut self, identifier: usize) -> Endpoints<T> {
 
         // this is race-y global initialisation of all channels for all workers, performed by the
         // first worker that enters this critical section
@@ -126,7 +161,10 @@ impl Allocate for Process {
                 let mut pushers = Vec::with_capacity(self.peers);
                 let mut pullers = Vec::with_capacity(self.peers);
                 for buzzer in self.buzzers.iter() {
-                    let (s, r): (Sender<T>, Receiver<T>) = crossbeam_channel::unbounded();
+                    let (s, r): (Sender<T>, Receiver<T>) = match self.capacity {
+                        Some(capacity) => crossbeam_channel::bounded(capacity),
+                        None => crossbeam_channel::unbounded(),
+                    };
                     // TODO: the buzzer in the pusher may be redundant, because we need to buzz post-counter.
                     pushers.push((Pusher { target: s }, buzzer.clone()));
                     pullers.push(Puller { source: r, current: None });
@@ -162,23 +200,64 @@ impl Allocate for Process {
         use crate::allocator::counters::ArcPusher as CountPusher;
         use crate::allocator::counters::Puller as CountPuller;
 
-        let sends =
-        sends.into_iter()
-             .zip(self.counters_send.iter())
-             .map(|((s,b), sender)| CountPusher::new(s, identifier, sender.clone(), b))
-             .map(|s| Box::new(s) as Box<dyn Push<T>>)
-             .collect::<Vec<_>>();
+        let statistics = self.statistics.channel(identifier);
+
+        let sends = if self.capacity.is_some() {
+            let backlogs = &mut self.backlogs;
+            sends.into_iter()
+                 .zip(self.counters_send.iter())
+                 .map(|((s,b), sender)| {
+                     let queue = Rc::new(RefCell::new(VecDeque::new()));
+                     backlogs.push(Box::new(Backlog {
+                         queue: Rc::clone(&queue),
+                         target: s.target.clone(),
+                         identifier,
+                         events: sender.clone(),
+                         buzzer: b.clone(),
+                     }));
+                     let s = BoundedPusher { target: s.target, queue };
+                     let s = CountPusher::new(s, identifier, sender.clone(), b);
+                     Box::new(StatisticsPusher::new(s, Rc::clone(&statistics))) as Box<dyn Push<T>>
+                 })
+                 .collect::<Vec<_>>()
+        }
+        else {
+            sends.into_iter()
+                 .zip(self.counters_send.iter())
+                 .map(|((s,b), sender)| CountPusher::new(s, identifier, sender.clone(), b))
+                 .map(|s| Box::new(StatisticsPusher::new(s, Rc::clone(&statistics))) as Box<dyn Push<T>>)
+                 .collect::<Vec<_>>()
+        };
 
-        let recv = Box::new(CountPuller::new(recv, identifier, self.inner.events().clone())) as Box<dyn Pull<T>>;
+        let recv = CountPuller::new(recv, identifier, self.inner.events().clone());
+        let recv = Box::new(StatisticsPuller::new(recv, statistics)) as Box<dyn Pull<T>>;
 
         (sends, recv)
     }
+}
+
+impl Allocate for Process {
+    fn index(&self) -> usize { self.index }
+    fn peers(&self) -> usize { self.peers }
+    fn allocate<T: Any+Send>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<T>>>, Box<dyn Pull<T>>) {
+        self.allocate_moved(identifier)
+    }
+    fn allocate_local<T: Any+Send>(&mut self, identifier: usize) -> Result<Endpoints<T>, String> {
+        Ok(self.allocate_moved(identifier))
+    }
 
     fn events(&self) -> &Rc<RefCell<Vec<usize>>> {
         self.inner.events()
     }
 
     fn await_events(&self, duration: Option<Duration>) {
+        // Held elements are not announced when peers make capacity, so we must check back.
+        let duration = if self.backlog > 0 {
+            Some(duration.map_or(BACKLOG_RETRY, |duration| std::cmp::min(duration, BACKLOG_RETRY)))
+        }
+        else {
+            duration
+        };
         self.inner.await_events(duration);
     }
 
@@ -188,8 +267,31 @@ impl Allocate for Process {
             events.push(index);
         }
     }
+
+    fn release(&mut self) {
+        self.backlogs.retain_mut(|backlog| !backlog.retired());
+        self.backlog = self.backlogs.iter_mut().map(|backlog| backlog.flush()).sum();
+    }
+
+    fn backlog(&self) -> usize { self.backlog }
+
+    fn statistics(&self) -> HashMap<usize, ChannelStatistics> { self.statistics.snapshot() }
 }
 
+impl Drop for Process {
+    /// Sends the held elements that fit in their channels, and discards the others.
+    fn drop(&mut self) {
+        // Blocking until peers make capacity could deadlock, as they may themselves be dropping
+        // their allocators while holding elements for this worker.
+        for backlog in self.backlogs.iter_mut() {
+            backlog.flush();
+        }
+    }
+}
+
+/// The longest a worker with held elements parks before attempting to send them.
+const BACKLOG_RETRY: Duration = Duration::from_millis(1);
+
 /// The push half of an intra-process channel.
 struct Pusher<T> {
     target: Sender<T>,
@@ -213,6 +315,78 @@ impl<T> Push<T> for Pusher<T> {
     }
 }
 
+/// The push half of a bounded intra-process channel.
+///
+/// Elements that do not fit in the channel are held in `queue`, which the allocator flushes.
+struct BoundedPusher<T> {
+    target: Sender<T>,
+    queue: Rc<RefCell<VecDeque<T>>>,
+}
+
+impl<T> Push<T> for BoundedPusher<T> {
+    #[inline] fn push(&mut self, element: &mut Option<T>) {
+        if let Some(element) = element.take() {
+            let mut queue = self.queue.borrow_mut();
+            // Elements must not overtake those already held.
+            if queue.is_empty() {
+                if let Err(crossbeam_channel::TrySendError::Full(element)) = self.target.try_send(element) {
+                    queue.push_back(element);
+                }
+            }
+            else {
+                queue.push_back(element);
+            }
+        }
+    }
+}
+
+/// Elements held for a bounded channel, type-erased for the allocator.
+trait Backlogged {
+    /// Sends held elements as capacity allows, and returns the number that remain.
+    fn flush(&mut self) -> usize;
+    /// Indicates that no elements are held, and that none can be added.
+    fn retired(&self) -> bool;
+}
+
+/// The elements held by a `BoundedPusher`, and what is needed to announce them once sent.
+struct Backlog<T> {
+    queue: Rc<RefCell<VecDeque<T>>>,
+    target: Sender<T>,
+    identifier: usize,
+    events: Sender<usize>,
+    buzzer: Buzzer,
+}
+
+impl<T> Backlogged for Backlog<T> {
+    fn flush(&mut self) -> usize {
+        let mut queue = self.queue.borrow_mut();
+        let mut sent = false;
+        while let Some(element) = queue.pop_front() {
+            match self.target.try_send(element) {
+                Ok(()) => { sent = true; },
+                Err(crossbeam_channel::TrySendError::Full(element)) => {
+                    queue.push_front(element);
+                    break;
+                },
+                // The remote endpoint has shut down, and will not receive the elements.
+                Err(crossbeam_channel::TrySendError::Disconnected(_)) => {
+                    queue.clear();
+                },
+            }
+        }
+        // Announce the sent elements as their original push did, which may have found no data.
+        if sent {
+            let _ = self.events.send(self.identifier);
+            self.buzzer.buzz();
+        }
+        queue.len()
+    }
+
+    fn retired(&self) -> bool {
+        Rc::strong_count(&self.queue) == 1 && self.queue.borrow().is_empty()
+    }
+}
+
 /// The pull half of an intra-process channel.
 struct Puller<T> {
     current: Option<T>,
@@ -226,3 +400,84 @@ impl<T> Pull<T> for Puller<T> {
         &mut self.current
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Arc, Barrier};
+
+    use crate::allocator::{Allocate, AllocateBuilder, Record};
+    use crate::{Push, Pull};
+    use super::Process;
+
+    #[test]
+    fn bounded_backlog() {
+        let barrier = Arc::new(Barrier::new(2));
+        let guards = Process::new_vector_with_capacity(2, Some(1))
+            .into_iter()
+            .map(|builder| {
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    let mut allocator = builder.build();
+                    let (mut pushers, mut puller) = allocator.allocate::<Record>(0);
+                    if allocator.index() == 0 {
+                        // Pushing at a full channel holds on to elements, rather than blocking.
+                        for round in 0 .. 10 {
+                            pushers[1].send(Record(round));
+                        }
+                        allocator.release();
+                        assert_eq!(allocator.backlog(), 9);
+                        barrier.wait();
+                        while allocator.backlog() > 0 {
+                            allocator.release();
+                        }
+                    }
+                    else {
+                        barrier.wait();
+                        let mut received = Vec::new();
+                        while received.len() < 10 {
+                            allocator.receive();
+                            if let Some(Record(round)) = puller.recv() {
+                                received.push(round);
+                            }
+                        }
+                        assert_eq!(received, (0 .. 10).collect::<Vec<_>>());
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for guard in guards {
+            guard.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn drop_with_backlogs() {
+        let barrier = Arc::new(Barrier::new(2));
+        let guards = Process::new_vector_with_capacity(2, Some(1))
+            .into_iter()
+            .map(|builder| {
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    let mut allocator = builder.build();
+                    let (mut pushers, puller) = allocator.allocate::<Record>(0);
+                    let peer = 1 - allocator.index();
+                    for round in 0 .. 10 {
+                        pushers[peer].send(Record(round));
+                    }
+                    allocator.release();
+                    assert_eq!(allocator.backlog(), 9);
+                    // Each worker holds elements for the other, which does not pull them.
+                    barrier.wait();
+                    drop(allocator);
+                    drop(puller);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for guard in guards {
+            guard.join().unwrap();
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::cell::RefCell;
 use std::time::Duration;
 use std::collections::VecDeque;
 
-use crate::allocator::{Allocate, AllocateBuilder};
+use crate::allocator::{Allocate, AllocateBuilder, Endpoints};
 use crate::allocator::counters::Pusher as CountPusher;
 use crate::allocator::counters::Puller as CountPuller;
 use crate::{Push, Pull};
@@ -33,6 +33,10 @@ impl Allocate for Thread {
         let (pusher, puller) = Thread::new_from(identifier, self.events.clone());
         (vec![Box::new(pusher)], Box::new(puller))
     }
+    fn allocate_local<T: 'static>(&mut self, identifier: usize) -> Result<Endpoints<T>, String> {
+        let (pusher, puller) = Thread::new_from(identifier, self.events.clone());
+        Ok((vec![Box::new(pusher)], Box::new(puller)))
+    }
     fn events(&self) -> &Rc<RefCell<Vec<usize>>> {
         &self.events
     }
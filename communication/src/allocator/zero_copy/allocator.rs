@@ -11,8 +11,10 @@ use crate::networking::MessageHeader;
 use crate::{Allocate, Push, Pull};
 use crate::allocator::{AllocateBuilder, Exchangeable};
 use crate::allocator::canary::Canary;
+use crate::allocator::counters::{ChannelStatistics, Statistics};
 
 use super::bytes_exchange::{BytesPull, SendEndpoint, MergeQueue};
+use super::tcp::PriorityChannels;
 use super::push_pull::{Pusher, PullerInner};
 
 /// Builds an instance of a TcpAllocator.
@@ -27,8 +29,12 @@ pub struct TcpBuilder<A: AllocateBuilder> {
     peers:  usize,                      // number of peer allocators.
     futures:   Vec<Receiver<MergeQueue>>,  // to receive queues to each network thread.
     promises:   Vec<Sender<MergeQueue>>,    // to send queues from each network thread.
+    priority:   Option<PriorityChannels>,   // channels the network threads send first, if enabled.
 }
 
+/// Builders for local threads, and the queues with which to spawn egress and ingress network threads.
+type NetworkBuilders<A> = (Vec<TcpBuilder<A>>, Vec<Vec<Sender<MergeQueue>>>, Vec<Vec<Receiver<MergeQueue>>>);
+
 /// Creates a vector of builders, sharing appropriate state.
 ///
 /// `threads` is the number of workers in a single process, `processes` is the
@@ -45,9 +51,21 @@ pub fn new_vector<A: AllocateBuilder>(
     allocators: Vec<A>,
     my_process: usize,
     processes: usize)
--> (Vec<TcpBuilder<A>>,
-    Vec<Vec<Sender<MergeQueue>>>,
-    Vec<Vec<Receiver<MergeQueue>>>)
+-> NetworkBuilders<A>
+{
+    new_vector_with_priority(allocators, my_process, processes, None)
+}
+
+/// Creates a vector of builders, as [`new_vector`].
+///
+/// If `priority` is supplied, the allocators record the channels they are asked to
+/// prioritize in it, for use by the network threads.
+pub fn new_vector_with_priority<A: AllocateBuilder>(
+    allocators: Vec<A>,
+    my_process: usize,
+    processes: usize,
+    priority: Option<PriorityChannels>)
+-> NetworkBuilders<A>
 {
     let threads = allocators.len();
 
@@ -68,6 +86,7 @@ pub fn new_vector<A: AllocateBuilder>(
                 peers: threads * processes,
                 promises,
                 futures,
+                priority: priority.clone(),
             }})
         .collect();
 
@@ -109,6 +128,8 @@ impl<A: AllocateBuilder> TcpBuilder<A> {
             sends,
             recvs,
             to_local: HashMap::new(),
+            priority: self.priority,
+            statistics: Statistics::default(),
         }
     }
 }
@@ -130,6 +151,9 @@ pub struct TcpAllocator<A: Allocate> {
     sends:      Vec<Rc<RefCell<SendEndpoint<MergeQueue>>>>,     // sends[x] -> goes to process x.
     recvs:      Vec<MergeQueue>,                                // recvs[x] <- from process x.
     to_local:   HashMap<usize, Rc<RefCell<VecDeque<Bytes>>>>,   // to worker-local typed pullers.
+
+    priority:   Option<PriorityChannels>,                       // channels the network threads send first, if enabled.
+    statistics: Statistics,                                     // statistics of serialized messages.
 }
 
 impl<A: Allocate> Allocate for TcpAllocator<A> {
@@ -145,6 +169,7 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
 
         // Result list of boxed pushers.
         let mut pushes = Vec::<Box<dyn Push<T>>>::new();
+        let statistics = self.statistics.channel(identifier);
 
         // Inner exchange allocations.
         let inner_peers = self.inner.peers();
@@ -170,7 +195,7 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
 
                 // create, box, and stash new process_binary pusher.
                 if process_id > self.index / inner_peers { process_id -= 1; }
-                pushes.push(Box::new(Pusher::new(header, self.sends[process_id].clone())));
+                pushes.push(Box::new(Pusher::new(header, self.sends[process_id].clone(), statistics.clone())));
             }
         }
 
@@ -182,10 +207,23 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
 
         use crate::allocator::counters::Puller as CountPuller;
         let canary = Canary::new(identifier, self.canaries.clone());
-        let puller = Box::new(CountPuller::new(PullerInner::new(inner_recv, channel, canary), identifier, self.events().clone()));
+        let puller = Box::new(CountPuller::new(PullerInner::new(inner_recv, channel, canary, statistics), identifier, self.events().clone()));
 
         (pushes, puller, )
     }
+    fn statistics(&self) -> HashMap<usize, ChannelStatistics> {
+        // Combine the statistics of serialized messages with those of the inner allocator.
+        let mut statistics = self.inner.statistics();
+        for (identifier, channel) in self.statistics.snapshot() {
+            *statistics.entry(identifier).or_default() += channel;
+        }
+        statistics
+    }
+    fn prioritize(&mut self, identifier: usize) {
+        if let Some(priority) = &self.priority {
+            priority.insert(identifier);
+        }
+    }
 
     // Perform preparatory work, most likely reading binary buffers from self.recv.
     #[inline(never)]
@@ -12,6 +12,7 @@ use crate::networking::MessageHeader;
 use crate::{Allocate, Push, Pull};
 use crate::allocator::{AllocateBuilder, Exchangeable};
 use crate::allocator::canary::Canary;
+use crate::allocator::counters::{ChannelStatistics, Statistics};
 
 use super::bytes_exchange::{BytesPull, SendEndpoint, MergeQueue};
 
@@ -84,6 +85,7 @@ impl ProcessBuilder {
             sends,
             recvs,
             to_local: HashMap::new(),
+            statistics: Statistics::default(),
         }
     }
 }
@@ -114,6 +116,7 @@ pub struct ProcessAllocator {
     sends:      Vec<Rc<RefCell<SendEndpoint<MergeQueue>>>>, // sends[x] -> goes to thread x.
     recvs:      Vec<MergeQueue>,                            // recvs[x] <- from thread x.
     to_local:   HashMap<usize, Rc<RefCell<VecDeque<Bytes>>>>,          // to worker-local typed pullers.
+    statistics: Statistics,
 }
 
 impl Allocate for ProcessAllocator {
@@ -128,6 +131,7 @@ impl Allocate for ProcessAllocator {
         self.channel_id_bound = Some(identifier);
 
         let mut pushes = Vec::<Box<dyn Push<T>>>::with_capacity(self.peers());
+        let statistics = self.statistics.channel(identifier);
 
         for target_index in 0 .. self.peers() {
 
@@ -141,7 +145,7 @@ impl Allocate for ProcessAllocator {
             };
 
             // create, box, and stash new process_binary pusher.
-            pushes.push(Box::new(Pusher::new(header, self.sends[target_index].clone())));
+            pushes.push(Box::new(Pusher::new(header, self.sends[target_index].clone(), statistics.clone())));
         }
 
         let channel =
@@ -152,7 +156,7 @@ impl Allocate for ProcessAllocator {
 
         use crate::allocator::counters::Puller as CountPuller;
         let canary = Canary::new(identifier, self.canaries.clone());
-        let puller = Box::new(CountPuller::new(Puller::new(channel, canary), identifier, self.events().clone()));
+        let puller = Box::new(CountPuller::new(Puller::new(channel, canary, statistics), identifier, self.events().clone()));
 
         (pushes, puller)
     }
@@ -240,6 +244,8 @@ impl Allocate for ProcessAllocator {
     fn events(&self) -> &Rc<RefCell<Vec<usize>>> {
         &self.events
     }
+    fn statistics(&self) -> HashMap<usize, ChannelStatistics> { self.statistics.snapshot() }
+
     fn await_events(&self, duration: Option<std::time::Duration>) {
         if self.events.borrow().is_empty() {
             if let Some(duration) = duration {
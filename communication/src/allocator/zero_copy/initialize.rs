@@ -1,12 +1,16 @@
 //! Network initialization.
 
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
 use std::sync::Arc;
 use timely_logging::Logger;
 use crate::allocator::process::ProcessBuilder;
+use crate::initialize::{NetworkOptions, ReconnectPolicy};
 use crate::logging::CommunicationEventBuilder;
-use crate::networking::create_sockets;
-use super::tcp::{send_loop, recv_loop};
-use super::allocator::{TcpBuilder, new_vector};
+use crate::networking::{create_sockets, unix_paths};
+use super::reconnect::{AcceptGuard, Link};
+use super::tcp::{send_loop_with, recv_loop_with, PriorityChannels};
+use super::allocator::{TcpBuilder, new_vector_with_priority};
 use super::stream::Stream;
 
 /// Join handles for send and receive threads.
@@ -16,6 +20,8 @@ use super::stream::Stream;
 pub struct CommsGuard {
     send_guards: Vec<::std::thread::JoinHandle<()>>,
     recv_guards: Vec<::std::thread::JoinHandle<()>>,
+    /// Accepts re-established connections, until the send and receive threads complete.
+    accept_guard: Option<AcceptGuard>,
 }
 
 impl Drop for CommsGuard {
@@ -28,12 +34,17 @@ impl Drop for CommsGuard {
             handle.join().expect("Recv thread panic");
         }
         // println!("RECV THREADS JOINED");
+        // The receive threads no longer re-establish connections.
+        self.accept_guard.take();
     }
 }
 
 use crate::logging::CommunicationSetup;
 
 /// Initializes network connections
+///
+/// If the addresses start with [`UNIX_PREFIX`](crate::networking::UNIX_PREFIX), the processes
+/// connect through Unix domain sockets at the paths that follow, rather than through TCP.
 pub fn initialize_networking(
     addresses: Vec<String>,
     my_index: usize,
@@ -43,8 +54,106 @@ pub fn initialize_networking(
 )
 -> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
 {
-    let sockets = create_sockets(addresses, my_index, noisy)?;
-    initialize_networking_from_sockets(sockets, my_index, threads, log_sender)
+    initialize_networking_with_options(addresses, my_index, threads, noisy, NetworkOptions::default(), log_sender)
+}
+
+/// Initializes network connections, as [`initialize_networking`], with `options` for the connections.
+///
+/// If `options.prioritize` is set, messages on channels the workers prioritize are sent ahead of
+/// other messages; otherwise, all messages are sent in the order they were enqueued.
+///
+/// If `options.reconnect` is set, dropped connections are re-established according to the policy.
+/// This process then continues to listen at its address until the returned guard is dropped.
+/// Dropped Unix domain socket connections are not re-established.
+pub fn initialize_networking_with_options(
+    addresses: Vec<String>,
+    my_index: usize,
+    threads: usize,
+    noisy: bool,
+    options: NetworkOptions,
+    log_sender: Arc<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEventBuilder>>+Send+Sync>,
+)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    let NetworkOptions { prioritize, reconnect } = options;
+    if let Some(paths) = unix_paths(&addresses)? {
+        if reconnect.is_some() {
+            return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidInput, "reconnecting requires TCP addresses"));
+        }
+        return initialize_unix_networking(paths, my_index, threads, noisy, prioritize, log_sender);
+    }
+
+    let sockets = create_sockets(addresses.clone(), my_index, noisy)?;
+    let (links, accept_guard) = match reconnect {
+        Some(policy) => links(&sockets, &addresses, my_index, policy)?,
+        None => (sockets.iter().map(|_| None).collect(), None),
+    };
+    initialize_networking_from_links(sockets, links, accept_guard, my_index, threads, prioritize, log_sender)
+}
+
+/// Initializes network connections through Unix domain sockets at `paths`.
+#[cfg(unix)]
+fn initialize_unix_networking(
+    paths: Vec<PathBuf>,
+    my_index: usize,
+    threads: usize,
+    noisy: bool,
+    prioritize: bool,
+    log_sender: Arc<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEventBuilder>>+Send+Sync>,
+)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    let sockets = crate::networking::create_unix_sockets(paths, my_index, noisy)?;
+    let links = sockets.iter().map(|_| None).collect();
+    initialize_networking_from_links(sockets, links, None, my_index, threads, prioritize, log_sender)
+}
+
+/// Initializes network connections through Unix domain sockets, which this platform lacks.
+#[cfg(not(unix))]
+fn initialize_unix_networking(
+    _paths: Vec<PathBuf>,
+    _my_index: usize,
+    _threads: usize,
+    _noisy: bool,
+    _prioritize: bool,
+    _log_sender: Arc<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEventBuilder>>+Send+Sync>,
+)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    Err(::std::io::Error::new(::std::io::ErrorKind::Unsupported, "Unix domain sockets are not supported on this platform"))
+}
+
+/// The link to each process, if connections to it are re-established.
+type Links<S> = Vec<Option<Arc<Link<S>>>>;
+
+/// Creates a link for each socket, and listens for re-established connections from processes with greater indexes.
+fn links(sockets: &[Option<TcpStream>], addresses: &[String], my_index: usize, policy: ReconnectPolicy) -> ::std::io::Result<(Links<TcpStream>, Option<AcceptGuard>)> {
+    let mut links = Vec::with_capacity(sockets.len());
+    for (index, socket) in sockets.iter().enumerate() {
+        let link = match socket {
+            None => None,
+            Some(socket) if index < my_index => {
+                let address = addresses[index].clone();
+                let dial = move || {
+                    let stream = TcpStream::connect(&address)?;
+                    stream.set_nodelay(true)?;
+                    Ok(stream)
+                };
+                Some(Arc::new(Link::dial(policy.clone(), my_index, socket, dial)?))
+            },
+            Some(socket) => Some(Arc::new(Link::accept(policy.clone(), my_index, socket)?)),
+        };
+        links.push(link);
+    }
+
+    let mut accept_guard = None;
+    if my_index + 1 < addresses.len() {
+        let listener = TcpListener::bind(&addresses[my_index][..])?;
+        let accepting = links.iter().enumerate().map(|(index, link)| link.clone().filter(|_| index > my_index)).collect();
+        accept_guard = Some(AcceptGuard::spawn(listener, accepting)?);
+    }
+
+    Ok((links, accept_guard))
 }
 
 /// Initialize send and recv threads from sockets.
@@ -55,9 +164,70 @@ pub fn initialize_networking(
 /// It is important that the `sockets` argument contain sockets for each remote process, in order, and
 /// with position `my_index` set to `None`.
 pub fn initialize_networking_from_sockets<S: Stream + 'static>(
+    sockets: Vec<Option<S>>,
+    my_index: usize,
+    threads: usize,
+    log_sender: Arc<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEventBuilder>>+Send+Sync>,
+)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    let links = sockets.iter().map(|_| None).collect();
+    initialize_networking_from_links(sockets, links, None, my_index, threads, false, log_sender)
+}
+
+/// Initialize send and recv threads from streams to remote processes, keyed by process index.
+///
+/// This method is available for users who establish connections themselves, for example to wrap them
+/// in TLS or to route them through proxies, and skips the connection setup of `initialize_networking`.
+/// The caller guarantees that the streams of all processes form a complete mesh, and this method checks
+/// that `streams` contains exactly one stream for each of the `processes` processes other than `my_index`.
+///
+/// Streams may be of any type implementing [`Stream`]. The send and receive threads of each connection
+/// use separate handles to its stream, obtained with [`Stream::try_clone`]. The resulting builders, each
+/// wrapped in [`GenericBuilder::ZeroCopy`](crate::allocator::GenericBuilder::ZeroCopy), and guard can be
+/// supplied to [`initialize_from`](crate::initialize_from).
+pub fn initialize_networking_from_streams<S, I>(
+    streams: I,
+    my_index: usize,
+    processes: usize,
+    threads: usize,
+    prioritize: bool,
+    log_sender: Arc<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEventBuilder>>+Send+Sync>,
+)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+where
+    S: Stream + 'static,
+    I: IntoIterator<Item=(usize, S)>,
+{
+    let invalid = |message: String| ::std::io::Error::new(::std::io::ErrorKind::InvalidInput, message);
+    if my_index >= processes {
+        return Err(invalid(format!("process {} out of range for {} processes", my_index, processes)));
+    }
+    let mut sockets: Vec<Option<S>> = (0 .. processes).map(|_| None).collect();
+    for (index, stream) in streams {
+        if index == my_index || index >= processes {
+            return Err(invalid(format!("stream to process {} invalid for process {} of {}", index, my_index, processes)));
+        }
+        if sockets[index].replace(stream).is_some() {
+            return Err(invalid(format!("multiple streams to process {}", index)));
+        }
+    }
+    let connected = sockets.iter().filter(|socket| socket.is_some()).count();
+    if connected + 1 < processes {
+        return Err(invalid(format!("streams to {} processes, but {} remote processes", connected, processes - 1)));
+    }
+    let links = sockets.iter().map(|_| None).collect();
+    initialize_networking_from_links(sockets, links, None, my_index, threads, prioritize, log_sender)
+}
+
+/// Initialize send and recv threads from sockets, and the links through which to re-establish them.
+fn initialize_networking_from_links<S: Stream + 'static>(
     mut sockets: Vec<Option<S>>,
+    links: Links<S>,
+    accept_guard: Option<AcceptGuard>,
     my_index: usize,
     threads: usize,
+    prioritize: bool,
     log_sender: Arc<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEventBuilder>>+Send+Sync>,
 )
 -> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
@@ -70,7 +240,8 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
     let processes = sockets.len();
 
     let process_allocators = crate::allocator::process::Process::new_vector(threads);
-    let (builders, promises, futures) = new_vector(process_allocators, my_index, processes);
+    let priority = prioritize.then(PriorityChannels::default);
+    let (builders, promises, futures) = new_vector_with_priority(process_allocators, my_index, processes, priority.clone());
 
     let mut promises_iter = promises.into_iter();
     let mut futures_iter = futures.into_iter();
@@ -79,11 +250,13 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
     let mut recv_guards = Vec::with_capacity(sockets.len());
 
     // for each process, if a stream exists (i.e. not local) ...
-    for (index, stream) in sockets.into_iter().enumerate().filter_map(|(i, s)| s.map(|s| (i, s))) {
+    for ((index, stream), link) in sockets.into_iter().enumerate().zip(links).filter_map(|((i, s), l)| s.map(|s| ((i, s), l))) {
         let remote_recv = promises_iter.next().unwrap();
 
         {
             let log_sender = log_sender.clone();
+            let priority = priority.clone();
+            let link = link.clone();
             let stream = stream.try_clone()?;
             let join_guard =
             ::std::thread::Builder::new()
@@ -96,7 +269,7 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
                         remote: Some(index),
                     });
 
-                    send_loop(stream, remote_recv, my_index, index, logger);
+                    send_loop_with(stream, remote_recv, priority, my_index, index, link, logger);
                 })?;
 
             send_guards.push(join_guard);
@@ -117,12 +290,61 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
                         sender: false,
                         remote: Some(index),
                     });
-                    recv_loop(stream, remote_send, threads * my_index, my_index, index, logger);
+                    recv_loop_with(stream, remote_send, threads * my_index, my_index, index, link, logger);
                 })?;
 
             recv_guards.push(join_guard);
         }
     }
 
-    Ok((builders, CommsGuard { send_guards, recv_guards }))
+    Ok((builders, CommsGuard { send_guards, recv_guards, accept_guard }))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+
+    use crate::allocator::{Allocate, GenericBuilder, Record};
+    use crate::{Push, Pull};
+    use super::initialize_networking_from_streams;
+
+    #[test]
+    fn from_streams() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream1 = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream0, _) = listener.accept().unwrap();
+
+        let handles: Vec<_> = [(0, 1, stream0), (1, 0, stream1)].into_iter().map(|(index, peer, stream)| {
+            std::thread::spawn(move || {
+                let (builders, guard) = initialize_networking_from_streams(vec![(peer, stream)], index, 2, 1, false, Arc::new(|_| None)).unwrap();
+                let builders = builders.into_iter().map(GenericBuilder::ZeroCopy).collect();
+                let guards = crate::initialize_from(builders, Box::new(guard), |mut allocator| {
+                    let (mut pushers, mut puller) = allocator.allocate::<Record>(0);
+                    pushers[1 - allocator.index()].send(Record(allocator.index() as u64));
+                    pushers[1 - allocator.index()].done();
+                    loop {
+                        allocator.receive();
+                        if let Some(record) = puller.recv() { return record.0; }
+                        allocator.release();
+                    }
+                }).unwrap();
+                guards.join().into_iter().map(Result::unwrap).collect::<Vec<_>>()
+            })
+        }).collect();
+
+        let received: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        assert_eq!(received, vec![vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn from_streams_incomplete() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let result = initialize_networking_from_streams(vec![(1, stream)], 0, 3, 1, false, Arc::new(|_| None));
+        assert_eq!(result.err().unwrap().kind(), std::io::ErrorKind::InvalidInput);
+        let result = initialize_networking_from_streams(Vec::<(usize, TcpStream)>::new(), 2, 2, 1, false, Arc::new(|_| None));
+        assert_eq!(result.err().unwrap().kind(), std::io::ErrorKind::InvalidInput);
+    }
 }
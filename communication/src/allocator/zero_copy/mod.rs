@@ -11,6 +11,7 @@
 pub mod bytes_slab;
 pub mod bytes_exchange;
 pub mod tcp;
+pub mod reconnect;
 pub mod allocator;
 pub mod allocator_process;
 pub mod initialize;
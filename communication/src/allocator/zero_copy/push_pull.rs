@@ -1,12 +1,13 @@
 //! Push and Pull implementations wrapping serialized data.
 
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 
 use timely_bytes::arc::Bytes;
 
 use crate::allocator::canary::Canary;
+use crate::allocator::counters::ChannelStatistics;
 use crate::networking::MessageHeader;
 use crate::{Bytesable, Push, Pull};
 
@@ -19,15 +20,17 @@ use super::bytes_exchange::{BytesPush, SendEndpoint};
 pub struct Pusher<T, P: BytesPush> {
     header:     MessageHeader,
     sender:     Rc<RefCell<SendEndpoint<P>>>,
+    statistics: Rc<Cell<ChannelStatistics>>,
     phantom:    ::std::marker::PhantomData<T>,
 }
 
 impl<T, P: BytesPush> Pusher<T, P> {
-    /// Creates a new `Pusher` from a header and shared byte buffer.
-    pub fn new(header: MessageHeader, sender: Rc<RefCell<SendEndpoint<P>>>) -> Pusher<T, P> {
+    /// Creates a new `Pusher` from a header, shared byte buffer, and channel statistics.
+    pub fn new(header: MessageHeader, sender: Rc<RefCell<SendEndpoint<P>>>, statistics: Rc<Cell<ChannelStatistics>>) -> Pusher<T, P> {
         Pusher {
             header,
             sender,
+            statistics,
             phantom:    ::std::marker::PhantomData,
         }
     }
@@ -43,6 +46,7 @@ impl<T: Bytesable, P: BytesPush> Push<T> for Pusher<T, P> {
             self.header.seqno += 1;
             header.length = element.length_in_bytes();
             assert!(header.length > 0);
+            ChannelStatistics::sent(&self.statistics, header.length);
 
             // acquire byte buffer and write header, element.
             let mut borrow = self.sender.borrow_mut();
@@ -68,15 +72,17 @@ pub struct Puller<T> {
     _canary: Canary,
     current: Option<T>,
     receiver: Rc<RefCell<VecDeque<Bytes>>>,    // source of serialized buffers
+    statistics: Rc<Cell<ChannelStatistics>>,
 }
 
 impl<T: Bytesable> Puller<T> {
-    /// Creates a new `Puller` instance from a shared queue.
-    pub fn new(receiver: Rc<RefCell<VecDeque<Bytes>>>, _canary: Canary) -> Puller<T> {
+    /// Creates a new `Puller` instance from a shared queue and channel statistics.
+    pub fn new(receiver: Rc<RefCell<VecDeque<Bytes>>>, _canary: Canary, statistics: Rc<Cell<ChannelStatistics>>) -> Puller<T> {
         Puller {
             _canary,
             current: None,
             receiver,
+            statistics,
         }
     }
 }
@@ -88,7 +94,10 @@ impl<T: Bytesable> Pull<T> for Puller<T> {
         self.receiver
             .borrow_mut()
             .pop_front()
-            .map(T::from_bytes);
+            .map(|bytes| {
+                ChannelStatistics::received(&self.statistics, bytes.len());
+                T::from_bytes(bytes)
+            });
 
         &mut self.current
     }
@@ -105,16 +114,20 @@ pub struct PullerInner<T> {
     _canary: Canary,
     current: Option<T>,
     receiver: Rc<RefCell<VecDeque<Bytes>>>,     // source of serialized buffers
+    statistics: Rc<Cell<ChannelStatistics>>,
 }
 
 impl<T: Bytesable> PullerInner<T> {
-    /// Creates a new `PullerInner` instance from a shared queue.
-    pub fn new(inner: Box<dyn Pull<T>>, receiver: Rc<RefCell<VecDeque<Bytes>>>, _canary: Canary) -> Self {
+    /// Creates a new `PullerInner` instance from a shared queue and channel statistics.
+    ///
+    /// The statistics account only for serialized messages; `inner` should account for its own.
+    pub fn new(inner: Box<dyn Pull<T>>, receiver: Rc<RefCell<VecDeque<Bytes>>>, _canary: Canary, statistics: Rc<Cell<ChannelStatistics>>) -> Self {
         PullerInner {
             inner,
             _canary,
             current: None,
             receiver,
+            statistics,
         }
     }
 }
@@ -132,7 +145,10 @@ impl<T: Bytesable> Pull<T> for PullerInner<T> {
             self.receiver
                 .borrow_mut()
                 .pop_front()
-                .map(T::from_bytes);
+                .map(|bytes| {
+                    ChannelStatistics::received(&self.statistics, bytes.len());
+                    T::from_bytes(bytes)
+                });
 
             &mut self.current
         }
@@ -0,0 +1,301 @@
+//! Re-establishing dropped connections between processes.
+//!
+//! Each connection carries a byte stream in each direction, of which the receiving side counts the
+//! bytes of complete messages. When a connection drops, the receive threads of the two processes
+//! establish a new connection and exchange these counts, and each send thread resumes its stream
+//! from the count its peer reports, replaying bytes it has retained. The counts always fall on
+//! message boundaries, and a partially received message is discarded and replayed whole. If the
+//! bytes to replay are no longer retained, they are lost, and the network threads fail as they
+//! would without reconnection.
+//!
+//! The process with the greater index re-establishes the connection, as it established the first.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread::{JoinHandle, Thread};
+use std::time::{Duration, Instant};
+
+use byteorder::{ReadBytesExt, WriteBytesExt};
+
+use crate::initialize::ReconnectPolicy;
+use crate::networking::HANDSHAKE_MAGIC;
+
+use super::stream::Stream;
+
+/// The byte order of the reconnection handshake, as for the initial handshake.
+type ByteOrder = byteorder::BigEndian;
+
+/// How a link re-establishes its connection.
+enum Role<S> {
+    /// Connect to the remote process, using the supplied function.
+    Dial(Mutex<Box<dyn FnMut() -> io::Result<S> + Send>>),
+    /// Await a connection from the remote process, offered by an [`AcceptGuard`]'s thread.
+    Accept,
+}
+
+/// The state of a link shared by its send and receive threads.
+struct State<S> {
+    /// A handle to the current connection, used to shut it down.
+    current: Option<S>,
+    /// The current connection for the send thread, and the number of bytes the remote received.
+    resume: Option<(S, u64)>,
+    /// A connection offered by the remote process, and the number of bytes it received.
+    offered: Option<(S, u64)>,
+    /// Set if the connection could not be re-established.
+    failed: bool,
+    /// The send thread, to be woken when the connection changes.
+    sender: Option<Thread>,
+}
+
+/// A connection to a remote process, which the network threads re-establish if it drops.
+pub struct Link<S> {
+    policy: ReconnectPolicy,
+    /// The index of this process.
+    index: usize,
+    role: Role<S>,
+    /// The number of times the connection has been re-established.
+    generation: AtomicUsize,
+    state: Mutex<State<S>>,
+    changed: Condvar,
+}
+
+impl<S: Stream> Link<S> {
+    /// A link that re-establishes its connection using `dial`, from the process `index`.
+    pub fn dial<F>(policy: ReconnectPolicy, index: usize, stream: &S, dial: F) -> io::Result<Self>
+    where
+        F: FnMut() -> io::Result<S> + Send + 'static,
+    {
+        Self::new(policy, index, stream, Role::Dial(Mutex::new(Box::new(dial))))
+    }
+
+    /// A link that re-establishes its connection by awaiting an offer from the remote process.
+    pub fn accept(policy: ReconnectPolicy, index: usize, stream: &S) -> io::Result<Self> {
+        Self::new(policy, index, stream, Role::Accept)
+    }
+
+    fn new(policy: ReconnectPolicy, index: usize, stream: &S, role: Role<S>) -> io::Result<Self> {
+        let state = State {
+            current: Some(stream.try_clone()?),
+            resume: None,
+            offered: None,
+            failed: false,
+            sender: None,
+        };
+        Ok(Link {
+            policy,
+            index,
+            role,
+            generation: AtomicUsize::new(0),
+            state: Mutex::new(state),
+            changed: Condvar::new(),
+        })
+    }
+
+    /// The policy of the link.
+    pub fn policy(&self) -> &ReconnectPolicy { &self.policy }
+
+    /// The number of times the connection has been re-established.
+    pub fn generation(&self) -> usize { self.generation.load(Ordering::SeqCst) }
+
+    /// Registers the current thread as the send thread, to be woken when the connection changes.
+    pub fn register_sender(&self) {
+        self.lock().sender = Some(std::thread::current());
+    }
+
+    /// Shuts down the connection, if it is still that of `generation`.
+    ///
+    /// Both network threads then observe errors, and the receive thread re-establishes the connection.
+    pub fn interrupt(&self, generation: usize) {
+        let state = self.lock();
+        if self.generation() == generation {
+            if let Some(current) = state.current.as_ref() {
+                let _ = current.shutdown(Shutdown::Both);
+            }
+        }
+    }
+
+    /// Offers a connection from the remote process, which received `received` bytes.
+    ///
+    /// The current connection is shut down, as the remote process considers it dropped.
+    pub fn offer(&self, stream: S, received: u64) {
+        let mut state = self.lock();
+        if let Some(current) = state.current.as_ref() {
+            let _ = current.shutdown(Shutdown::Both);
+        }
+        state.offered = Some((stream, received));
+        self.changed.notify_all();
+    }
+
+    /// Re-establishes the connection, reporting that this process received `received` bytes.
+    ///
+    /// Called by the receive thread, which continues with the returned connection. The send thread
+    /// is handed the connection, and the number of bytes the remote process received, by [`Link::resume`].
+    pub fn reconnect(&self, received: u64) -> io::Result<S> {
+        if let Some(current) = self.lock().current.take() {
+            let _ = current.shutdown(Shutdown::Both);
+        }
+
+        let result = match &self.role {
+            Role::Dial(dial) => self.redial(&mut **dial.lock().expect("Link mutex poisoned"), received),
+            Role::Accept => self.await_offer(received),
+        };
+
+        let mut state = self.lock();
+        match result.and_then(|(stream, remote)| Ok((stream.try_clone()?, stream.try_clone()?, stream, remote))) {
+            Ok((current, resume, stream, remote)) => {
+                state.current = Some(current);
+                state.resume = Some((resume, remote));
+                self.generation.fetch_add(1, Ordering::SeqCst);
+                self.changed.notify_all();
+                if let Some(sender) = state.sender.as_ref() { sender.unpark(); }
+                Ok(stream)
+            },
+            Err(error) => {
+                state.failed = true;
+                self.changed.notify_all();
+                if let Some(sender) = state.sender.as_ref() { sender.unpark(); }
+                Err(error)
+            },
+        }
+    }
+
+    /// Awaits a connection newer than `generation`, the number of bytes the remote process received,
+    /// and the generation of the connection.
+    ///
+    /// Called by the send thread, which should resume its stream from that number of bytes. Returns
+    /// `None` if the connection could not be re-established.
+    pub fn resume(&self, generation: usize) -> Option<(S, u64, usize)> {
+        let mut state = self.lock();
+        loop {
+            if state.failed { return None; }
+            if self.generation() > generation {
+                if let Some((stream, received)) = state.resume.take() {
+                    return Some((stream, received, self.generation()));
+                }
+            }
+            state = self.changed.wait(state).expect("Link mutex poisoned");
+        }
+    }
+
+    /// Connects to the remote process, with exponential backoff, and exchanges received byte counts.
+    fn redial(&self, dial: &mut dyn FnMut() -> io::Result<S>, received: u64) -> io::Result<(S, u64)> {
+        let mut backoff = self.policy.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let result = dial().and_then(|mut stream| {
+                stream.write_u64::<ByteOrder>(HANDSHAKE_MAGIC)?;
+                stream.write_u64::<ByteOrder>(self.index as u64)?;
+                stream.write_u64::<ByteOrder>(received)?;
+                let remote = stream.read_u64::<ByteOrder>()?;
+                Ok((stream, remote))
+            });
+            match result {
+                Ok(result) => return Ok(result),
+                Err(error) if attempt >= self.policy.attempts => return Err(error),
+                Err(_) => {
+                    std::thread::sleep(backoff);
+                    backoff = std::cmp::min(2 * backoff, self.policy.max_backoff);
+                    attempt += 1;
+                },
+            }
+        }
+    }
+
+    /// Awaits a connection from the remote process, for as long as it would attempt to connect.
+    fn await_offer(&self, received: u64) -> io::Result<(S, u64)> {
+        let deadline = Instant::now() + self.policy.patience();
+        let mut state = self.lock();
+        let (mut stream, remote) = loop {
+            if let Some(offered) = state.offered.take() {
+                break offered;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "remote process did not reconnect"));
+            }
+            state = self.changed.wait_timeout(state, deadline - now).expect("Link mutex poisoned").0;
+        };
+        drop(state);
+        stream.write_u64::<ByteOrder>(received)?;
+        Ok((stream, remote))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<S>> {
+        self.state.lock().expect("Link mutex poisoned")
+    }
+}
+
+impl ReconnectPolicy {
+    /// The longest a process attempts to re-establish a connection.
+    fn patience(&self) -> Duration {
+        let mut backoff = self.initial_backoff;
+        let mut total = Duration::default();
+        for _ in 0 .. self.attempts {
+            total += backoff;
+            backoff = std::cmp::min(2 * backoff, self.max_backoff);
+        }
+        total
+    }
+}
+
+/// A thread accepting connections from remote processes re-establishing their links.
+///
+/// On drop, the guard stops the thread and joins with it.
+pub struct AcceptGuard {
+    /// The address at which the thread listens, to wake it up.
+    address: SocketAddr,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl AcceptGuard {
+    /// Spawns a thread that accepts connections at `listener` and offers them to the links.
+    ///
+    /// The entry at index `i` of `links` is the link to process `i`, if this process awaits its connections.
+    /// Connections that do not present the handshake of a known link are dropped.
+    pub fn spawn(listener: TcpListener, links: Vec<Option<Arc<Link<TcpStream>>>>) -> io::Result<Self> {
+        let mut address = listener.local_addr()?;
+        if address.ip().is_unspecified() {
+            let localhost = if address.is_ipv4() { Ipv4Addr::LOCALHOST.into() } else { Ipv6Addr::LOCALHOST.into() };
+            address.set_ip(localhost);
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop2 = Arc::clone(&stop);
+        let handle = std::thread::Builder::new()
+            .name("timely:accept".to_owned())
+            .spawn(move || accept_loop(listener, links, &stop2))?;
+        Ok(AcceptGuard { address, stop, handle: Some(handle) })
+    }
+}
+
+impl Drop for AcceptGuard {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake up the thread, which observes `stop` after accepting the connection.
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            handle.join().expect("Accept thread panic");
+        }
+    }
+}
+
+/// Accepts connections at `listener` and offers them to `links`, until `stop` is set.
+fn accept_loop(listener: TcpListener, links: Vec<Option<Arc<Link<TcpStream>>>>, stop: &AtomicBool) {
+    for stream in listener.incoming() {
+        if stop.load(Ordering::SeqCst) { break; }
+        let Ok(mut stream) = stream else { continue };
+        let handshake = stream.set_nodelay(true).and_then(|()| {
+            let magic = stream.read_u64::<ByteOrder>()?;
+            let index = stream.read_u64::<ByteOrder>()? as usize;
+            let received = stream.read_u64::<ByteOrder>()?;
+            Ok((magic, index, received))
+        });
+        if let Ok((HANDSHAKE_MAGIC, index, received)) = handshake {
+            if let Some(Some(link)) = links.get(index) {
+                link.offer(stream, received);
+            }
+        }
+    }
+}
@@ -1,15 +1,20 @@
 //! Methods related to reading from and writing to TCP connections
 
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
 use crossbeam_channel::{Sender, Receiver};
 
+use timely_bytes::arc::Bytes;
+
 use crate::networking::MessageHeader;
 
 use super::bytes_slab::BytesSlab;
 use super::bytes_exchange::MergeQueue;
+use super::reconnect::Link;
 use super::stream::Stream;
 
-use timely_logging::Logger;
+use timely_logging::{Logger, TypedLogger};
 
 use crate::logging::{CommunicationEvent, CommunicationEventBuilder, MessageEvent, StateEvent};
 
@@ -30,11 +35,29 @@ fn tcp_panic(context: &'static str, cause: io::Error) -> ! {
 /// receive thread panics with a message that starts with "timely communication error:"
 /// in an attempt to take down the computation and cause the failures to cascade.
 pub fn recv_loop<S>(
+    reader: S,
+    targets: Vec<Receiver<MergeQueue>>,
+    worker_offset: usize,
+    process: usize,
+    remote: usize,
+    logger: Option<Logger<CommunicationEventBuilder>>)
+where
+    S: Stream,
+{
+    recv_loop_with(reader, targets, worker_offset, process, remote, None, logger)
+}
+
+/// Repeatedly reads from a TcpStream and carves out messages, as [`recv_loop`].
+///
+/// If `link` is supplied, the receive thread re-establishes a failed connection through
+/// it, discarding any partially received message, and panics only if that fails.
+pub fn recv_loop_with<S>(
     mut reader: S,
     targets: Vec<Receiver<MergeQueue>>,
     worker_offset: usize,
     process: usize,
     remote: usize,
+    link: Option<Arc<Link<S>>>,
     logger: Option<Logger<CommunicationEventBuilder>>)
 where
     S: Stream,
@@ -46,6 +69,8 @@ where
     let mut targets: Vec<MergeQueue> = targets.into_iter().map(|x| x.recv().expect("Failed to receive MergeQueue")).collect();
 
     let mut buffer = BytesSlab::new(20);
+    // The number of bytes of complete messages received, from which a re-established connection resumes.
+    let mut received = 0u64;
 
     // Where we stash Bytes before handing them off.
     let mut stageds = Vec::with_capacity(targets.len());
@@ -69,14 +94,19 @@ where
 
         // Attempt to read some more bytes into self.buffer.
         let read = match reader.read(buffer.empty()) {
-            Err(x) => tcp_panic("reading data", x),
-            Ok(0) => {
-                tcp_panic(
-                    "reading data",
-                    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "socket closed"),
-                );
-            }
-            Ok(n) => n,
+            Ok(0) => Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "socket closed")),
+            result => result,
+        };
+        let read = match (read, link.as_ref()) {
+            (Ok(n), _) => n,
+            (Err(x), None) => tcp_panic("reading data", x),
+            (Err(_), Some(link)) => {
+                // Discard any partial message, which the remote process will send again.
+                let partial = buffer.valid().len();
+                buffer.extract(partial);
+                reader = link.reconnect(received).unwrap_or_else(|e| tcp_panic("reconnecting", e));
+                continue;
+            },
         };
 
         buffer.make_valid(read);
@@ -87,6 +117,7 @@ where
             // TODO: Consolidate message sequences sent to the same worker?
             let peeled_bytes = header.required_bytes();
             let bytes = buffer.extract(peeled_bytes);
+            received += peeled_bytes as u64;
 
             // Record message receipt.
             logger.as_mut().map(|logger| {
@@ -121,6 +152,158 @@ where
     logger.as_mut().map(|l| l.log(StateEvent { send: false, process, remote, start: false, }));
 }
 
+/// Identifiers of channels whose messages the send loop writes ahead of other messages.
+///
+/// Cloning returns another handle to the same set of channels.
+#[derive(Clone, Default)]
+pub struct PriorityChannels {
+    channels: Arc<Mutex<HashSet<usize>>>,
+}
+
+impl PriorityChannels {
+    /// Indicates that messages on `channel` should be written ahead of other messages.
+    pub fn insert(&self, channel: usize) {
+        self.channels.lock().expect("PriorityChannels mutex poisoned").insert(channel);
+    }
+}
+
+/// The number of bytes of non-priority messages to write before checking for priority messages.
+const BACKLOG_CHUNK_BYTES: usize = 1 << 16;
+
+/// Logs the messages in `bytes`, and writes them to `writer`.
+fn write_messages<S: Stream>(writer: &mut LinkWriter<S>, mut bytes: Bytes, logger: &mut Option<TypedLogger<CommunicationEventBuilder, CommunicationEvent>>) {
+    // Record message sends.
+    logger.as_mut().map(|logger| {
+        let mut offset = 0;
+        while let Some(header) = MessageHeader::try_read(&mut bytes[offset..]) {
+            logger.log(MessageEvent { is_send: true, header, });
+            offset += header.required_bytes();
+        }
+    });
+
+    writer.write(bytes);
+}
+
+/// Writes to a stream, and resumes writing to a re-established stream if a `link` is supplied.
+///
+/// Without a link, failing to write panics. With a link, the writer retains the most recently
+/// written bytes, and resumes from the bytes the remote process reports having received.
+struct LinkWriter<S: Stream> {
+    writer: BufWriter<S>,
+    link: Option<Arc<Link<S>>>,
+    /// The generation of the link that `writer` writes to.
+    generation: usize,
+    /// The number of bytes written, from the start of the first connection.
+    written: u64,
+    /// Recently written bytes, with their offsets, to resume from.
+    retained: VecDeque<(u64, Bytes)>,
+    /// The number of bytes in `retained`.
+    retained_bytes: usize,
+}
+
+impl<S: Stream> LinkWriter<S> {
+    fn new(stream: S, link: Option<Arc<Link<S>>>) -> Self {
+        if let Some(link) = link.as_ref() { link.register_sender(); }
+        LinkWriter {
+            writer: BufWriter::with_capacity(1 << 16, stream),
+            link,
+            generation: 0,
+            written: 0,
+            retained: VecDeque::new(),
+            retained_bytes: 0,
+        }
+    }
+
+    /// Writes `bytes`, which should consist of complete messages.
+    fn write(&mut self, bytes: Bytes) {
+        let offset = self.written;
+        self.written += bytes.len() as u64;
+        let result = match self.link.as_ref() {
+            None => self.writer.write_all(&bytes[..]),
+            Some(_) => {
+                // Retain the bytes at least until recovering from a failed write, which resumes from them.
+                let result = self.writer.write_all(&bytes[..]);
+                self.retained_bytes += bytes.len();
+                self.retained.push_back((offset, bytes));
+                result
+            },
+        };
+        if let Err(error) = result {
+            self.recover("writing data", error);
+        }
+        self.trim();
+    }
+
+    /// Discards the oldest retained bytes, beyond those the policy of the link retains.
+    fn trim(&mut self) {
+        if let Some(link) = self.link.as_ref() {
+            let limit = link.policy().retained_bytes;
+            while self.retained.front().is_some_and(|(_, front)| self.retained_bytes - front.len() >= limit) {
+                let (_, front) = self.retained.pop_front().unwrap();
+                self.retained_bytes -= front.len();
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Err(error) = self.writer.flush() {
+            self.recover("flushing writer", error);
+        }
+    }
+
+    /// Resumes writing to a re-established connection, if the link has one.
+    fn poll(&mut self) {
+        if self.link.as_ref().is_some_and(|link| link.generation() > self.generation) {
+            self.resume();
+        }
+    }
+
+    /// Handles a failure to write, by panicking or resuming on a re-established connection.
+    fn recover(&mut self, context: &'static str, error: io::Error) {
+        match self.link.as_ref() {
+            None => tcp_panic(context, error),
+            Some(link) => {
+                link.interrupt(self.generation);
+                self.resume();
+            },
+        }
+    }
+
+    /// Awaits a re-established connection, and writes the bytes the remote process did not receive.
+    fn resume(&mut self) {
+        let link = self.link.as_ref().expect("resuming without a link");
+        let (stream, received, generation) = link.resume(self.generation).unwrap_or_else(|| {
+            tcp_panic("reconnecting", io::Error::new(io::ErrorKind::NotConnected, "connection could not be re-established"))
+        });
+        self.generation = generation;
+        // Discard bytes buffered for the dropped connection; they are retained if they must be sent.
+        let (_, _) = std::mem::replace(&mut self.writer, BufWriter::with_capacity(1 << 16, stream)).into_parts();
+
+        let oldest = self.retained.front().map(|(offset, _)| *offset).unwrap_or(self.written);
+        if received > self.written || received < oldest {
+            tcp_panic("resuming data", io::Error::new(io::ErrorKind::InvalidData, "bytes to resume from are no longer retained"));
+        }
+        let mut result = Ok(());
+        for (offset, bytes) in self.retained.iter() {
+            let end = offset + bytes.len() as u64;
+            if end > received {
+                let start = received.saturating_sub(*offset) as usize;
+                result = result.and_then(|()| self.writer.write_all(&bytes[start..]));
+            }
+        }
+        if let Err(error) = result.and_then(|()| self.writer.flush()) {
+            self.recover("resuming data", error);
+        }
+    }
+
+    /// Writes the final bytes of the stream, and shuts it down for writing.
+    fn finish(mut self, bytes: Bytes) {
+        self.write(bytes);
+        self.flush();
+        self.writer.get_mut().shutdown(::std::net::Shutdown::Write).unwrap_or_else(|e| tcp_panic("shutting down writer", e));
+    }
+}
+
 /// Repeatedly sends messages into a TcpStream.
 ///
 /// The intended communication pattern is a sequence of (header, message)^* for valid
@@ -130,11 +313,33 @@ where
 /// "timely communication error:" in an attempt to take down the computation and cause the
 /// failures to cascade.
 pub fn send_loop<S: Stream>(
+    writer: S,
+    sources: Vec<Sender<MergeQueue>>,
+    process: usize,
+    remote: usize,
+    logger: Option<Logger<CommunicationEventBuilder>>)
+{
+    send_loop_with(writer, sources, None, process, remote, None, logger)
+}
+
+/// Repeatedly sends messages into a TcpStream, as [`send_loop`].
+///
+/// If `priority` is supplied, messages on its channels are written ahead of messages on
+/// other channels, which wait in a backlog that is written in bounded amounts between checks
+/// for new priority messages. Messages within each channel are written in the order they were
+/// enqueued. Otherwise, or while `priority` is empty, all messages are written in the order
+/// they were enqueued.
+///
+/// If `link` is supplied, the send thread awaits the connection re-established by the receive
+/// thread when writing fails, and resumes writing where the remote process stopped receiving.
+pub fn send_loop_with<S: Stream>(
     // TODO: Maybe we don't need BufWriter with consolidation in writes.
     writer: S,
     sources: Vec<Sender<MergeQueue>>,
+    priority: Option<PriorityChannels>,
     process: usize,
     remote: usize,
+    link: Option<Arc<Link<S>>>,
     logger: Option<Logger<CommunicationEventBuilder>>)
 {
     let mut logger = logger.map(|logger| logger.into_typed::<CommunicationEvent>());
@@ -148,44 +353,77 @@ pub fn send_loop<S: Stream>(
         queue
     }).collect();
 
-    let mut writer = ::std::io::BufWriter::with_capacity(1 << 16, writer);
+    let mut writer = LinkWriter::new(writer, link);
     let mut stash = Vec::new();
+    // Messages not on priority channels, waiting to be written.
+    let mut backlog = VecDeque::new();
+    let mut prioritized = Vec::new();
 
     while !sources.is_empty() {
 
+        writer.poll();
+
         // TODO: Round-robin better, to release resources fairly when overloaded.
         for source in sources.iter_mut() {
             use crate::allocator::zero_copy::bytes_exchange::BytesPull;
             source.drain_into(&mut stash);
         }
 
-        if stash.is_empty() {
+        if stash.is_empty() && backlog.is_empty() {
             // No evidence of records to read, but sources not yet empty (at start of loop).
             // We are going to flush our writer (to move buffered data), double check on the
             // sources for emptiness and wait on a signal only if we are sure that there will
             // still be a signal incoming.
             //
             // We could get awoken by more data, a channel closing, or spuriously perhaps.
-            writer.flush().unwrap_or_else(|e| tcp_panic("flushing writer", e));
+            writer.flush();
             sources.retain(|source| !source.is_complete());
             if !sources.is_empty() {
                 std::thread::park();
             }
         }
         else {
-            // TODO: Could do scatter/gather write here.
-            for mut bytes in stash.drain(..) {
-
-                // Record message sends.
-                logger.as_mut().map(|logger| {
-                    let mut offset = 0;
-                    while let Some(header) = MessageHeader::try_read(&mut bytes[offset..]) {
-                        logger.log(MessageEvent { is_send: true, header, });
-                        offset += header.required_bytes();
+            // Separate messages on priority channels from the others, if there are any.
+            let channels = priority
+                .as_ref()
+                .map(|priority| priority.channels.lock().expect("PriorityChannels mutex poisoned"))
+                .filter(|channels| !channels.is_empty());
+            let single_lane = channels.is_none();
+            if single_lane {
+                backlog.extend(stash.drain(..));
+            }
+            else if let Some(channels) = channels.as_ref() {
+                for mut bytes in stash.drain(..) {
+                    while let Some(header) = MessageHeader::try_read(&mut bytes[..]) {
+                        let message = bytes.extract_to(header.required_bytes());
+                        if channels.contains(&header.channel) {
+                            prioritized.push(message);
+                        }
+                        else {
+                            backlog.push_back(message);
+                        }
                     }
-                });
+                }
+            }
+            ::std::mem::drop(channels);
 
-                writer.write_all(&bytes[..]).unwrap_or_else(|e| tcp_panic("writing data", e));
+            if !prioritized.is_empty() {
+                for bytes in prioritized.drain(..) {
+                    write_messages(&mut writer, bytes, &mut logger);
+                }
+                writer.flush();
+            }
+
+            // TODO: Could do scatter/gather write here.
+            let mut written = 0;
+            while single_lane || written < BACKLOG_CHUNK_BYTES {
+                if let Some(bytes) = backlog.pop_front() {
+                    written += bytes.len();
+                    write_messages(&mut writer, bytes, &mut logger);
+                }
+                else {
+                    break;
+                }
             }
         }
     }
@@ -200,11 +438,164 @@ pub fn send_loop<S: Stream>(
         length:     0,
         seqno:      0,
     };
-    header.write_to(&mut writer).unwrap_or_else(|e| tcp_panic("writing data", e));
-    writer.flush().unwrap_or_else(|e| tcp_panic("flushing writer", e));
-    writer.get_mut().shutdown(::std::net::Shutdown::Write).unwrap_or_else(|e| tcp_panic("shutting down writer", e));
+    let mut bytes = Vec::new();
+    header.write_to(&mut bytes).unwrap_or_else(|e| tcp_panic("writing data", e));
+    writer.finish(Bytes::from(bytes));
     logger.as_mut().map(|logger| logger.log(MessageEvent { is_send: true, header }));
 
     // Log the send thread's end.
     logger.as_mut().map(|l| l.log(StateEvent { send: true, process, remote, start: false, }));
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Read;
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use timely_bytes::arc::Bytes;
+
+    use crate::buzzer::Buzzer;
+    use crate::initialize::ReconnectPolicy;
+    use crate::networking::MessageHeader;
+    use crate::allocator::zero_copy::bytes_exchange::{BytesPull, BytesPush, MergeQueue};
+    use crate::allocator::zero_copy::reconnect::{AcceptGuard, Link};
+    use super::{recv_loop_with, send_loop_with, PriorityChannels};
+
+    /// Serializes a message with an empty payload of `length` bytes.
+    fn message(channel: usize, length: usize) -> Bytes {
+        sequenced(channel, length, 0)
+    }
+
+    /// Serializes a message with an empty payload of `length` bytes and sequence number `seqno`.
+    fn sequenced(channel: usize, length: usize, seqno: usize) -> Bytes {
+        let header = MessageHeader { channel, source: 0, target: 0, length, seqno };
+        let mut bytes = Vec::new();
+        header.write_to(&mut bytes).unwrap();
+        bytes.resize(header.required_bytes(), 0);
+        Bytes::from(bytes)
+    }
+
+    /// Enqueues a data backlog followed by one progress message, and returns the channels
+    /// of the messages in the order the send loop wrote them.
+    fn send(priority: Option<PriorityChannels>) -> Vec<usize> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let writer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut reader, _) = listener.accept().unwrap();
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let guard = std::thread::spawn(move || send_loop_with(writer, vec![sender], priority, 0, 1, None, None));
+
+        let mut queue = receiver.recv().unwrap();
+        let mut messages: Vec<_> = (0 .. 1000).map(|_| message(1, 1 << 10)).collect();
+        messages.push(message(0, 8));
+        queue.extend(messages);
+        drop(queue);
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).unwrap();
+        guard.join().unwrap();
+
+        let mut channels = Vec::new();
+        let mut offset = 0;
+        while let Some(header) = MessageHeader::try_read(&mut bytes[offset..]) {
+            if header.length > 0 { channels.push(header.channel); }
+            offset += header.required_bytes();
+        }
+        channels
+    }
+
+    #[test]
+    fn single_lane() {
+        let channels = send(None);
+        assert_eq!(channels.len(), 1001);
+        assert_eq!(channels.last(), Some(&0));
+    }
+
+    #[test]
+    fn priority_lane() {
+        let priority = PriorityChannels::default();
+        priority.insert(0);
+        let channels = send(Some(priority));
+        assert_eq!(channels.len(), 1001);
+        // The progress message overtakes the whole data backlog.
+        assert_eq!(channels.first(), Some(&0));
+    }
+
+    /// Sends two batches of messages from process 1 to process 0 over links with `retained_bytes`,
+    /// optionally dropping the connection between the batches, and returns the received sequence numbers.
+    fn send_reconnecting(retained_bytes: usize, interrupt: bool) -> Vec<usize> {
+        let policy = ReconnectPolicy {
+            attempts: 20,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(100),
+            retained_bytes,
+        };
+
+        // Process 1 connects to process 0, and re-establishes the connection if it drops.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let stream1 = TcpStream::connect(address).unwrap();
+        let (stream0, _) = listener.accept().unwrap();
+        let link0 = Arc::new(Link::accept(policy.clone(), 0, &stream0).unwrap());
+        let link1 = Arc::new(Link::dial(policy, 1, &stream1, move || TcpStream::connect(address)).unwrap());
+        let accept_guard = AcceptGuard::spawn(listener, vec![None, Some(link0.clone())]).unwrap();
+
+        // Each process sends to the other, and process 0 collects what process 1 sends.
+        let mut threads = Vec::new();
+        let mut queues = Vec::new();
+        let mut received = None;
+        for (process, stream, link) in [(0, stream0, link0), (1, stream1, link1.clone())] {
+            let remote = 1 - process;
+            let (send_loop_queue, send_queue) = crossbeam_channel::unbounded();
+            let (recv_queue, recv_loop_queue) = crossbeam_channel::unbounded();
+            let (writer, link2) = (stream.try_clone().unwrap(), link.clone());
+            threads.push(std::thread::spawn(move || send_loop_with(writer, vec![send_loop_queue], None, process, remote, Some(link2), None)));
+            threads.push(std::thread::spawn(move || recv_loop_with(stream, vec![recv_loop_queue], 0, process, remote, Some(link), None)));
+            let queue = MergeQueue::new(Buzzer::default());
+            recv_queue.send(queue.clone()).unwrap();
+            if process == 0 { received = Some(queue); }
+            queues.push(send_queue.recv().unwrap());
+        }
+
+        let mut queue = queues.pop().unwrap();
+        queue.extend((0 .. 1000).map(|seqno| sequenced(1, 1 << 10, seqno)));
+        if interrupt {
+            std::thread::sleep(Duration::from_millis(50));
+            link1.interrupt(link1.generation());
+        }
+        queue.extend((1000 .. 2000).map(|seqno| sequenced(1, 1 << 10, seqno)));
+        drop(queue);
+        drop(queues);
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        drop(accept_guard);
+        assert_eq!(link1.generation() > 0, interrupt);
+
+        let mut bytes = Vec::new();
+        received.unwrap().drain_into(&mut bytes);
+        let mut seqnos = Vec::new();
+        for mut bytes in bytes {
+            while let Some(header) = MessageHeader::try_read(&mut bytes[..]) {
+                seqnos.push(header.seqno);
+                bytes.extract_to(header.required_bytes());
+            }
+        }
+        seqnos
+    }
+
+    #[test]
+    fn reconnect() {
+        assert_eq!(send_reconnecting(1 << 20, true), (0 .. 2000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reconnect_retaining_nothing() {
+        // Without a dropped connection, retaining no bytes loses no messages.
+        assert_eq!(send_reconnecting(0, false), (0 .. 2000).collect::<Vec<_>>());
+    }
+}
@@ -1,11 +1,11 @@
 //! Initialization logic for a generic instance of the `Allocate` channel allocation trait.
 
 use std::thread;
-#[cfg(feature = "getopts")]
 use std::io::BufRead;
 use std::sync::Arc;
 use std::fmt::{Debug, Formatter};
 use std::any::Any;
+use std::time::Duration;
 
 #[cfg(feature = "getopts")]
 use getopts;
@@ -14,7 +14,7 @@ use timely_logging::Logger;
 use crate::allocator::thread::ThreadBuilder;
 use crate::allocator::{AllocateBuilder, Process, Generic, GenericBuilder};
 use crate::allocator::zero_copy::allocator_process::ProcessBuilder;
-use crate::allocator::zero_copy::initialize::initialize_networking;
+use crate::allocator::zero_copy::initialize::{initialize_networking, initialize_networking_with_options};
 use crate::logging::{CommunicationEventBuilder, CommunicationSetup};
 
 /// Possible configurations for the communication infrastructure.
@@ -26,19 +26,43 @@ pub enum Config {
     Process(usize),
     /// Use one process with an indicated number of threads. Use zero-copy exchange channels.
     ProcessBinary(usize),
+    /// Use one process with an indicated number of threads, and channels of bounded capacity.
+    ///
+    /// See [`Process::new_vector_with_capacity`] for how full channels are handled.
+    ProcessBounded {
+        /// Number of worker threads
+        threads: usize,
+        /// Number of elements each channel can hold
+        capacity: usize,
+    },
     /// Expect multiple processes.
     Cluster {
         /// Number of per-process worker threads
         threads: usize,
         /// Identity of this process
         process: usize,
-        /// Addresses of all processes
+        /// Addresses of all processes, either all TCP addresses or all `unix:` socket paths
         addresses: Vec<String>,
         /// Verbosely report connection process
         report: bool,
         /// Closure to create a new logger for a communication thread
         log_fn: Arc<dyn Fn(CommunicationSetup) -> Option<Logger<CommunicationEventBuilder>> + Send + Sync>,
-    }
+    },
+    /// Expect multiple processes, as [`Config::Cluster`], with options for their network connections.
+    ClusterWithOptions {
+        /// Number of per-process worker threads
+        threads: usize,
+        /// Identity of this process
+        process: usize,
+        /// Addresses of all processes, either all TCP addresses or all `unix:` socket paths
+        addresses: Vec<String>,
+        /// Verbosely report connection process
+        report: bool,
+        /// Options for the network connections between processes
+        options: NetworkOptions,
+        /// Closure to create a new logger for a communication thread
+        log_fn: Arc<dyn Fn(CommunicationSetup) -> Option<Logger<CommunicationEventBuilder>> + Send + Sync>,
+    },
 }
 
 impl Debug for Config {
@@ -47,6 +71,11 @@ impl Debug for Config {
             Config::Thread => write!(f, "Config::Thread()"),
             Config::Process(n) => write!(f, "Config::Process({})", n),
             Config::ProcessBinary(n) => write!(f, "Config::ProcessBinary({})", n),
+            Config::ProcessBounded { threads, capacity } => f
+                .debug_struct("Config::ProcessBounded")
+                .field("threads", threads)
+                .field("capacity", capacity)
+                .finish(),
             Config::Cluster { threads, process, addresses, report, .. } => f
                 .debug_struct("Config::Cluster")
                 .field("threads", threads)
@@ -54,11 +83,58 @@ impl Debug for Config {
                 .field("addresses", addresses)
                 .field("report", report)
                 // TODO: Use `.finish_non_exhaustive()` after rust/#67364 lands
+                .finish(),
+            Config::ClusterWithOptions { threads, process, addresses, report, options, .. } => f
+                .debug_struct("Config::ClusterWithOptions")
+                .field("threads", threads)
+                .field("process", process)
+                .field("addresses", addresses)
+                .field("report", report)
+                .field("options", options)
                 .finish()
         }
     }
 }
 
+/// Options for the network connections between the processes of a cluster.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetworkOptions {
+    /// Send progress messages ahead of data messages
+    pub prioritize: bool,
+    /// Re-establish dropped connections, or fail if `None`
+    pub reconnect: Option<ReconnectPolicy>,
+}
+
+/// How the network threads of a cluster respond to a dropped connection.
+///
+/// The network threads attempt to re-establish the connection, with exponential backoff between
+/// attempts, and resume the byte streams of the connection where the remote process stopped
+/// receiving complete messages. To do so, each send thread retains the most recently sent bytes,
+/// and if the bytes to resume from are no longer retained, the computation fails as it would
+/// without reconnection. The computation also fails if the connection cannot be re-established.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// The number of attempts to re-establish a connection.
+    pub attempts: usize,
+    /// The delay after the first failed attempt, which doubles after each subsequent failed attempt.
+    pub initial_backoff: Duration,
+    /// The longest delay between attempts.
+    pub max_backoff: Duration,
+    /// The number of most recently sent bytes each send thread retains to resume from.
+    pub retained_bytes: usize,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            retained_bytes: 1 << 26,
+        }
+    }
+}
+
 impl Config {
     /// Installs options into a [`getopts::Options`] struct that corresponds
     /// to the parameters in the configuration.
@@ -77,6 +153,9 @@ impl Config {
         opts.optopt("h", "hostfile", "text file whose lines are process addresses", "FILE");
         opts.optflag("r", "report", "reports connection progress");
         opts.optflag("z", "zerocopy", "enable zero-copy for intra-process communication");
+        opts.optopt("", "capacity", "bound intra-process channels to this many messages", "NUM");
+        opts.optflag("", "prioritize", "send progress messages ahead of data messages between processes");
+        opts.optflag("", "reconnect", "re-establish dropped connections between processes");
     }
 
     /// Instantiates a configuration based upon the parsed options in `matches`.
@@ -89,47 +168,41 @@ impl Config {
     /// it is by default.
     #[cfg(feature = "getopts")]
     pub fn from_matches(matches: &getopts::Matches) -> Result<Config, String> {
-        let threads = matches.opt_get_default("w", 1_usize).map_err(|e| e.to_string())?;
-        let process = matches.opt_get_default("p", 0_usize).map_err(|e| e.to_string())?;
-        let processes = matches.opt_get_default("n", 1_usize).map_err(|e| e.to_string())?;
-        let report = matches.opt_present("report");
-        let zerocopy = matches.opt_present("zerocopy");
+        Settings::from_matches(matches)?.build()
+    }
 
-        if processes > 1 {
-            let mut addresses = Vec::new();
-            if let Some(hosts) = matches.opt_str("h") {
-                let file = ::std::fs::File::open(hosts.clone()).map_err(|e| e.to_string())?;
-                let reader = ::std::io::BufReader::new(file);
-                for line in reader.lines().take(processes) {
-                    addresses.push(line.map_err(|e| e.to_string())?);
-                }
-                if addresses.len() < processes {
-                    return Err(format!("could only read {} addresses from {}, but -n: {}", addresses.len(), hosts, processes));
-                }
-            }
-            else {
-                for index in 0..processes {
-                    addresses.push(format!("localhost:{}", 2101 + index));
-                }
-            }
+    /// Instantiates a configuration from environment variables.
+    ///
+    /// The configuration is that of the equivalent options of [`Self::install_options`]:
+    ///
+    /// * `TIMELY_WORKERS`: number of per-process worker threads, as `--threads`.
+    /// * `TIMELY_PROCESS`: identity of this process, as `--process`.
+    /// * `TIMELY_PROCESSES`: number of processes, as `--processes`.
+    /// * `TIMELY_ADDRESSES`: comma-separated process addresses, in place of a hostfile.
+    /// * `TIMELY_HOSTFILE`: text file whose lines are process addresses, as `--hostfile`.
+    /// * `TIMELY_CAPACITY`: bound on the messages of intra-process channels, as `--capacity`.
+    /// * `TIMELY_REPORT`, `TIMELY_ZEROCOPY`, `TIMELY_PRIORITIZE`, `TIMELY_RECONNECT`: `true`
+    ///   or `false`, as the presence of the flag of the same name.
+    ///
+    /// Unset variables take the defaults of their options. An invalid value results in an error
+    /// that names its variable.
+    pub fn from_env() -> Result<Config, String> {
+        Settings::from_env()?.build()
+    }
 
-            assert!(processes == addresses.len());
-            Ok(Config::Cluster {
-                threads,
-                process,
-                addresses,
-                report,
-                log_fn: Arc::new(|_| None),
-            })
-        } else if threads > 1 {
-            if zerocopy {
-                Ok(Config::ProcessBinary(threads))
-            } else {
-                Ok(Config::Process(threads))
-            }
-        } else {
-            Ok(Config::Thread)
-        }
+    /// Constructs a new configuration from the supplied text arguments and environment variables.
+    ///
+    /// As [`Self::from_args`], but settings absent from the arguments are read from the
+    /// environment variables of [`Self::from_env`]. Arguments take precedence over variables.
+    ///
+    /// This method is only available if the `getopts` feature is enabled, which
+    /// it is by default.
+    #[cfg(feature = "getopts")]
+    pub fn from_args_and_env<I: Iterator<Item=String>>(args: I) -> Result<Config, String> {
+        let mut opts = getopts::Options::new();
+        Config::install_options(&mut opts);
+        let matches = opts.parse(args).map_err(|e| e.to_string())?;
+        Settings::from_matches(&matches)?.or(Settings::from_env()?).build()
     }
 
     /// Constructs a new configuration by parsing the supplied text arguments.
@@ -158,6 +231,12 @@ impl Config {
             Config::ProcessBinary(threads) => {
                 Ok((ProcessBuilder::new_vector(threads).into_iter().map(GenericBuilder::ProcessBinary).collect(), Box::new(())))
             },
+            Config::ProcessBounded { capacity: 0, .. } => {
+                Err("bounded channels require a non-zero capacity".to_owned())
+            },
+            Config::ProcessBounded { threads, capacity } => {
+                Ok((Process::new_vector_with_capacity(threads, Some(capacity)).into_iter().map(GenericBuilder::Process).collect(), Box::new(())))
+            },
             Config::Cluster { threads, process, addresses, report, log_fn } => {
                 match initialize_networking(addresses, process, threads, report, log_fn) {
                     Ok((stuff, guard)) => {
@@ -166,10 +245,188 @@ impl Config {
                     Err(err) => Err(format!("failed to initialize networking: {}", err))
                 }
             },
+            Config::ClusterWithOptions { threads, process, addresses, report, options, log_fn } => {
+                match initialize_networking_with_options(addresses, process, threads, report, options, log_fn) {
+                    Ok((stuff, guard)) => {
+                        Ok((stuff.into_iter().map(GenericBuilder::ZeroCopy).collect(), Box::new(guard)))
+                    },
+                    Err(err) => Err(format!("failed to initialize networking: {}", err))
+                }
+            },
+        }
+    }
+}
+
+/// Settings from which to construct a [`Config`], each `None` if unspecified.
+#[derive(Default)]
+struct Settings {
+    threads: Option<usize>,
+    process: Option<usize>,
+    processes: Option<usize>,
+    addresses: Option<Addresses>,
+    capacity: Option<usize>,
+    report: Option<bool>,
+    zerocopy: Option<bool>,
+    prioritize: Option<bool>,
+    reconnect: Option<bool>,
+}
+
+/// The source of the addresses of processes.
+enum Addresses {
+    /// A text file whose lines are process addresses.
+    Hostfile(String),
+    /// The process addresses.
+    List(Vec<String>),
+}
+
+impl Settings {
+    /// Settings from options installed by [`Config::install_options`].
+    #[cfg(feature = "getopts")]
+    fn from_matches(matches: &getopts::Matches) -> Result<Self, String> {
+        let flag = |name| matches.opt_present(name).then_some(true);
+        Ok(Settings {
+            threads: matches.opt_get::<usize>("w").map_err(|e| e.to_string())?,
+            process: matches.opt_get::<usize>("p").map_err(|e| e.to_string())?,
+            processes: matches.opt_get::<usize>("n").map_err(|e| e.to_string())?,
+            addresses: matches.opt_str("h").map(Addresses::Hostfile),
+            capacity: matches.opt_get::<usize>("capacity").map_err(|e| e.to_string())?,
+            report: flag("report"),
+            zerocopy: flag("zerocopy"),
+            prioritize: flag("prioritize"),
+            reconnect: flag("reconnect"),
+        })
+    }
+
+    /// Settings from the environment variables described by [`Config::from_env`].
+    fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|name| match std::env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(error) => Err(format!("{}: {}", name, error)),
+        })
+    }
+
+    /// Settings from the variables described by [`Config::from_env`], whose values `lookup` provides.
+    fn from_lookup<L: Fn(&str)->Result<Option<String>, String>>(lookup: L) -> Result<Self, String> {
+        let addresses = match (lookup("TIMELY_ADDRESSES")?, lookup("TIMELY_HOSTFILE")?) {
+            (Some(_), Some(_)) => return Err("TIMELY_ADDRESSES and TIMELY_HOSTFILE cannot both be set".to_owned()),
+            (Some(list), None) => Some(Addresses::List(list.split(',').map(|address| address.trim().to_owned()).filter(|address| !address.is_empty()).collect())),
+            (None, hostfile) => hostfile.map(Addresses::Hostfile),
+        };
+        Ok(Settings {
+            threads: parse(&lookup, "TIMELY_WORKERS")?,
+            process: parse(&lookup, "TIMELY_PROCESS")?,
+            processes: parse(&lookup, "TIMELY_PROCESSES")?,
+            addresses,
+            capacity: parse(&lookup, "TIMELY_CAPACITY")?,
+            report: parse(&lookup, "TIMELY_REPORT")?,
+            zerocopy: parse(&lookup, "TIMELY_ZEROCOPY")?,
+            prioritize: parse(&lookup, "TIMELY_PRIORITIZE")?,
+            reconnect: parse(&lookup, "TIMELY_RECONNECT")?,
+        })
+    }
+
+    /// Settings from `self`, and from `other` where `self` leaves them unspecified.
+    #[cfg(feature = "getopts")]
+    fn or(self, other: Settings) -> Settings {
+        Settings {
+            threads: self.threads.or(other.threads),
+            process: self.process.or(other.process),
+            processes: self.processes.or(other.processes),
+            addresses: self.addresses.or(other.addresses),
+            capacity: self.capacity.or(other.capacity),
+            report: self.report.or(other.report),
+            zerocopy: self.zerocopy.or(other.zerocopy),
+            prioritize: self.prioritize.or(other.prioritize),
+            reconnect: self.reconnect.or(other.reconnect),
+        }
+    }
+
+    /// The configuration described by the settings, with defaults for unspecified settings.
+    fn build(self) -> Result<Config, String> {
+        let threads = self.threads.unwrap_or(1);
+        let process = self.process.unwrap_or(0);
+        let processes = self.processes.unwrap_or(1);
+        let report = self.report.unwrap_or(false);
+        let zerocopy = self.zerocopy.unwrap_or(false);
+        let options = NetworkOptions {
+            prioritize: self.prioritize.unwrap_or(false),
+            reconnect: self.reconnect.unwrap_or(false).then(ReconnectPolicy::default),
+        };
+        let capacity = self.capacity;
+
+        if processes > 1 {
+            let mut addresses = Vec::new();
+            match self.addresses {
+                Some(Addresses::Hostfile(hosts)) => {
+                    let file = ::std::fs::File::open(hosts.clone()).map_err(|e| e.to_string())?;
+                    let reader = ::std::io::BufReader::new(file);
+                    for line in reader.lines().take(processes) {
+                        addresses.push(line.map_err(|e| e.to_string())?);
+                    }
+                    if addresses.len() < processes {
+                        return Err(format!("could only read {} addresses from {}, but -n: {}", addresses.len(), hosts, processes));
+                    }
+                },
+                Some(Addresses::List(list)) => {
+                    addresses.extend(list.into_iter().take(processes));
+                    if addresses.len() < processes {
+                        return Err(format!("only {} addresses supplied, but -n: {}", addresses.len(), processes));
+                    }
+                },
+                None => {
+                    for index in 0..processes {
+                        addresses.push(format!("localhost:{}", 2101 + index));
+                    }
+                },
+            }
+
+            assert!(processes == addresses.len());
+            crate::networking::unix_paths(&addresses).map_err(|e| e.to_string())?;
+            if options == NetworkOptions::default() {
+                Ok(Config::Cluster {
+                    threads,
+                    process,
+                    addresses,
+                    report,
+                    log_fn: Arc::new(|_| None),
+                })
+            } else {
+                Ok(Config::ClusterWithOptions {
+                    threads,
+                    process,
+                    addresses,
+                    report,
+                    options,
+                    log_fn: Arc::new(|_| None),
+                })
+            }
+        } else if threads > 1 {
+            if zerocopy {
+                Ok(Config::ProcessBinary(threads))
+            } else if let Some(capacity) = capacity {
+                Ok(Config::ProcessBounded { threads, capacity })
+            } else {
+                Ok(Config::Process(threads))
+            }
+        } else {
+            Ok(Config::Thread)
         }
     }
 }
 
+/// Parses the value of the variable `name`, if it is set.
+fn parse<T, L>(lookup: &L, name: &str) -> Result<Option<T>, String>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+    L: Fn(&str)->Result<Option<String>, String>,
+{
+    lookup(name)?
+        .map(|value| value.trim().parse().map_err(|e| format!("invalid value {:?} for {}: {}", value, name, e)))
+        .transpose()
+}
+
 /// Initializes communication and executes a distributed computation.
 ///
 /// This method allocates an `allocator::Generic` for each thread, spawns local worker threads,
@@ -403,3 +660,57 @@ impl<T:Send+'static> Drop for WorkerGuards<T> {
         // println!("WORKER THREADS JOINED");
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use super::{Config, Settings};
+
+    /// Settings from the variables in `vars`.
+    fn settings(vars: &[(&str, &str)]) -> Result<Settings, String> {
+        let vars: HashMap<_, _> = vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        Settings::from_lookup(|name| Ok(vars.get(name).cloned()))
+    }
+
+    #[cfg(feature = "getopts")]
+    #[test]
+    fn env_matches_args() {
+        let from_env = settings(&[
+            ("TIMELY_WORKERS", "2"),
+            ("TIMELY_PROCESS", "1"),
+            ("TIMELY_PROCESSES", "2"),
+            ("TIMELY_ADDRESSES", "host0:2101, host1:2101"),
+            ("TIMELY_PRIORITIZE", "true"),
+        ]).unwrap().build().unwrap();
+        let Config::ClusterWithOptions { threads, process, addresses, report, options, .. } = from_env else { panic!("expected a cluster") };
+        assert_eq!((threads, process, report, options.prioritize, options.reconnect), (2, 1, false, true, None));
+        assert_eq!(addresses, vec!["host0:2101".to_owned(), "host1:2101".to_owned()]);
+
+        let from_env = settings(&[("TIMELY_WORKERS", "4"), ("TIMELY_CAPACITY", "16")]).unwrap().build().unwrap();
+        let from_args = Config::from_args(["-w", "4", "--capacity", "16"].iter().map(|arg| arg.to_string())).unwrap();
+        assert_eq!(format!("{:?}", from_env), format!("{:?}", from_args));
+    }
+
+    #[test]
+    fn env_errors() {
+        let error = settings(&[("TIMELY_WORKERS", "four")]).err().unwrap();
+        assert!(error.contains("TIMELY_WORKERS"), "{}", error);
+        let error = settings(&[("TIMELY_RECONNECT", "yes")]).err().unwrap();
+        assert!(error.contains("TIMELY_RECONNECT"), "{}", error);
+        let error = settings(&[("TIMELY_PROCESSES", "3"), ("TIMELY_ADDRESSES", "host0:2101,host1:2101")]).unwrap().build().err().unwrap();
+        assert!(error.contains("-n: 3"), "{}", error);
+    }
+
+    #[cfg(feature = "getopts")]
+    #[test]
+    fn args_take_precedence() {
+        let mut opts = getopts::Options::new();
+        Config::install_options(&mut opts);
+        let matches = opts.parse(["-w", "3", "--zerocopy"]).unwrap();
+        let env = settings(&[("TIMELY_WORKERS", "2"), ("TIMELY_ZEROCOPY", "false"), ("TIMELY_PROCESS", "1")]).unwrap();
+        let config = Settings::from_matches(&matches).unwrap().or(env).build().unwrap();
+        assert!(matches!(config, Config::ProcessBinary(3)));
+    }
+}
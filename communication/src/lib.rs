@@ -105,7 +105,7 @@ pub mod buzzer;
 
 pub use allocator::Generic as Allocator;
 pub use allocator::{Allocate, Exchangeable};
-pub use initialize::{initialize, initialize_from, Config, WorkerGuards};
+pub use initialize::{initialize, initialize_from, Config, NetworkOptions, ReconnectPolicy, WorkerGuards};
 
 use timely_bytes::arc::Bytes;
 
@@ -140,6 +140,32 @@ pub trait Push<T> {
     /// Pushes `None`, conventionally signalling a flush.
     #[inline]
     fn done(&mut self) { self.push(&mut None); }
+    /// Pushes each element of `iter`, and then signals a flush with `done()`.
+    ///
+    /// Implementors may override this to push elements more efficiently, but should also
+    /// conclude with a flush.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use timely_communication::{Pull, Push};
+    /// use timely_communication::allocator::Thread;
+    ///
+    /// let (mut pusher, mut puller) = Thread::new_from::<u64>(0, Rc::new(RefCell::new(Vec::new())));
+    /// pusher.push_iter(0 .. 3);
+    /// assert_eq!(puller.recv(), Some(0));
+    /// assert_eq!(puller.recv(), Some(1));
+    /// assert_eq!(puller.recv(), Some(2));
+    /// assert_eq!(puller.recv(), None);
+    /// ```
+    #[inline]
+    fn push_iter<I: IntoIterator<Item=T>>(&mut self, iter: I) where Self: Sized {
+        for element in iter {
+            self.send(element);
+        }
+        self.done();
+    }
 }
 
 impl<T, P: ?Sized + Push<T>> Push<T> for Box<P> {
@@ -161,6 +187,9 @@ pub trait Pull<T> {
     /// Takes an `Option<T>` and leaves `None` behind.
     #[inline]
     fn recv(&mut self) -> Option<T> { self.pull().take() }
+    /// Wraps the puller in a [`Peekable`], which can inspect the next element without taking it.
+    #[inline]
+    fn peekable(self) -> Peekable<T, Self> where Self: Sized { Peekable::new(self) }
 }
 
 impl<T, P: ?Sized + Pull<T>> Pull<T> for Box<P> {
@@ -168,6 +197,69 @@ impl<T, P: ?Sized + Pull<T>> Pull<T> for Box<P> {
     fn pull(&mut self) -> &mut Option<T> { (**self).pull() }
 }
 
+/// A puller that can inspect the next element without taking it.
+///
+/// As each call to `pull` replaces the previously pulled element, a puller cannot in general
+/// present an element without also moving past it. A `Peekable` holds on to a pulled element
+/// until it is pulled again.
+///
+/// # Examples
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use timely_communication::{Pull, Push};
+/// use timely_communication::allocator::Thread;
+///
+/// let (mut pusher, puller) = Thread::new_from::<u64>(0, Rc::new(RefCell::new(Vec::new())));
+/// pusher.send(3);
+/// pusher.send(5);
+///
+/// let mut puller = puller.peekable();
+/// assert_eq!(puller.peek(), Some(&3));
+/// assert_eq!(puller.peek(), Some(&3));
+/// assert_eq!(puller.recv(), Some(3));
+/// assert_eq!(puller.peek(), Some(&5));
+/// assert_eq!(puller.recv(), Some(5));
+/// assert_eq!(puller.peek(), None);
+/// ```
+#[derive(Debug)]
+pub struct Peekable<T, P> {
+    puller: P,
+    peeked: Option<T>,
+    current: Option<T>,
+}
+
+impl<T, P: Pull<T>> Peekable<T, P> {
+    /// Wraps `puller`.
+    pub fn new(puller: P) -> Self {
+        Peekable { puller, peeked: None, current: None }
+    }
+
+    /// Returns a reference to the next element, without taking it.
+    ///
+    /// The next call to `pull` or `recv` returns the element.
+    #[inline]
+    pub fn peek(&mut self) -> Option<&T> {
+        if self.peeked.is_none() {
+            self.peeked = self.puller.pull().take();
+        }
+        self.peeked.as_ref()
+    }
+}
+
+impl<T, P: Pull<T>> Pull<T> for Peekable<T, P> {
+    #[inline]
+    fn pull(&mut self) -> &mut Option<T> {
+        match self.peeked.take() {
+            Some(element) => {
+                self.current = Some(element);
+                &mut self.current
+            },
+            None => self.puller.pull(),
+        }
+    }
+}
+
 
 use crossbeam_channel::{Sender, Receiver};
 
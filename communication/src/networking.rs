@@ -1,8 +1,11 @@
 //! Networking code for sending and receiving fixed size `Vec<u8>` between machines.
 
 use std::io;
-use std::io::{Read, Result};
+use std::io::{Read, Result, Write};
 use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread;
 use std::thread::sleep;
@@ -15,11 +18,17 @@ use serde::{Deserialize, Serialize};
 // This constant is sent along immediately after establishing a TCP stream, so
 // that it is easy to sniff out Timely traffic when it is multiplexed with
 // other traffic on the same port.
-const HANDSHAKE_MAGIC: u64 = 0xc2f1fb770118add9;
+pub(crate) const HANDSHAKE_MAGIC: u64 = 0xc2f1fb770118add9;
 
 /// The byte order for writing message headers and stream initialization.
 type ByteOrder = byteorder::BigEndian;
 
+/// The prefix of process addresses that are paths to Unix domain sockets, rather than TCP addresses.
+///
+/// For example, `unix:/tmp/timely-0.sock`. Either all or none of the addresses of a cluster must
+/// have the prefix. The byte streams are the same over either kind of connection.
+pub const UNIX_PREFIX: &str = "unix:";
+
 /// Framing data for each `Vec<u8>` transmission, indicating a typed channel, the source and
 /// destination workers, and the length in bytes.
 // *Warning*: Adding, removing and altering fields requires to adjust the implementation below!
@@ -116,8 +125,7 @@ pub fn start_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bo
             match TcpStream::connect(address) {
                 Ok(mut stream) => {
                     stream.set_nodelay(true).expect("set_nodelay call failed");
-                    stream.write_u64::<ByteOrder>(HANDSHAKE_MAGIC).expect("failed to encode/send handshake magic");
-                    stream.write_u64::<ByteOrder>(my_index as u64).expect("failed to encode/send worker index");
+                    send_handshake(&mut stream, my_index);
                     if noisy { println!("worker {}:\tconnection to worker {}", my_index, index); }
                     break Some(stream);
                 },
@@ -140,18 +148,165 @@ pub fn await_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bo
     for _ in (my_index + 1) .. addresses.len() {
         let mut stream = listener.accept()?.0;
         stream.set_nodelay(true).expect("set_nodelay call failed");
-        let mut buffer = [0u8;16];
-        stream.read_exact(&mut buffer)?;
-        let mut cursor = io::Cursor::new(buffer);
-        let magic = cursor.read_u64::<ByteOrder>().expect("failed to decode magic");
-        if magic != HANDSHAKE_MAGIC {
-            return Err(io::Error::new(io::ErrorKind::InvalidData,
-                "received incorrect timely handshake"));
-        }
-        let identifier = cursor.read_u64::<ByteOrder>().expect("failed to decode worker index") as usize;
+        let identifier = recv_handshake(&mut stream)?;
         results[identifier - my_index - 1] = Some(stream);
         if noisy { println!("worker {}:\tconnection from worker {}", my_index, identifier); }
     }
 
     Ok(results)
 }
+
+/// Writes the handshake that opens a connection from process `my_index`.
+fn send_handshake<W: Write>(stream: &mut W, my_index: usize) {
+    stream.write_u64::<ByteOrder>(HANDSHAKE_MAGIC).expect("failed to encode/send handshake magic");
+    stream.write_u64::<ByteOrder>(my_index as u64).expect("failed to encode/send worker index");
+}
+
+/// Reads the handshake that opens a connection, and returns the index of the connecting process.
+fn recv_handshake<R: Read>(stream: &mut R) -> Result<usize> {
+    let mut buffer = [0u8;16];
+    stream.read_exact(&mut buffer)?;
+    let mut cursor = io::Cursor::new(buffer);
+    let magic = cursor.read_u64::<ByteOrder>().expect("failed to decode magic");
+    if magic != HANDSHAKE_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            "received incorrect timely handshake"));
+    }
+    Ok(cursor.read_u64::<ByteOrder>().expect("failed to decode worker index") as usize)
+}
+
+/// The paths of Unix domain sockets in `addresses`, if they have the [`UNIX_PREFIX`].
+///
+/// Returns `None` if no address has the prefix, and an error if only some do.
+pub fn unix_paths(addresses: &[String]) -> Result<Option<Vec<PathBuf>>> {
+    let paths: Vec<_> = addresses.iter().filter_map(|address| address.strip_prefix(UNIX_PREFIX)).map(PathBuf::from).collect();
+    if paths.is_empty() {
+        Ok(None)
+    }
+    else if paths.len() == addresses.len() {
+        Ok(Some(paths))
+    }
+    else {
+        Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("either all or none of the process addresses must start with {:?}", UNIX_PREFIX)))
+    }
+}
+
+/// Creates Unix domain socket connections from a list of socket paths.
+///
+/// As [`create_sockets`], but with paths in place of host addresses. Process `my_index` binds
+/// a socket at its path, which must not exist, and removes it once all connections are made.
+#[cfg(unix)]
+pub fn create_unix_sockets(paths: Vec<PathBuf>, my_index: usize, noisy: bool) -> Result<Vec<Option<UnixStream>>> {
+
+    let paths1 = Arc::new(paths);
+    let paths2 = paths1.clone();
+
+    let start_task = thread::spawn(move || start_unix_connections(paths1, my_index, noisy));
+    let await_task = thread::spawn(move || await_unix_connections(paths2, my_index, noisy));
+
+    let mut results = start_task.join().unwrap()?;
+    results.push(None);
+    let to_extend = await_task.join().unwrap()?;
+    results.extend(to_extend);
+
+    if noisy { println!("worker {}:\tinitialization complete", my_index) }
+
+    Ok(results)
+}
+
+/// Result contains connections `[0, my_index - 1]`.
+#[cfg(unix)]
+pub fn start_unix_connections(paths: Arc<Vec<PathBuf>>, my_index: usize, noisy: bool) -> Result<Vec<Option<UnixStream>>> {
+    let results = paths.iter().take(my_index).enumerate().map(|(index, path)| {
+        loop {
+            match UnixStream::connect(path) {
+                Ok(mut stream) => {
+                    send_handshake(&mut stream, my_index);
+                    if noisy { println!("worker {}:\tconnection to worker {}", my_index, index); }
+                    break Some(stream);
+                },
+                Err(error) => {
+                    println!("worker {}:\terror connecting to worker {}: {}; retrying", my_index, index, error);
+                    sleep(Duration::from_secs(1));
+                },
+            }
+        }
+    }).collect();
+
+    Ok(results)
+}
+
+/// Result contains connections `[my_index + 1, paths.len() - 1]`.
+#[cfg(unix)]
+pub fn await_unix_connections(paths: Arc<Vec<PathBuf>>, my_index: usize, noisy: bool) -> Result<Vec<Option<UnixStream>>> {
+    let mut results: Vec<_> = (0..(paths.len() - my_index - 1)).map(|_| None).collect();
+    if results.is_empty() { return Ok(results); }
+    let listener = UnixListener::bind(&paths[my_index])?;
+
+    let accepted = ((my_index + 1) .. paths.len()).try_for_each(|_| {
+        let mut stream = listener.accept()?.0;
+        let identifier = recv_handshake(&mut stream)?;
+        results[identifier - my_index - 1] = Some(stream);
+        if noisy { println!("worker {}:\tconnection from worker {}", my_index, identifier); }
+        Ok(())
+    });
+
+    // Remove the socket, as no further connections are expected.
+    drop(listener);
+    std::fs::remove_file(&paths[my_index])?;
+    accepted.map(|()| results)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{unix_paths, UNIX_PREFIX};
+
+    #[test]
+    fn unix_addresses() {
+        let tcp = vec!["localhost:2101".to_owned(), "localhost:2102".to_owned()];
+        assert!(unix_paths(&tcp).unwrap().is_none());
+        let unix = vec![format!("{}/tmp/a.sock", UNIX_PREFIX), format!("{}b.sock", UNIX_PREFIX)];
+        assert_eq!(unix_paths(&unix).unwrap(), Some(vec!["/tmp/a.sock".into(), "b.sock".into()]));
+        let mixed = vec![tcp[0].clone(), unix[1].clone()];
+        assert!(unix_paths(&mixed).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_sockets() {
+        use std::io::{Read, Write};
+        use super::create_unix_sockets;
+
+        let directory = std::env::temp_dir().join(format!("timely-unix-sockets-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let paths: Vec<_> = (0 .. 3).map(|index| directory.join(format!("{}.sock", index))).collect();
+
+        let handles: Vec<_> = (0 .. 3).map(|index| {
+            let paths = paths.clone();
+            std::thread::spawn(move || {
+                let mut sockets = create_unix_sockets(paths, index, false).unwrap();
+                assert!(sockets[index].is_none());
+                // Each process learns the index of each peer from its connection.
+                for socket in sockets.iter_mut().flatten() {
+                    socket.write_all(&[index as u8]).unwrap();
+                }
+                let mut peers = Vec::new();
+                for socket in sockets.iter_mut().flatten() {
+                    let mut buffer = [0u8];
+                    socket.read_exact(&mut buffer).unwrap();
+                    peers.push(buffer[0] as usize);
+                }
+                assert_eq!(peers, (0 .. 3).filter(|peer| *peer != index).collect::<Vec<_>>());
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        // The sockets are removed once all connections are made.
+        assert!(paths.iter().all(|path| !path.exists()));
+        std::fs::remove_dir(&directory).unwrap();
+    }
+}
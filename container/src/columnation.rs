@@ -372,14 +372,22 @@ mod container {
         fn at_capacity(&self) -> bool {
             self.len() == self.capacity()
         }
+        fn preferred_capacity() -> usize {
+            crate::buffer::default_capacity::<T>()
+        }
+        fn preferred_capacity_for(buffer_size_bytes: usize) -> usize {
+            crate::buffer::capacity_for::<T>(buffer_size_bytes)
+        }
         fn ensure_capacity(&mut self, stash: &mut Option<Self>) {
+            self.ensure_capacity_for(stash, Self::preferred_capacity());
+        }
+        fn ensure_capacity_for(&mut self, stash: &mut Option<Self>, capacity: usize) {
             if self.capacity() == 0 {
                 *self = stash.take().unwrap_or_default();
                 self.clear();
             }
-            let preferred = crate::buffer::default_capacity::<T>();
-            if self.capacity() < preferred {
-                self.reserve(preferred - self.capacity());
+            if self.capacity() < capacity {
+                self.reserve(capacity - self.capacity());
             }
         }
     }
@@ -32,14 +32,22 @@ impl<R: Region> SizableContainer for FlatStack<R> {
     fn at_capacity(&self) -> bool {
         self.len() == self.capacity()
     }
+    fn preferred_capacity() -> usize {
+        buffer::default_capacity::<R::Index>()
+    }
+    fn preferred_capacity_for(buffer_size_bytes: usize) -> usize {
+        buffer::capacity_for::<R::Index>(buffer_size_bytes)
+    }
     fn ensure_capacity(&mut self, stash: &mut Option<Self>) {
+        self.ensure_capacity_for(stash, Self::preferred_capacity());
+    }
+    fn ensure_capacity_for(&mut self, stash: &mut Option<Self>, capacity: usize) {
         if self.capacity() == 0 {
             *self = stash.take().unwrap_or_default();
             self.clear();
         }
-        let preferred = buffer::default_capacity::<R::Index>();
-        if self.capacity() < preferred {
-            self.reserve(preferred - self.capacity());
+        if self.capacity() < capacity {
+            self.reserve(capacity - self.capacity());
         }
     }
 }
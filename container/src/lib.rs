@@ -2,7 +2,8 @@
 
 #![forbid(missing_docs)]
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
 
 pub mod columnation;
 pub mod flatcontainer;
@@ -66,12 +67,115 @@ pub trait Container: Default {
 pub trait SizableContainer: Container {
     /// Indicates that the container is "full" and should be shipped.
     fn at_capacity(&self) -> bool;
+    /// The number of elements the container aims to hold before it is at capacity, for the
+    /// default buffer size [`buffer::BUFFER_SIZE_BYTES`].
+    ///
+    /// The default implementation returns [`buffer::BUFFER_SIZE_BYTES`], which is the capacity
+    /// for byte-sized elements, and containers that know the size of their elements should
+    /// override it.
+    ///
+    /// # Examples
+    ///
+    /// A container that prefers to hold three elements, which container builders respect.
+    /// ```
+    /// use timely_container::{CapacityContainerBuilder, Container, ContainerBuilder, PushInto, SizableContainer};
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct Triples(Vec<u64>);
+    ///
+    /// impl Container for Triples {
+    ///     type ItemRef<'a> = &'a u64;
+    ///     type Item<'a> = u64;
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn clear(&mut self) { self.0.clear() }
+    ///     type Iter<'a> = std::slice::Iter<'a, u64>;
+    ///     fn iter(&self) -> Self::Iter<'_> { self.0.iter() }
+    ///     type DrainIter<'a> = std::vec::Drain<'a, u64>;
+    ///     fn drain(&mut self) -> Self::DrainIter<'_> { self.0.drain(..) }
+    /// }
+    ///
+    /// impl SizableContainer for Triples {
+    ///     fn at_capacity(&self) -> bool { self.0.len() == self.0.capacity() }
+    ///     fn preferred_capacity() -> usize { 3 }
+    ///     fn ensure_capacity(&mut self, stash: &mut Option<Self>) {
+    ///         self.ensure_capacity_for(stash, Self::preferred_capacity());
+    ///     }
+    ///     fn ensure_capacity_for(&mut self, _stash: &mut Option<Self>, capacity: usize) {
+    ///         if self.0.capacity() < capacity {
+    ///             self.0.reserve_exact(capacity - self.0.capacity());
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl PushInto<u64> for Triples {
+    ///     fn push_into(&mut self, item: u64) { self.0.push(item) }
+    /// }
+    ///
+    /// fn lengths(items: u64) -> Vec<usize> {
+    ///     let mut builder = CapacityContainerBuilder::<Triples>::default();
+    ///     for item in 0 .. items {
+    ///         builder.push_into(item);
+    ///     }
+    ///     let mut lengths = Vec::new();
+    ///     while let Some(container) = builder.finish() {
+    ///         lengths.push(container.len());
+    ///     }
+    ///     lengths
+    /// }
+    ///
+    /// assert_eq!(lengths(7), vec![3, 3, 1]);
+    ///
+    /// // Builders scale the preference to the buffer size of the thread.
+    /// use timely_container::buffer::{set_buffer_size_bytes, BUFFER_SIZE_BYTES};
+    /// set_buffer_size_bytes(Some(2 * BUFFER_SIZE_BYTES));
+    /// assert_eq!(lengths(7), vec![6, 1]);
+    /// ```
+    fn preferred_capacity() -> usize {
+        buffer::BUFFER_SIZE_BYTES
+    }
+    /// The number of elements the container aims to hold before it is at capacity, for buffers
+    /// of `buffer_size_bytes` bytes.
+    ///
+    /// Container builders size the containers they fill to this capacity. The default
+    /// implementation scales [`Self::preferred_capacity`] by the ratio of `buffer_size_bytes`
+    /// to [`buffer::BUFFER_SIZE_BYTES`], and returns at least one.
+    fn preferred_capacity_for(buffer_size_bytes: usize) -> usize {
+        if buffer_size_bytes == buffer::BUFFER_SIZE_BYTES {
+            Self::preferred_capacity()
+        } else {
+            std::cmp::max(1, Self::preferred_capacity().saturating_mul(buffer_size_bytes) / buffer::BUFFER_SIZE_BYTES)
+        }
+    }
     /// Restores `self` to its desired capacity, if it has one.
     ///
     /// The `stash` argument is available, and may have the intended capacity.
     /// However, it may be non-empty, and may be of the wrong capacity. The
     /// method should guard against these cases.
     fn ensure_capacity(&mut self, stash: &mut Option<Self>);
+    /// Restores `self` to a capacity of `capacity` elements, if it has a desired capacity.
+    ///
+    /// Container builders call this method with [`Self::preferred_capacity_for`] the buffer size
+    /// they read from [`buffer::buffer_size_bytes`] when they were constructed. The `stash`
+    /// argument is as for [`Self::ensure_capacity`]. The default implementation ignores
+    /// `capacity` and calls [`Self::ensure_capacity`].
+    fn ensure_capacity_for(&mut self, stash: &mut Option<Self>, capacity: usize) {
+        let _ = capacity;
+        self.ensure_capacity(stash);
+    }
+}
+
+/// A container whose elements can be modified in place.
+///
+/// Modifying elements must not change the number of elements in the container.
+pub trait IterMutContainer: Container {
+    /// The type of elements when modifying the container.
+    type ItemMut<'a> where Self: 'a;
+
+    /// Iterator type when modifying the container.
+    type IterMut<'a>: Iterator<Item=Self::ItemMut<'a>> where Self: 'a;
+
+    /// Returns an iterator that modifies the contents of this container.
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
 }
 
 /// A container that can absorb items of a specific type.
@@ -116,6 +220,25 @@ pub trait ContainerBuilder: Default + 'static {
     /// be called repeatedly until it returns `None`.
     #[must_use]
     fn finish(&mut self) -> Option<&mut Self::Container>;
+    /// Indicates a good moment to release resources, such as allocations retained for reuse.
+    ///
+    /// The caller should call [`Self::finish`] until it returns `None` first, and the builder
+    /// must not discard data it has not yet produced. The default implementation does nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely_container::{CapacityContainerBuilder, ContainerBuilder, PushInto};
+    ///
+    /// let mut builder = CapacityContainerBuilder::<Vec<u64>>::default();
+    /// builder.push_into(1);
+    /// assert_eq!(builder.finish(), Some(&mut vec![1]));
+    /// assert!(builder.finish().is_none());
+    /// builder.relax();
+    /// // The builder allocates again when next pushed at.
+    /// builder.push_into(2);
+    /// assert_eq!(builder.finish(), Some(&mut vec![2]));
+    /// ```
+    fn relax(&mut self) { }
     /// Partitions `container` among `builders`, using the function `index` to direct items.
     fn partition<I>(container: &mut Self::Container, builders: &mut [Self], mut index: I)
     where
@@ -143,7 +266,10 @@ pub trait LengthPreservingContainerBuilder : ContainerBuilder { }
 /// across [`Self::finish`] to maintain a low memory footprint.
 ///
 /// Maintains FIFO order.
-#[derive(Default, Debug)]
+///
+/// Containers are sized for the buffer size of [`buffer::buffer_size_bytes`] at the time the
+/// builder is constructed.
+#[derive(Debug)]
 pub struct CapacityContainerBuilder<C>{
     /// Container that we're writing to.
     current: C,
@@ -151,13 +277,26 @@ pub struct CapacityContainerBuilder<C>{
     empty: Option<C>,
     /// Completed containers pending to be sent.
     pending: VecDeque<C>,
+    /// The buffer size in bytes to size containers for.
+    buffer_size_bytes: usize,
+}
+
+impl<C: Default> Default for CapacityContainerBuilder<C> {
+    fn default() -> Self {
+        Self {
+            current: C::default(),
+            empty: None,
+            pending: VecDeque::default(),
+            buffer_size_bytes: buffer::buffer_size_bytes(),
+        }
+    }
 }
 
 impl<T, C: SizableContainer + PushInto<T>> PushInto<T> for CapacityContainerBuilder<C> {
     #[inline]
     fn push_into(&mut self, item: T) {
         // Ensure capacity
-        self.current.ensure_capacity(&mut self.empty);
+        self.current.ensure_capacity_for(&mut self.empty, C::preferred_capacity_for(self.buffer_size_bytes));
 
         // Push item
         self.current.push(item);
@@ -169,6 +308,49 @@ impl<T, C: SizableContainer + PushInto<T>> PushInto<T> for CapacityContainerBuil
     }
 }
 
+impl<C: SizableContainer> CapacityContainerBuilder<C> {
+    /// Pushes all of `items`, producing the same containers as pushing each item in turn.
+    ///
+    /// Capacity is restored only when starting a container, rather than before each item,
+    /// which assumes that [`SizableContainer::ensure_capacity_for`] has no effect on a
+    /// container it has already restored and that is not at capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely_container::{CapacityContainerBuilder, ContainerBuilder, PushInto};
+    /// use timely_container::buffer::default_capacity;
+    ///
+    /// let items = 0 .. 2 * default_capacity::<u64>() as u64 + 5;
+    /// let mut bulk = CapacityContainerBuilder::<Vec<u64>>::default();
+    /// let mut single = CapacityContainerBuilder::<Vec<u64>>::default();
+    /// bulk.extend_into(items.clone());
+    /// items.for_each(|item| single.push_into(item));
+    /// while let Some(container) = single.finish() {
+    ///     assert_eq!(bulk.finish(), Some(container));
+    /// }
+    /// assert!(bulk.finish().is_none());
+    /// ```
+    pub fn extend_into<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        C: PushInto<I::Item>,
+    {
+        let mut items = items.into_iter();
+        while let Some(item) = items.next() {
+            self.current.ensure_capacity_for(&mut self.empty, C::preferred_capacity_for(self.buffer_size_bytes));
+            self.current.push(item);
+            // Fill the container without restoring its capacity between items.
+            while !self.current.at_capacity() {
+                match items.next() {
+                    Some(item) => self.current.push(item),
+                    None => return,
+                }
+            }
+            self.pending.push_back(std::mem::take(&mut self.current));
+        }
+    }
+}
+
 impl<C: Container + Clone + 'static> ContainerBuilder for CapacityContainerBuilder<C> {
     type Container = C;
 
@@ -190,10 +372,228 @@ impl<C: Container + Clone + 'static> ContainerBuilder for CapacityContainerBuild
         self.empty = self.pending.pop_front();
         self.empty.as_mut()
     }
+
+    fn relax(&mut self) {
+        self.empty = None;
+        if self.current.is_empty() {
+            self.current = Default::default();
+        }
+        self.pending.shrink_to_fit();
+    }
 }
 
 impl<C: Container + Clone + 'static> LengthPreservingContainerBuilder for CapacityContainerBuilder<C> { }
 
+/// A container builder that removes duplicate items from each container it builds.
+///
+/// Items are chunked as by [`CapacityContainerBuilder`], and each container is deduplicated
+/// before it is made available. Deduplication only happens within a single container: the
+/// same item may appear in several extracted containers, and removing those duplicates
+/// requires a stateful operator. Each container keeps the first occurrence of each item, in the
+/// order the items were pushed.
+///
+/// As it changes the number of items, this builder does not implement
+/// [`LengthPreservingContainerBuilder`].
+///
+/// # Examples
+/// ```
+/// use timely_container::{ContainerBuilder, DedupContainerBuilder, PushInto};
+///
+/// let mut builder = DedupContainerBuilder::default();
+/// for item in [3, 1, 1, 2, 3, 3] {
+///     builder.push_into(item);
+/// }
+/// assert_eq!(builder.finish(), Some(&mut vec![3, 1, 2]));
+/// assert!(builder.finish().is_none());
+/// ```
+#[derive(Debug)]
+pub struct DedupContainerBuilder<T> {
+    /// Container that we're writing to.
+    current: Vec<T>,
+    /// Empty allocation.
+    empty: Option<Vec<T>>,
+    /// Completed containers pending to be sent.
+    pending: VecDeque<Vec<T>>,
+    /// Scratch space to deduplicate containers.
+    distinct: HashSet<T>,
+    /// The buffer size in bytes to size containers for.
+    buffer_size_bytes: usize,
+}
+
+impl<T> Default for DedupContainerBuilder<T> {
+    fn default() -> Self {
+        Self {
+            current: Vec::default(),
+            empty: None,
+            pending: VecDeque::default(),
+            distinct: HashSet::default(),
+            buffer_size_bytes: buffer::buffer_size_bytes(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> DedupContainerBuilder<T> {
+    /// Deduplicates the current container and moves it to the pending containers.
+    fn seal(&mut self) {
+        let distinct = &mut self.distinct;
+        self.current.retain(|item| distinct.insert(item.clone()));
+        distinct.clear();
+        self.pending.push_back(std::mem::take(&mut self.current));
+    }
+}
+
+impl<T: Hash + Eq + Clone> PushInto<T> for DedupContainerBuilder<T> {
+    #[inline]
+    fn push_into(&mut self, item: T) {
+        self.current.ensure_capacity_for(&mut self.empty, Vec::<T>::preferred_capacity_for(self.buffer_size_bytes));
+        self.current.push(item);
+        if self.current.at_capacity() {
+            self.seal();
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone + 'static> ContainerBuilder for DedupContainerBuilder<T> {
+    type Container = Vec<T>;
+
+    #[inline]
+    fn extract(&mut self) -> Option<&mut Vec<T>> {
+        if let Some(container) = self.pending.pop_front() {
+            self.empty = Some(container);
+            self.empty.as_mut()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn finish(&mut self) -> Option<&mut Vec<T>> {
+        if !self.current.is_empty() {
+            self.seal();
+        }
+        self.empty = self.pending.pop_front();
+        self.empty.as_mut()
+    }
+
+    fn relax(&mut self) {
+        self.empty = None;
+        if self.current.is_empty() {
+            self.current = Default::default();
+        }
+        self.pending.shrink_to_fit();
+        self.distinct.shrink_to_fit();
+    }
+}
+
+/// A container builder that drops items equal to the item pushed immediately before them.
+///
+/// Items are chunked as by [`CapacityContainerBuilder`], in the order they are pushed, and an item
+/// is dropped if it equals the previous item pushed into the builder, even if that item is in an
+/// earlier container. [`ContainerBuilder::finish`] ends the sequence of items, and the first item
+/// pushed after it is always kept.
+///
+/// As it changes the number of items, this builder does not implement
+/// [`LengthPreservingContainerBuilder`].
+///
+/// # Examples
+/// ```
+/// use timely_container::{AdjacentDedupBuilder, ContainerBuilder, PushInto};
+///
+/// let mut builder = AdjacentDedupBuilder::<Vec<usize>, _>::default();
+/// for item in [1, 1, 2, 3, 3, 1] {
+///     builder.push_into(item);
+/// }
+/// assert_eq!(builder.finish().unwrap(), &vec![1, 2, 3, 1]);
+/// assert!(builder.finish().is_none());
+///
+/// // Items after `finish` are not compared with those before it.
+/// builder.push_into(1);
+/// assert_eq!(builder.finish().unwrap(), &vec![1]);
+/// assert!(builder.finish().is_none());
+///
+/// // Items are compared with the last item of a full container.
+/// let capacity = timely_container::buffer::default_capacity::<usize>();
+/// for item in 0 .. capacity {
+///     builder.push_into(item);
+/// }
+/// builder.push_into(capacity - 1);
+/// assert_eq!(builder.extract().unwrap().len(), capacity);
+/// assert!(builder.finish().is_none());
+/// ```
+#[derive(Debug)]
+pub struct AdjacentDedupBuilder<C, T> {
+    /// Container that we're writing to.
+    current: C,
+    /// Empty allocation.
+    empty: Option<C>,
+    /// Completed containers pending to be sent.
+    pending: VecDeque<C>,
+    /// The item last pushed since the last call to `finish`.
+    last: Option<T>,
+    /// The buffer size in bytes to size containers for.
+    buffer_size_bytes: usize,
+}
+
+impl<C: Default, T> Default for AdjacentDedupBuilder<C, T> {
+    fn default() -> Self {
+        Self {
+            current: C::default(),
+            empty: None,
+            pending: VecDeque::default(),
+            last: None,
+            buffer_size_bytes: buffer::buffer_size_bytes(),
+        }
+    }
+}
+
+impl<T: PartialEq + Clone, C: SizableContainer + PushInto<T>> PushInto<T> for AdjacentDedupBuilder<C, T> {
+    #[inline]
+    fn push_into(&mut self, item: T) {
+        if self.last.as_ref() == Some(&item) {
+            return;
+        }
+        self.last = Some(item.clone());
+
+        self.current.ensure_capacity_for(&mut self.empty, C::preferred_capacity_for(self.buffer_size_bytes));
+        self.current.push(item);
+        if self.current.at_capacity() {
+            self.pending.push_back(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+impl<C: Container + Clone + 'static, T: 'static> ContainerBuilder for AdjacentDedupBuilder<C, T> {
+    type Container = C;
+
+    #[inline]
+    fn extract(&mut self) -> Option<&mut C> {
+        if let Some(container) = self.pending.pop_front() {
+            self.empty = Some(container);
+            self.empty.as_mut()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn finish(&mut self) -> Option<&mut C> {
+        self.last = None;
+        if !self.current.is_empty() {
+            self.pending.push_back(std::mem::take(&mut self.current));
+        }
+        self.empty = self.pending.pop_front();
+        self.empty.as_mut()
+    }
+
+    fn relax(&mut self) {
+        self.empty = None;
+        if self.current.is_empty() {
+            self.current = Default::default();
+        }
+        self.pending.shrink_to_fit();
+    }
+}
+
 impl<T> Container for Vec<T> {
     type ItemRef<'a> = &'a T where T: 'a;
     type Item<'a> = T where T: 'a;
@@ -225,16 +625,44 @@ impl<T> SizableContainer for Vec<T> {
     fn at_capacity(&self) -> bool {
         self.len() == self.capacity()
     }
+    fn preferred_capacity() -> usize {
+        buffer::default_capacity::<T>()
+    }
+    fn preferred_capacity_for(buffer_size_bytes: usize) -> usize {
+        buffer::capacity_for::<T>(buffer_size_bytes)
+    }
     fn ensure_capacity(&mut self, stash: &mut Option<Self>) {
-        if self.capacity() == 0 {
-            *self = stash.take().unwrap_or_default();
-            self.clear();
-        }
-        let preferred = buffer::default_capacity::<T>();
-        if self.capacity() < preferred {
-            self.reserve(preferred - self.capacity());
+        reserve_preferred(self, stash, Self::preferred_capacity());
+    }
+    fn ensure_capacity_for(&mut self, stash: &mut Option<Self>, capacity: usize) {
+        reserve_preferred(self, stash, capacity);
+    }
+}
+
+/// Restores `vec` to a capacity of `preferred` elements, reusing the allocation in `stash` if
+/// it is not larger.
+fn reserve_preferred<T>(vec: &mut Vec<T>, stash: &mut Option<Vec<T>>, preferred: usize) {
+    if vec.capacity() == 0 {
+        // Stashed allocations may have been returned by others, and those larger than
+        // preferred would produce oversized containers.
+        if let Some(stashed) = stash.take().filter(|stashed| stashed.capacity() <= preferred) {
+            *vec = stashed;
+            vec.clear();
         }
     }
+    if vec.capacity() < preferred {
+        vec.reserve_exact(preferred - vec.capacity());
+    }
+}
+
+impl<T> IterMutContainer for Vec<T> {
+    type ItemMut<'a> = &'a mut T where T: 'a;
+
+    type IterMut<'a> = std::slice::IterMut<'a, T> where Self: 'a;
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.as_mut_slice().iter_mut()
+    }
 }
 
 impl<T> PushInto<T> for Vec<T> {
@@ -341,23 +769,131 @@ mod arc {
     }
 }
 
+mod option {
+    use crate::Container;
+
+    /// An optional container, with no elements when `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely_container::Container;
+    ///
+    /// let mut none: Option<Vec<u64>> = Default::default();
+    /// assert!(none.is_empty());
+    /// assert_eq!(none.drain().count(), 0);
+    ///
+    /// let mut some = Some(vec![1, 2, 3]);
+    /// assert_eq!(some.len(), 3);
+    /// assert_eq!(some.drain().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    impl<C: Container> Container for Option<C> {
+        type ItemRef<'a> = C::ItemRef<'a> where Self: 'a;
+        type Item<'a> = C::Item<'a> where Self: 'a;
+
+        fn len(&self) -> usize {
+            self.as_ref().map_or(0, C::len)
+        }
+
+        fn is_empty(&self) -> bool {
+            self.as_ref().map_or(true, C::is_empty)
+        }
+
+        fn clear(&mut self) {
+            if let Some(inner) = self {
+                inner.clear();
+            }
+        }
+
+        type Iter<'a> = std::iter::Flatten<std::option::IntoIter<C::Iter<'a>>> where Self: 'a;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.as_ref().map(C::iter).into_iter().flatten()
+        }
+
+        type DrainIter<'a> = std::iter::Flatten<std::option::IntoIter<C::DrainIter<'a>>> where Self: 'a;
+
+        fn drain(&mut self) -> Self::DrainIter<'_> {
+            self.as_mut().map(C::drain).into_iter().flatten()
+        }
+    }
+}
+
 pub mod buffer {
     //! Functionality related to calculating default buffer sizes
 
+    use std::cell::Cell;
+
     /// The upper limit for buffers to allocate, size in bytes. [default_capacity] converts
     /// this to size in elements.
     pub const BUFFER_SIZE_BYTES: usize = 1 << 13;
 
+    thread_local! {
+        /// An override of [BUFFER_SIZE_BYTES] for the current thread.
+        static BUFFER_SIZE_OVERRIDE: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
     /// The maximum buffer capacity in elements. Returns a number between [BUFFER_SIZE_BYTES]
     /// and 1, inclusively.
     pub const fn default_capacity<T>() -> usize {
+        capacity_for::<T>(BUFFER_SIZE_BYTES)
+    }
+
+    /// The maximum capacity in elements of a buffer of `buffer_size_bytes` bytes. Returns a
+    /// number between `buffer_size_bytes` and 1, inclusively.
+    pub const fn capacity_for<T>(buffer_size_bytes: usize) -> usize {
         let size = std::mem::size_of::<T>();
         if size == 0 {
-            BUFFER_SIZE_BYTES
-        } else if size <= BUFFER_SIZE_BYTES {
-            BUFFER_SIZE_BYTES / size
+            buffer_size_bytes
+        } else if size <= buffer_size_bytes {
+            buffer_size_bytes / size
         } else {
             1
         }
     }
+
+    /// The buffer size in bytes for the current thread, which is [BUFFER_SIZE_BYTES] unless
+    /// overridden with [set_buffer_size_bytes].
+    pub fn buffer_size_bytes() -> usize {
+        BUFFER_SIZE_OVERRIDE.with(|size| size.get()).unwrap_or(BUFFER_SIZE_BYTES)
+    }
+
+    /// Overrides the buffer size in bytes for the current thread, or restores the default
+    /// for `None`.
+    ///
+    /// Container builders read the buffer size when they are constructed, and builders that
+    /// already exist are unaffected.
+    pub fn set_buffer_size_bytes(buffer_size_bytes: Option<usize>) {
+        BUFFER_SIZE_OVERRIDE.with(|size| size.set(buffer_size_bytes));
+    }
+
+    /// Overrides the buffer size in bytes for the current thread until the returned guard is
+    /// dropped, which restores the previous override.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely_container::buffer::{buffer_size_bytes, override_buffer_size_bytes, BUFFER_SIZE_BYTES};
+    ///
+    /// let guard = override_buffer_size_bytes(64);
+    /// assert_eq!(buffer_size_bytes(), 64);
+    /// drop(guard);
+    /// assert_eq!(buffer_size_bytes(), BUFFER_SIZE_BYTES);
+    /// ```
+    pub fn override_buffer_size_bytes(buffer_size_bytes: usize) -> BufferSizeGuard {
+        let previous = BUFFER_SIZE_OVERRIDE.with(|size| size.replace(Some(buffer_size_bytes)));
+        BufferSizeGuard { previous }
+    }
+
+    /// Restores the buffer size override that was in place before
+    /// [override_buffer_size_bytes] when dropped.
+    #[must_use]
+    #[derive(Debug)]
+    pub struct BufferSizeGuard {
+        previous: Option<usize>,
+    }
+
+    impl Drop for BufferSizeGuard {
+        fn drop(&mut self) {
+            set_buffer_size_bytes(self.previous);
+        }
+    }
 }
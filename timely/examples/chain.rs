@@ -0,0 +1,51 @@
+use timely::dataflow::{InputHandle, Scope, Stream};
+use timely::dataflow::operators::{Input, Map, Probe};
+
+/// Extends `stream` by a chain of `depth` map operators, each of which passes records through.
+///
+/// Each operator hides its records from the optimizer, so that the chain does a small amount of
+/// work per record and operator, and its cost is mostly that of the operators themselves.
+fn map_chain<G: Scope>(stream: &Stream<G, u64>, depth: usize) -> Stream<G, u64> {
+    let mut stream = stream.clone();
+    for _ in 0 .. depth {
+        stream = stream.map(std::hint::black_box);
+    }
+    stream
+}
+
+fn main() {
+
+    // Measures the time to move records through chains of operators of varying depths, for example
+    // `cargo run --release --example chain -- 100 1000 100`.
+    let mut args = std::env::args().skip(1);
+    let depth = args.next().map(|x| x.parse::<usize>().unwrap()).unwrap_or(100);
+    let records = args.next().map(|x| x.parse::<u64>().unwrap()).unwrap_or(1_000);
+    let rounds = args.next().map(|x| x.parse::<u64>().unwrap()).unwrap_or(100);
+
+    timely::execute_from_args(args, move |worker| {
+
+        let mut input = InputHandle::new();
+        let probe = worker.dataflow(|scope| {
+            map_chain(&scope.input_from(&mut input), depth).probe()
+        });
+
+        let timer = std::time::Instant::now();
+        for round in 0 .. rounds {
+            if worker.index() == 0 {
+                for record in 0 .. records {
+                    input.send(record);
+                }
+            }
+            input.advance_to(round + 1);
+            worker.step_while(|| probe.less_than(input.time()));
+        }
+
+        let elapsed = timer.elapsed();
+        if worker.index() == 0 {
+            let per_round = elapsed.div_f64(rounds.max(1) as f64);
+            let per_operator = per_round.div_f64(depth.max(1) as f64);
+            println!("{:?}\tdepth {}: {:?} per round, {:?} per operator and round", elapsed, depth, per_round, per_operator);
+        }
+
+    }).unwrap();
+}
@@ -60,45 +60,185 @@ impl<T, C: Container> Message<T, C> {
 //
 // Serialization of each field is meant to be `u64` aligned, so that each has the ability
 // to be decoded using safe transmutation, e.g. `bytemuck`.
-impl<T, C> crate::communication::Bytesable for Message<T, C>
+impl<T, C> Message<T, C>
 where
     T: Serialize + for<'a> Deserialize<'a>,
-    C: ContainerBytes,
 {
-    fn from_bytes(mut bytes: crate::bytes::arc::Bytes) -> Self {
+    fn from_bytes_with<E: ContainerEncoder<C>>(mut bytes: crate::bytes::arc::Bytes) -> Self {
         use byteorder::ReadBytesExt;
         let mut slice = &bytes[..];
         let from: usize = slice.read_u64::<byteorder::LittleEndian>().unwrap().try_into().unwrap();
         let seq: usize = slice.read_u64::<byteorder::LittleEndian>().unwrap().try_into().unwrap();
+        let before = slice.len();
         let time: T = ::bincode::deserialize_from(&mut slice).expect("bincode::deserialize() failed");
-        let time_size = ::bincode::serialized_size(&time).expect("bincode::serialized_size() failed") as usize;
+        let time_size = before - slice.len();
         // We expect to find the `data` payload at `8 + 8 + round_up(time_size)`;
         let bytes_read = 8 + 8 + ((time_size + 7) & !7);
         bytes.extract_to(bytes_read);
-        let data: C = ContainerBytes::from_bytes(bytes);
+        let data: C = E::from_bytes(bytes);
         Self { time, data, from, seq }
     }
 
-    fn length_in_bytes(&self) -> usize {
+    pub(crate) fn length_in_bytes_with<E: ContainerEncoder<C>>(&self) -> usize {
         let time_size = ::bincode::serialized_size(&self.time).expect("bincode::serialized_size() failed") as usize;
         // 16 comes from the two `u64` fields: `from` and `seq`.
-        16 + ((time_size + 7) & !7) + self.data.length_in_bytes()
+        16 + ((time_size + 7) & !7) + E::length_in_bytes(&self.data)
     }
 
-    fn into_bytes<W: ::std::io::Write>(&self, writer: &mut W) {
+    fn write_bytes_with<E: ContainerEncoder<C>, W: ::std::io::Write>(&self, writer: &mut W) {
         use byteorder::WriteBytesExt;
         writer.write_u64::<byteorder::LittleEndian>(self.from.try_into().unwrap()).unwrap();
         writer.write_u64::<byteorder::LittleEndian>(self.seq.try_into().unwrap()).unwrap();
-        ::bincode::serialize_into(&mut *writer, &self.time).expect("bincode::serialize_into() failed");
-        let time_size = ::bincode::serialized_size(&self.time).expect("bincode::serialized_size() failed") as usize;
+        // Count the bytes of the time as they are written, rather than serializing it twice.
+        let mut counter = implementations::WriteCounter::new(&mut *writer);
+        ::bincode::serialize_into(&mut counter, &self.time).expect("bincode::serialize_into() failed");
+        let time_size = counter.count;
         let time_slop = ((time_size + 7) & !7) - time_size;
-        writer.write(&[0u8; 8][..time_slop]).unwrap();
-        self.data.into_bytes(&mut *writer);
+        writer.write_all(&[0u8; 8][..time_slop]).unwrap();
+        E::into_bytes(&self.data, &mut *writer);
+    }
+}
+
+impl<T, C> crate::communication::Bytesable for Message<T, C>
+where
+    T: Serialize + for<'a> Deserialize<'a>,
+    C: ContainerBytes,
+{
+    #[inline]
+    fn from_bytes(bytes: crate::bytes::arc::Bytes) -> Self {
+        Self::from_bytes_with::<DefaultEncoder>(bytes)
+    }
+
+    #[inline]
+    fn length_in_bytes(&self) -> usize {
+        self.length_in_bytes_with::<DefaultEncoder>()
+    }
+
+    #[inline]
+    fn into_bytes<W: ::std::io::Write>(&self, writer: &mut W) {
+        self.write_bytes_with::<DefaultEncoder, W>(writer)
     }
 }
 
+/// A `Message` whose container is serialized by the encoder `E`, rather than by its `ContainerBytes` implementation.
+///
+/// The header of the message, its source, sequence number, and time, are serialized as for `Message`.
+/// Only the container's encoding differs, which allows a channel to use an encoding suited to its data.
+pub struct EncodedMessage<T, C, E> {
+    /// The message to serialize.
+    pub message: Message<T, C>,
+    phantom: std::marker::PhantomData<fn() -> E>,
+}
+
+impl<T, C, E> EncodedMessage<T, C, E> {
+    /// Wraps a message to be serialized with `E`.
+    #[inline]
+    pub fn new(message: Message<T, C>) -> Self {
+        Self { message, phantom: std::marker::PhantomData }
+    }
+}
+
+impl<T, C, E> crate::communication::Bytesable for EncodedMessage<T, C, E>
+where
+    T: Serialize + for<'a> Deserialize<'a>,
+    E: ContainerEncoder<C>,
+{
+    fn from_bytes(bytes: crate::bytes::arc::Bytes) -> Self {
+        Self::new(Message::from_bytes_with::<E>(bytes))
+    }
+
+    fn length_in_bytes(&self) -> usize {
+        self.message.length_in_bytes_with::<E>()
+    }
+
+    fn into_bytes<W: ::std::io::Write>(&self, writer: &mut W) {
+        self.message.write_bytes_with::<E, W>(writer)
+    }
+}
+
+/// A strategy for serializing containers of type `C`, for use in place of `ContainerBytes`.
+///
+/// Implementations must uphold the alignment contract of `Message` serialization: `length_in_bytes`
+/// must be a multiple of eight, and `into_bytes` must write exactly that many bytes, padding as needed.
+/// The bytes presented to `from_bytes` are exactly those written by `into_bytes`, including padding.
+pub trait ContainerEncoder<C> {
+    /// Decodes a container from `bytes`.
+    fn from_bytes(bytes: crate::bytes::arc::Bytes) -> C;
+
+    /// The number of bytes required to serialize `container`.
+    fn length_in_bytes(container: &C) -> usize;
+
+    /// Writes the binary representation of `container` into `writer`.
+    fn into_bytes<W: ::std::io::Write>(container: &C, writer: &mut W);
+}
+
+/// The default encoder, which uses the container's `ContainerBytes` implementation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultEncoder;
+
+impl<C: ContainerBytes> ContainerEncoder<C> for DefaultEncoder {
+    #[inline]
+    fn from_bytes(bytes: crate::bytes::arc::Bytes) -> C {
+        C::from_bytes(bytes)
+    }
+
+    #[inline]
+    fn length_in_bytes(container: &C) -> usize {
+        container.length_in_bytes()
+    }
+
+    #[inline]
+    fn into_bytes<W: ::std::io::Write>(container: &C, writer: &mut W) {
+        container.into_bytes(writer)
+    }
+}
+
+/// An encoder for `Vec<u8>` that writes the bytes themselves, rather than serializing them with `bincode`.
+///
+/// The bytes are preceded by their length as a little-endian `u64`, and padded to a multiple of eight
+/// bytes. Decoding copies the bytes out of the received buffer once, without parsing them.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::channels::RawBytes;
+/// use timely::dataflow::channels::pact::Exchange;
+/// use timely::dataflow::operators::{ToStream, Operator};
+///
+/// timely::example(|scope| {
+///     let pact = Exchange::new(|x: &u8| *x as u64).encoded_with::<RawBytes>();
+///     (0..10u8).to_stream(scope)
+///              .sink(pact, "Sink", |input| {
+///                  input.for_each(|_time, data| println!("seen: {:?}", data));
+///              });
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RawBytes;
+
+impl ContainerEncoder<Vec<u8>> for RawBytes {
+    fn from_bytes(bytes: crate::bytes::arc::Bytes) -> Vec<u8> {
+        let length: usize = u64::from_le_bytes(bytes[.. 8].try_into().unwrap()).try_into().unwrap();
+        bytes[8 .. 8 + length].to_vec()
+    }
+
+    #[inline]
+    fn length_in_bytes(container: &Vec<u8>) -> usize {
+        8 + ((container.len() + 7) & !7)
+    }
+
+    fn into_bytes<W: ::std::io::Write>(container: &Vec<u8>, writer: &mut W) {
+        use byteorder::WriteBytesExt;
+        writer.write_u64::<byteorder::LittleEndian>(container.len().try_into().unwrap()).unwrap();
+        writer.write_all(container).unwrap();
+        let slop = ((container.len() + 7) & !7) - container.len();
+        writer.write_all(&[0u8; 8][..slop]).unwrap();
+    }
+}
 
 /// A container-oriented version of `Bytesable` that can be implemented here for `Vec<T>` and other containers.
+///
+/// This is the encoding used by [`DefaultEncoder`], and is subject to the same alignment contract
+/// as [`ContainerEncoder`]. Channels may use other encodings through [`pact::ExchangeCore::encoded_with`].
 pub trait ContainerBytes {
     /// Wrap bytes as `Self`.
     fn from_bytes(bytes: crate::bytes::arc::Bytes) -> Self;
@@ -156,7 +296,30 @@ mod implementations {
         }
     }
 
-    use write_counter::WriteCounter;
+    // A pair is written as the length of the first component's bytes, followed by the bytes of
+    // each component. As the components' lengths are multiples of eight, so is that of the pair.
+    impl<C1: ContainerBytes, C2: ContainerBytes> ContainerBytes for (C1, C2) {
+        fn from_bytes(mut bytes: crate::bytes::arc::Bytes) -> Self {
+            use byteorder::ReadBytesExt;
+            let length: usize = (&bytes[..]).read_u64::<byteorder::LittleEndian>().unwrap().try_into().unwrap();
+            bytes.extract_to(8);
+            let first = bytes.extract_to(length);
+            (C1::from_bytes(first), C2::from_bytes(bytes))
+        }
+
+        fn length_in_bytes(&self) -> usize {
+            8 + self.0.length_in_bytes() + self.1.length_in_bytes()
+        }
+
+        fn into_bytes<W: Write>(&self, writer: &mut W) {
+            use byteorder::WriteBytesExt;
+            writer.write_u64::<byteorder::LittleEndian>(self.0.length_in_bytes().try_into().unwrap()).unwrap();
+            self.0.into_bytes(writer);
+            self.1.into_bytes(writer);
+        }
+    }
+
+    pub(super) use write_counter::WriteCounter;
     /// A `Write` wrapper that counts the bytes written.
     mod write_counter {
 
@@ -200,3 +363,228 @@ mod implementations {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Write;
+
+    use crate::bytes::arc::Bytes;
+    use crate::communication::Bytesable;
+    use super::{ContainerEncoder, DefaultEncoder, EncodedMessage, Message};
+
+    /// Encodes `Vec<u32>` as a `u32` length followed by the values, padded to eight bytes.
+    struct Raw;
+
+    impl ContainerEncoder<Vec<u32>> for Raw {
+        fn from_bytes(bytes: crate::bytes::arc::Bytes) -> Vec<u32> {
+            let mut words = bytes.chunks(4).map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()));
+            let length = words.next().unwrap() as usize;
+            words.take(length).collect()
+        }
+        fn length_in_bytes(container: &Vec<u32>) -> usize {
+            (4 * (container.len() + 1) + 7) & !7
+        }
+        fn into_bytes<W: Write>(container: &Vec<u32>, writer: &mut W) {
+            writer.write_all(&(container.len() as u32).to_le_bytes()).unwrap();
+            for value in container.iter() {
+                writer.write_all(&value.to_le_bytes()).unwrap();
+            }
+            let slop = Self::length_in_bytes(container) - 4 * (container.len() + 1);
+            writer.write_all(&[0u8; 8][..slop]).unwrap();
+        }
+    }
+
+    fn round_trip<M: Bytesable>(message: &M) -> (Vec<u8>, M) {
+        let mut bytes = Vec::new();
+        message.into_bytes(&mut bytes);
+        assert_eq!(bytes.len(), message.length_in_bytes());
+        assert_eq!(bytes.len() % 8, 0);
+        let decoded = M::from_bytes(Bytes::from(bytes.clone()));
+        (bytes, decoded)
+    }
+
+    #[test]
+    fn encoded_message() {
+        let message = Message::new((3u64, 5u32), vec![1u32, 2, 3], 1, 7);
+
+        let (bytes, decoded) = round_trip(&EncodedMessage::<_, _, Raw>::new(message.clone()));
+        let decoded = decoded.message;
+        assert_eq!((decoded.time, decoded.data, decoded.from, decoded.seq), ((3, 5), vec![1, 2, 3], 1, 7));
+
+        // The default encoder produces the same bytes as `Message` itself.
+        let (default, _) = round_trip(&EncodedMessage::<_, _, DefaultEncoder>::new(message.clone()));
+        let (plain, _) = round_trip(&message);
+        assert_eq!(default, plain);
+        assert_ne!(bytes, plain);
+    }
+
+    #[test]
+    fn raw_bytes() {
+        use super::RawBytes;
+        for length in [0, 1, 7, 8, 9] {
+            let data = (0 .. length as u8).collect::<Vec<_>>();
+            let message = Message::new(3u64, data.clone(), 1, 7);
+            let (bytes, decoded) = round_trip(&EncodedMessage::<_, _, RawBytes>::new(message));
+            assert_eq!(decoded.message.data, data);
+            // The header and time, the length of the data, and the data padded to eight bytes.
+            assert_eq!(bytes.len(), 24 + 8 + ((length + 7) & !7));
+            assert_eq!(&bytes[32 .. 32 + length], &data[..]);
+        }
+    }
+
+    #[test]
+    fn message_layout() {
+        let message = Message::new((3u64, 5u32), vec![1u64, 2, 3], 1, 7);
+        let (bytes, decoded) = round_trip(&message);
+        assert_eq!((decoded.time, decoded.data, decoded.from, decoded.seq), ((3, 5), vec![1, 2, 3], 1, 7));
+
+        // The source and sequence number, followed by the time and data, each padded to eight bytes.
+        let mut expected = Vec::new();
+        expected.extend(1u64.to_le_bytes());
+        expected.extend(7u64.to_le_bytes());
+        expected.extend(::bincode::serialize(&(3u64, 5u32)).unwrap());
+        expected.extend([0u8; 4]);
+        expected.extend(::bincode::serialize(&vec![1u64, 2, 3]).unwrap());
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn pair_bytes() {
+        use super::ContainerBytes;
+        let pair = (vec![1u8, 2, 3], vec![String::from("a"), String::from("bc")]);
+        assert_eq!(pair.length_in_bytes() % 8, 0);
+        let message = Message { time: 0u64, data: pair.clone(), from: 0, seq: 0 };
+        let (_, decoded) = round_trip(&message);
+        assert_eq!(decoded.data, pair);
+
+        let message = Message { time: 0u64, data: (Vec::<u64>::new(), vec![4u64]), from: 0, seq: 0 };
+        let (_, decoded) = round_trip(&message);
+        assert_eq!(decoded.data, (vec![], vec![4]));
+    }
+
+    #[test]
+    fn data_channel_logging() {
+        use crate::dataflow::channels::pact::Exchange;
+        use crate::dataflow::operators::{ToStream, Operator};
+        use crate::logging::{CommChannelKind, TimelyEvent, capture_timely_events};
+
+        let events = crate::execute::execute_directly(|worker| {
+            let events = capture_timely_events(worker);
+            worker.dataflow::<u64,_,_>(|scope| {
+                (0..10u64).to_stream(scope).sink(Exchange::new(|x: &u64| *x), "Sink", |input| input.for_each(|_, _| { }));
+            });
+            events
+        });
+        let events = events.lock().unwrap();
+        let data_channels = events.iter().filter(|event| matches!(event, TimelyEvent::CommChannels(event) if event.kind == CommChannelKind::Data));
+        assert_eq!(data_channels.count(), 1);
+    }
+
+    #[test]
+    fn communication_logging() {
+        use std::sync::{Arc, Mutex};
+        use crate::communication::logging::{CommunicationEvent, CommunicationEventBuilder};
+        use crate::dataflow::channels::pact::Exchange;
+        use crate::dataflow::operators::{ToStream, Operator};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events2 = Arc::clone(&events);
+        crate::execute::execute(crate::Config::process(2), move |worker| {
+            let captured = Arc::clone(&events2);
+            worker.log_register().insert::<CommunicationEventBuilder,_>("communication", move |_time, data| {
+                if let Some(data) = data {
+                    captured.lock().unwrap().extend(data.iter().filter_map(|(_, event)| match event {
+                        CommunicationEvent::Message(event) => Some(*event),
+                        _ => None,
+                    }));
+                }
+            });
+            worker.dataflow::<u64,_,_>(|scope| {
+                (0..10u64).to_stream(scope).sink(Exchange::new(|x: &u64| *x), "Sink", |input| input.for_each(|_, _| { }));
+            });
+        }).unwrap().join().into_iter().for_each(|result| result.unwrap());
+
+        let events = events.lock().unwrap();
+        let channel = events[0].header.channel;
+        for event in events.iter() {
+            assert_eq!(event.header.channel, channel);
+            assert!(event.header.length > 0);
+        }
+        let batches = |is_send: bool| {
+            let mut batches = events.iter().filter(|event| event.is_send == is_send).map(|event| event.header).map(|header| (header.source, header.target, header.seqno, header.length)).collect::<Vec<_>>();
+            batches.sort();
+            batches
+        };
+        // Each worker sends its records to both workers, itself included, and each batch is received as it was sent.
+        let sent = batches(true);
+        assert_eq!(sent.iter().map(|(source, target, _, _)| (*source, *target)).collect::<Vec<_>>(), vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(sent, batches(false));
+    }
+
+    #[test]
+    fn local_exchange() {
+        use std::sync::{Arc, Mutex};
+        use crate::dataflow::channels::pact::Exchange;
+        use crate::dataflow::operators::{ToStream, Operator};
+
+        // Neither `Serialize` nor `Deserialize`.
+        #[derive(Clone)]
+        struct Local(u64);
+
+        let build = |worker: &mut crate::worker::Worker<crate::communication::allocator::Generic>, seen: Arc<Mutex<Vec<(usize, u64)>>>| {
+            let index = worker.index();
+            worker.dataflow::<u64,_,_>(|scope| {
+                (0..10u64).map(Local).to_stream(scope)
+                          .sink(Exchange::new(|x: &Local| x.0).local(), "Sink", move |input| {
+                              input.for_each(|_, data| seen.lock().unwrap().extend(data.iter().map(|x| (index, x.0))));
+                          });
+            });
+        };
+
+        // Each worker introduces all records, and receives those that hash to it.
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen2 = Arc::clone(&seen);
+        crate::execute(crate::Config::process(2), move |worker| build(worker, Arc::clone(&seen2))).unwrap();
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        let mut expected = (0..2).flat_map(|_| 0..10u64).map(|x| ((x % 2) as usize, x)).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(seen, expected);
+
+        // Allocators that serialize reject the channel when it is allocated.
+        let config = crate::Config { communication: crate::CommunicationConfig::ProcessBinary(1), worker: Default::default() };
+        let results = crate::execute(config, move |worker| build(worker, Arc::new(Mutex::new(Vec::new())))).unwrap().join();
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn encoded_exchange() {
+        use std::sync::{Arc, Mutex};
+        use crate::dataflow::channels::pact::Exchange;
+        use crate::dataflow::operators::{ToStream, Operator};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen2 = Arc::clone(&seen);
+        crate::execute(crate::Config::process(2), move |worker| {
+            let index = worker.index();
+            let seen = Arc::clone(&seen2);
+            worker.dataflow::<u64,_,_>(|scope| {
+                (0..10u32)
+                    .to_stream(scope)
+                    .sink(Exchange::new(|x: &u32| *x as u64).encoded_with::<Raw>(), "Sink", move |input| {
+                        input.for_each(|_time, data| {
+                            seen.lock().unwrap().extend(data.drain(..).map(|x| (index, x)));
+                        });
+                    });
+            });
+        }).unwrap();
+
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        // Each worker produces all records, which are routed by their value.
+        let mut expected = (0..10u32).flat_map(|x| [(x as usize % 2, x); 2]).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(seen, expected);
+    }
+}
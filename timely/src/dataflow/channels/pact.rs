@@ -13,9 +13,11 @@ use std::rc::Rc;
 use crate::{Container, container::{ContainerBuilder, LengthPreservingContainerBuilder, SizableContainer, CapacityContainerBuilder, PushInto}};
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
 use crate::communication::{Push, Pull};
+use crate::communication::logging::{CommunicationEvent, CommunicationEventBuilder, MessageEvent};
+use crate::communication::networking::MessageHeader;
 use crate::dataflow::channels::pushers::Exchange as ExchangePusher;
-use crate::dataflow::channels::Message;
-use crate::logging::{TimelyLogger as Logger, MessagesEvent};
+use crate::dataflow::channels::{Message, EncodedMessage, ContainerEncoder, DefaultEncoder};
+use crate::logging::{TimelyLogger as Logger, CommChannelKind, CommChannelsEvent, MessagesEvent};
 use crate::progress::Timestamp;
 use crate::worker::AsWorker;
 use crate::Data;
@@ -45,7 +47,7 @@ impl<T: 'static, C: Container + 'static> ParallelizationContract<T, C> for Pipel
 }
 
 /// An exchange between multiple observers by data
-pub struct ExchangeCore<CB, F> { hash_func: F, phantom: PhantomData<CB> }
+pub struct ExchangeCore<CB, F> { hash_func: F, finalizer: Option<fn(u64, usize) -> usize>, phantom: PhantomData<CB> }
 
 /// [ExchangeCore] specialized to vector-based containers.
 pub type Exchange<D, F> = ExchangeCore<CapacityContainerBuilder<Vec<D>>, F>;
@@ -59,6 +61,18 @@ where
     pub fn new_core(func: F) -> ExchangeCore<CB, F> {
         ExchangeCore {
             hash_func:  func,
+            finalizer:  None,
+            phantom:    PhantomData,
+        }
+    }
+
+    /// Allocates a new `Exchange` pact from a distribution function and a finalizer.
+    ///
+    /// See [`new_with_finalizer`](ExchangeCore::new_with_finalizer) for the role of the finalizer.
+    pub fn new_core_with_finalizer(func: F, finalizer: fn(u64, usize) -> usize) -> ExchangeCore<CB, F> {
+        ExchangeCore {
+            hash_func:  func,
+            finalizer:  Some(finalizer),
             phantom:    PhantomData,
         }
     }
@@ -70,9 +84,43 @@ where
     for<'a> F: FnMut(&C::Item<'a>)->u64
 {
     /// Allocates a new `Exchange` pact from a distribution function.
+    ///
+    /// Each record is sent to the worker whose index is the result of `func` modulo the
+    /// number of workers.
     pub fn new(func: F) -> ExchangeCore<CapacityContainerBuilder<C>, F> {
         ExchangeCore {
             hash_func:  func,
+            finalizer:  None,
+            phantom:    PhantomData,
+        }
+    }
+
+    /// Allocates a new `Exchange` pact from a distribution function and a finalizer.
+    ///
+    /// Each record is sent to the worker whose index is `finalizer(func(record), peers)`, which
+    /// must be less than `peers`, the number of workers, or the worker sending the record panics.
+    /// With a single worker the finalizer is not called. All workers must map records to the
+    /// same workers, which is why the finalizer is a function pointer that cannot capture state;
+    /// it should depend only on its arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::channels::pact::Exchange;
+    /// use timely::dataflow::operators::{ToStream, Inspect, Operator};
+    ///
+    /// timely::example(|scope| {
+    ///     // assign contiguous ranges of hashes to workers.
+    ///     let pact = Exchange::new_with_finalizer(|x: &u64| *x, |hash, peers| ((hash as u128 * peers as u128) >> 64) as usize);
+    ///     (0..10u64).to_stream(scope)
+    ///               .sink(pact, "Sink", |input| {
+    ///                   input.for_each(|_time, data| println!("seen: {:?}", data));
+    ///               });
+    /// });
+    /// ```
+    pub fn new_with_finalizer(func: F, finalizer: fn(u64, usize) -> usize) -> ExchangeCore<CapacityContainerBuilder<C>, F> {
+        ExchangeCore {
+            hash_func:  func,
+            finalizer:  Some(finalizer),
             phantom:    PhantomData,
         }
     }
@@ -91,17 +139,165 @@ where
 
     fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: Rc<[usize]>, logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
         let (senders, receiver) = allocator.allocate::<Message<T, CB::Container>>(identifier, address);
-        let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
-        (ExchangePusher::new(senders, self.hash_func), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()))
+        log_data_channel(&logging, identifier);
+        let communication = communication_logger(allocator);
+        let length: MessageLength<T, CB::Container> = |message| message.length_in_bytes_with::<DefaultEncoder>();
+        let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone()).with_communication(communication.clone(), length)).collect::<Vec<_>>();
+        (ExchangePusher::new_with_finalizer(senders, self.hash_func, self.finalizer), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()).with_communication(communication, length))
     }
 }
 
+/// Logs the allocation of a communication channel carrying data.
+fn log_data_channel(logging: &Option<Logger>, identifier: usize) {
+    if let Some(logger) = logging.as_ref() {
+        logger.log(CommChannelsEvent { identifier, kind: CommChannelKind::Data });
+    }
+}
+
+/// Logger for the "communication" log stream, which records the bytes each batch occupies.
+type CommunicationLogger = crate::logging_core::TypedLogger<CommunicationEventBuilder, CommunicationEvent>;
+
+/// Reports the number of bytes a message occupies when serialized.
+type MessageLength<T, C> = fn(&Message<T, C>) -> usize;
+
+/// Fetches the worker's "communication" logger, if one is installed.
+fn communication_logger<A: AsWorker>(allocator: &A) -> Option<CommunicationLogger> {
+    allocator.log_register().get::<CommunicationEventBuilder>("communication").map(Into::into)
+}
+
 impl<C, F> Debug for ExchangeCore<C, F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Exchange").finish()
     }
 }
 
+impl<CB, F> ExchangeCore<CB, F> {
+    /// Serializes the exchanged containers with the encoder `E` rather than with `ContainerBytes`.
+    ///
+    /// The encoder is only used for data sent between processes; data exchanged within a process
+    /// is not serialized. All workers must use the same encoder for the same channel.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::channels::DefaultEncoder;
+    /// use timely::dataflow::channels::pact::Exchange;
+    /// use timely::dataflow::operators::{ToStream, Operator};
+    ///
+    /// timely::example(|scope| {
+    ///     let pact = Exchange::new(|x: &u64| *x).encoded_with::<DefaultEncoder>();
+    ///     (0..10u64).to_stream(scope)
+    ///               .sink(pact, "Sink", |input| {
+    ///                   input.for_each(|_time, data| println!("seen: {:?}", data));
+    ///               });
+    /// });
+    /// ```
+    pub fn encoded_with<E>(self) -> EncodedExchange<CB, F, E> {
+        EncodedExchange {
+            exchange: self,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An exchange pact whose containers are serialized by the encoder `E`.
+///
+/// Constructed by [`ExchangeCore::encoded_with`].
+pub struct EncodedExchange<CB, F, E> { exchange: ExchangeCore<CB, F>, phantom: PhantomData<fn() -> E> }
+
+impl<T: Timestamp, CB, H: 'static, E: 'static> ParallelizationContract<T, CB::Container> for EncodedExchange<CB, H, E>
+where
+    CB: ContainerBuilder,
+    CB: for<'a> PushInto<<CB::Container as Container>::Item<'a>>,
+    CB::Container: Data + Send,
+    E: ContainerEncoder<CB::Container>,
+    for<'a> H: FnMut(&<CB::Container as Container>::Item<'a>) -> u64
+{
+    type Pusher = ExchangePusher<T, CB, LogPusher<T, CB::Container, EncodingPusher<T, CB::Container, E>>, H>;
+    type Puller = LogPuller<T, CB::Container, DecodingPuller<T, CB::Container, E>>;
+
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: Rc<[usize]>, logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<EncodedMessage<T, CB::Container, E>>(identifier, address);
+        log_data_channel(&logging, identifier);
+        let communication = communication_logger(allocator);
+        let length: MessageLength<T, CB::Container> = |message| message.length_in_bytes_with::<E>();
+        let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(EncodingPusher::new(x), allocator.index(), i, identifier, logging.clone()).with_communication(communication.clone(), length)).collect::<Vec<_>>();
+        let receiver = DecodingPuller::new(receiver);
+        let exchange = self.exchange;
+        (ExchangePusher::new_with_finalizer(senders, exchange.hash_func, exchange.finalizer), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()).with_communication(communication, length))
+    }
+}
+
+impl<C, F, E> Debug for EncodedExchange<C, F, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncodedExchange").finish()
+    }
+}
+
+impl<CB, F> ExchangeCore<CB, F> {
+    /// Exchanges containers by moving them between workers, without serializing them.
+    ///
+    /// This removes the requirement that the container implement `ContainerBytes`, which lets
+    /// types that implement neither `Serialize` nor `Deserialize` be exchanged, but only when all
+    /// workers share an address space and exchange typed data, as with the thread and process
+    /// allocators. Other allocators, for example those that communicate over the network, cannot
+    /// move such data, and connecting the pact to them panics when its channel is allocated.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::channels::pact::Exchange;
+    /// use timely::dataflow::operators::{ToStream, Operator};
+    ///
+    /// // A type that cannot be serialized.
+    /// #[derive(Clone, Debug)]
+    /// struct Local(u64);
+    ///
+    /// timely::execute(timely::Config::process(2), |worker| {
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         let pact = Exchange::new(|x: &Local| x.0).local();
+    ///         (0..10u64).map(Local).to_stream(scope)
+    ///                   .sink(pact, "Sink", |input| {
+    ///                       input.for_each(|_time, data| println!("seen: {:?}", data));
+    ///                   });
+    ///     });
+    /// }).unwrap();
+    /// ```
+    pub fn local(self) -> LocalExchange<CB, F> {
+        LocalExchange { exchange: self }
+    }
+}
+
+/// An exchange pact that moves containers between workers without serializing them.
+///
+/// Constructed by [`ExchangeCore::local`].
+pub struct LocalExchange<CB, F> { exchange: ExchangeCore<CB, F> }
+
+impl<T: Timestamp, CB, H: 'static> ParallelizationContract<T, CB::Container> for LocalExchange<CB, H>
+where
+    CB: ContainerBuilder,
+    CB: for<'a> PushInto<<CB::Container as Container>::Item<'a>>,
+    CB::Container: Data + Send,
+    for<'a> H: FnMut(&<CB::Container as Container>::Item<'a>) -> u64
+{
+    type Pusher = ExchangePusher<T, CB, LogPusher<T, CB::Container, Box<dyn Push<Message<T, CB::Container>>>>, H>;
+    type Puller = LogPuller<T, CB::Container, Box<dyn Pull<Message<T, CB::Container>>>>;
+
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: Rc<[usize]>, logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator
+            .allocate_local::<Message<T, CB::Container>>(identifier, address)
+            .unwrap_or_else(|error| panic!("LocalExchange: {}", error));
+        log_data_channel(&logging, identifier);
+        let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+        let exchange = self.exchange;
+        (ExchangePusher::new_with_finalizer(senders, exchange.hash_func, exchange.finalizer), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()))
+    }
+}
+
+impl<C, F> Debug for LocalExchange<C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalExchange").finish()
+    }
+}
+
 /// Wraps a `Message<T,D>` pusher to provide a `Push<(T, Content<D>)>`.
 #[derive(Debug)]
 pub struct LogPusher<T, C, P: Push<Message<T, C>>> {
@@ -112,6 +308,7 @@ pub struct LogPusher<T, C, P: Push<Message<T, C>>> {
     target: usize,
     phantom: PhantomData<(T, C)>,
     logging: Option<Logger>,
+    communication: Option<(CommunicationLogger, MessageLength<T, C>)>,
 }
 
 impl<T, C, P: Push<Message<T, C>>> LogPusher<T, C, P> {
@@ -125,8 +322,17 @@ impl<T, C, P: Push<Message<T, C>>> LogPusher<T, C, P> {
             target,
             phantom: PhantomData,
             logging,
+            communication: None,
         }
     }
+
+    /// Logs each batch sent to the "communication" log stream, with its size in bytes as reported by `length`.
+    ///
+    /// Nothing is logged, and `length` is not called, when `communication` is `None`.
+    pub fn with_communication(mut self, communication: Option<CommunicationLogger>, length: MessageLength<T, C>) -> Self {
+        self.communication = communication.map(|logger| (logger, length));
+        self
+    }
 }
 
 impl<T, C: Container, P: Push<Message<T, C>>> Push<Message<T, C>> for LogPusher<T, C, P> {
@@ -150,6 +356,18 @@ impl<T, C: Container, P: Push<Message<T, C>>> Push<Message<T, C>> for LogPusher<
                     length: bundle.data.len(),
                 })
             }
+            if let Some((logger, length)) = self.communication.as_ref() {
+                logger.log(MessageEvent {
+                    is_send: true,
+                    header: MessageHeader {
+                        channel: self.channel,
+                        source: self.source,
+                        target: self.target,
+                        length: length(bundle),
+                        seqno: self.counter - 1,
+                    },
+                });
+            }
         }
 
         self.pusher.push(pair);
@@ -164,6 +382,7 @@ pub struct LogPuller<T, C, P: Pull<Message<T, C>>> {
     index: usize,
     phantom: PhantomData<(T, C)>,
     logging: Option<Logger>,
+    communication: Option<(CommunicationLogger, MessageLength<T, C>)>,
 }
 
 impl<T, C, P: Pull<Message<T, C>>> LogPuller<T, C, P> {
@@ -175,8 +394,17 @@ impl<T, C, P: Pull<Message<T, C>>> LogPuller<T, C, P> {
             index,
             phantom: PhantomData,
             logging,
+            communication: None,
         }
     }
+
+    /// Logs each batch received to the "communication" log stream, with its size in bytes as reported by `length`.
+    ///
+    /// Nothing is logged, and `length` is not called, when `communication` is `None`.
+    pub fn with_communication(mut self, communication: Option<CommunicationLogger>, length: MessageLength<T, C>) -> Self {
+        self.communication = communication.map(|logger| (logger, length));
+        self
+    }
 }
 
 impl<T, C: Container, P: Pull<Message<T, C>>> Pull<Message<T, C>> for LogPuller<T, C, P> {
@@ -197,8 +425,74 @@ impl<T, C: Container, P: Pull<Message<T, C>>> Pull<Message<T, C>> for LogPuller<
                     length: bundle.data.len(),
                 });
             }
+            if let Some((logger, length)) = self.communication.as_ref() {
+                logger.log(MessageEvent {
+                    is_send: false,
+                    header: MessageHeader {
+                        channel,
+                        source: bundle.from,
+                        target,
+                        length: length(bundle),
+                        seqno: bundle.seq,
+                    },
+                });
+            }
         }
 
         result
     }
 }
+
+/// Wraps an `EncodedMessage` pusher to provide a `Push<Message<T, C>>`.
+pub struct EncodingPusher<T, C, E> {
+    pusher: Box<dyn Push<EncodedMessage<T, C, E>>>,
+}
+
+impl<T, C, E> EncodingPusher<T, C, E> {
+    /// Allocates a new pusher.
+    pub fn new(pusher: Box<dyn Push<EncodedMessage<T, C, E>>>) -> Self {
+        EncodingPusher { pusher }
+    }
+}
+
+impl<T, C, E> Push<Message<T, C>> for EncodingPusher<T, C, E> {
+    #[inline]
+    fn push(&mut self, message: &mut Option<Message<T, C>>) {
+        let mut encoded = message.take().map(EncodedMessage::new);
+        self.pusher.push(&mut encoded);
+        *message = encoded.map(|encoded| encoded.message);
+    }
+}
+
+impl<T, C, E> Debug for EncodingPusher<T, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncodingPusher").finish()
+    }
+}
+
+/// Wraps an `EncodedMessage` puller to provide a `Pull<Message<T, C>>`.
+pub struct DecodingPuller<T, C, E> {
+    puller: Box<dyn Pull<EncodedMessage<T, C, E>>>,
+    current: Option<Message<T, C>>,
+}
+
+impl<T, C, E> DecodingPuller<T, C, E> {
+    /// Allocates a new puller.
+    pub fn new(puller: Box<dyn Pull<EncodedMessage<T, C, E>>>) -> Self {
+        DecodingPuller { puller, current: None }
+    }
+}
+
+impl<T, C, E> Pull<Message<T, C>> for DecodingPuller<T, C, E> {
+    #[inline]
+    fn pull(&mut self) -> &mut Option<Message<T, C>> {
+        self.current = self.puller.pull().take().map(|encoded| encoded.message);
+        &mut self.current
+    }
+}
+
+impl<T, C, E> Debug for DecodingPuller<T, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodingPuller").finish()
+    }
+}
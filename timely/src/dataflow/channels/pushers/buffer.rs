@@ -84,6 +84,15 @@ impl<T, CB: ContainerBuilder, P: Push<Message<T, CB::Container>>> Buffer<T, CB,
         self.pusher.push(&mut None);
     }
 
+    /// Flushes all data as `cease` does, and releases resources held by the container builder.
+    ///
+    /// Intended for outputs that are expected to be idle for a while, as the builder may need to
+    /// allocate again when next used.
+    pub fn relax(&mut self) {
+        self.cease();
+        self.builder.relax();
+    }
+
     /// Extract pending data from the builder, but not forcing a flush.
     #[inline]
     fn extract_and_send(&mut self) {
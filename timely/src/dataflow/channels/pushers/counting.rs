@@ -0,0 +1,56 @@
+//! A pusher which consumes messages and counts their records, for benchmarks and tests.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::dataflow::channels::Message;
+use crate::communication::Push;
+use crate::Container;
+
+/// A pusher which consumes messages, adding the number of records in each to a shared count.
+///
+/// Each message's container is cleared and left in place, so that whoever pushed the message can
+/// reuse its allocation. The count is shared with the creator of the pusher, which can read it
+/// at any time, for example to check that a benchmark moved the expected number of records.
+///
+/// # Examples
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use timely::communication::Push;
+/// use timely::dataflow::channels::Message;
+/// use timely::dataflow::channels::pushers::{CountingPusher, Tee};
+///
+/// let count = Rc::new(Cell::new(0));
+/// let (mut tee, helper) = Tee::<u64, Vec<u64>>::new();
+/// helper.add_pusher(CountingPusher::new(Rc::clone(&count)));
+/// tee.push(&mut Some(Message::new(0, vec![1, 2, 3], 0, 0)));
+/// tee.push(&mut Some(Message::new(1, vec![4, 5], 0, 0)));
+/// assert_eq!(count.get(), 5);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CountingPusher {
+    count: Rc<Cell<u64>>,
+}
+
+impl CountingPusher {
+    /// Allocates a new `CountingPusher` that adds to `count`.
+    pub fn new(count: Rc<Cell<u64>>) -> Self {
+        CountingPusher { count }
+    }
+    /// The number of records consumed by this pusher and others sharing its count.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count.get()
+    }
+}
+
+impl<T, C: Container> Push<Message<T, C>> for CountingPusher {
+    #[inline]
+    fn push(&mut self, message: &mut Option<Message<T, C>>) {
+        if let Some(message) = message {
+            self.count.set(self.count.get() + message.data.len() as u64);
+            message.data.clear();
+        }
+    }
+}
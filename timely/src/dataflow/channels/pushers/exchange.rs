@@ -1,10 +1,16 @@
 //! The exchange pattern distributes pushed data between many target pushees.
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use crate::communication::Push;
 use crate::container::{ContainerBuilder, PushInto};
 use crate::dataflow::channels::Message;
 use crate::{Container, Data};
 
+/// A shared slot of pushers to replace those of an [`Exchange`] when it is next flushed.
+pub type Reconfiguration<P> = Rc<RefCell<Option<Vec<P>>>>;
+
 // TODO : Software write combining
 /// Distributes records among target pushees according to a distribution function.
 pub struct Exchange<T, CB, P, H>
@@ -17,6 +23,8 @@ where
     buffers: Vec<CB>,
     current: Option<T>,
     hash_func: H,
+    finalizer: Option<fn(u64, usize) -> usize>,
+    reconfiguration: Option<Reconfiguration<P>>,
 }
 
 impl<T: Clone, CB, P, H>  Exchange<T, CB, P, H>
@@ -27,6 +35,16 @@ where
 {
     /// Allocates a new `Exchange` from a supplied set of pushers and a distribution function.
     pub fn new(pushers: Vec<P>, key: H) -> Exchange<T, CB, P, H> {
+        Self::new_with_finalizer(pushers, key, None)
+    }
+    /// Allocates a new `Exchange` from a supplied set of pushers, a distribution function, and
+    /// an optional finalizer.
+    ///
+    /// The finalizer maps the result of the distribution function and the number of pushers
+    /// to the index of the pusher that receives the record, which must be less than the number
+    /// of pushers. An index that is not panics, with a message naming the index in debug builds.
+    /// If the finalizer is `None`, the index is the result modulo the number of pushers.
+    pub fn new_with_finalizer(pushers: Vec<P>, key: H, finalizer: Option<fn(u64, usize) -> usize>) -> Exchange<T, CB, P, H> {
         let mut buffers = vec![];
         for _ in 0..pushers.len() {
             buffers.push(Default::default());
@@ -34,8 +52,29 @@ where
         Exchange {
             pushers,
             hash_func: key,
+            finalizer,
             buffers,
             current: None,
+            reconfiguration: None,
+        }
+    }
+    /// Returns a slot through which the pushers can be replaced.
+    ///
+    /// Pushers placed in the slot replace the current pushers when the exchange is next flushed,
+    /// at which point all data has been pushed at the current pushers and each has been flushed,
+    /// and so records in flight are routed entirely by either the current or the new pushers.
+    /// Records are then distributed among the new pushers, whose number may differ.
+    pub fn reconfiguration(&mut self) -> Reconfiguration<P> {
+        Rc::clone(self.reconfiguration.get_or_insert_with(Default::default))
+    }
+    /// Replaces the pushers with those in the reconfiguration slot, if any.
+    ///
+    /// Must only be called when all buffers have been flushed.
+    fn reconfigure(&mut self) {
+        let pushers = self.reconfiguration.as_ref().and_then(|slot| slot.borrow_mut().take());
+        if let Some(pushers) = pushers {
+            self.buffers.resize_with(pushers.len(), Default::default);
+            self.pushers = pushers;
         }
     }
     #[inline]
@@ -76,8 +115,17 @@ where
 
             let hash_func = &mut self.hash_func;
 
+            // if a finalizer is supplied, it determines the pusher
+            if let Some(finalizer) = self.finalizer {
+                let num_pushers = self.pushers.len();
+                CB::partition(data, &mut self.buffers, |datum| {
+                    let index = finalizer((hash_func)(datum), num_pushers);
+                    debug_assert!(index < num_pushers, "exchange finalizer returned index {} for {} pushers", index, num_pushers);
+                    index
+                });
+            }
             // if the number of pushers is a power of two, use a mask
-            if self.pushers.len().is_power_of_two() {
+            else if self.pushers.len().is_power_of_two() {
                 let mask = (self.pushers.len() - 1) as u64;
                 CB::partition(data, &mut self.buffers, |datum| ((hash_func)(datum) & mask) as usize);
             }
@@ -99,5 +147,121 @@ where
                 self.pushers[index].push(&mut None);
             }
         }
+
+        // all data has been flushed, and the pushers can be replaced.
+        if message.is_none() {
+            self.reconfigure();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::communication::Push;
+    use crate::container::CapacityContainerBuilder;
+    use crate::container::buffer::default_capacity;
+    use crate::dataflow::channels::Message;
+    use super::Exchange;
+
+    /// Records the allocation and length of each message, and returns a buffer of some capacity.
+    struct Returning {
+        sent: Rc<RefCell<Vec<(*const u64, usize)>>>,
+        capacity: Option<usize>,
+    }
+
+    impl Push<Message<u64, Vec<u64>>> for Returning {
+        fn push(&mut self, message: &mut Option<Message<u64, Vec<u64>>>) {
+            if let Some(message) = message {
+                self.sent.borrow_mut().push((message.data.as_ptr(), message.data.len()));
+                if let Some(capacity) = self.capacity {
+                    message.data = Vec::with_capacity(capacity);
+                }
+            }
+        }
+    }
+
+    fn exchange(capacity: Option<usize>) -> Vec<(*const u64, usize)> {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let pushers = (0 .. 2).map(|_| Returning { sent: Rc::clone(&sent), capacity }).collect();
+        let mut exchange = Exchange::<_, CapacityContainerBuilder<Vec<u64>>, _, _>::new(pushers, |_: &u64| 0);
+        for _ in 0 .. 4 {
+            let data = vec![0; default_capacity::<u64>()];
+            exchange.push(&mut Some(Message::new(0, data, 0, 0)));
+        }
+        exchange.done();
+        let sent = sent.borrow().clone();
+        sent
+    }
+
+    #[test]
+    fn reuses_returned_buffers() {
+        let sent = exchange(None);
+        assert_eq!(sent.len(), 4);
+        assert!(sent.iter().all(|(ptr, len)| *ptr == sent[0].0 && *len == default_capacity::<u64>()));
+    }
+
+    #[test]
+    fn rejects_oversized_buffers() {
+        let sent = exchange(Some(10 * default_capacity::<u64>()));
+        assert_eq!(sent.len(), 4);
+        assert!(sent.iter().all(|(_, len)| *len == default_capacity::<u64>()));
+    }
+
+    /// Collects the data of each message.
+    struct Collecting(Rc<RefCell<Vec<u64>>>);
+
+    impl Push<Message<u64, Vec<u64>>> for Collecting {
+        fn push(&mut self, message: &mut Option<Message<u64, Vec<u64>>>) {
+            if let Some(message) = message {
+                self.0.borrow_mut().append(&mut message.data);
+            }
+        }
+    }
+
+    #[test]
+    fn finalizer() {
+        let received = (0 .. 3).map(|_| Rc::new(RefCell::new(Vec::new()))).collect::<Vec<_>>();
+        let pushers = received.iter().map(|r| Collecting(Rc::clone(r))).collect();
+        // route contiguous ranges of ten keys to each pusher.
+        let mut exchange = Exchange::<_, CapacityContainerBuilder<Vec<u64>>, _, _>::new_with_finalizer(pushers, |x: &u64| *x, Some(|hash, peers| std::cmp::min(hash as usize / 10, peers - 1)));
+        exchange.push(&mut Some(Message::new(0, (0 .. 40).rev().collect(), 0, 0)));
+        exchange.done();
+        for (index, received) in received.iter().enumerate() {
+            let mut received = received.borrow().clone();
+            received.sort();
+            let expected = if index < 2 { (10 * index as u64 .. 10 * index as u64 + 10).collect::<Vec<_>>() } else { (20 .. 40).collect() };
+            assert_eq!(received, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "exchange finalizer returned index 3 for 3 pushers")]
+    fn finalizer_out_of_range() {
+        let pushers = (0 .. 3).map(|_| Collecting(Rc::new(RefCell::new(Vec::new())))).collect();
+        let mut exchange = Exchange::<_, CapacityContainerBuilder<Vec<u64>>, _, _>::new_with_finalizer(pushers, |x: &u64| *x, Some(|_hash, peers| peers));
+        exchange.push(&mut Some(Message::new(0, vec![0], 0, 0)));
+    }
+
+    #[test]
+    fn reconfiguration() {
+        let received = (0 .. 3).map(|_| Rc::new(RefCell::new(Vec::new()))).collect::<Vec<_>>();
+        let pushers = received[.. 1].iter().map(|r| Collecting(Rc::clone(r))).collect();
+        let mut exchange = Exchange::<_, CapacityContainerBuilder<Vec<u64>>, _, _>::new(pushers, |x: &u64| *x);
+        let reconfiguration = exchange.reconfiguration();
+        exchange.push(&mut Some(Message::new(0, (0 .. 6).collect(), 0, 0)));
+        // Replacing the pushers waits for the next flush.
+        *reconfiguration.borrow_mut() = Some(received[1 ..].iter().map(|r| Collecting(Rc::clone(r))).collect());
+        exchange.push(&mut Some(Message::new(0, (6 .. 12).collect(), 0, 0)));
+        exchange.done();
+        exchange.push(&mut Some(Message::new(1, (12 .. 18).collect(), 0, 0)));
+        exchange.done();
+        assert_eq!(*received[0].borrow(), (0 .. 12).collect::<Vec<_>>());
+        assert_eq!(*received[1].borrow(), vec![12, 14, 16]);
+        assert_eq!(*received[2].borrow(), vec![13, 15, 17]);
     }
 }
@@ -12,6 +12,10 @@ use crate::{Container, Data};
 type PushList<T, C> = Rc<RefCell<Vec<Box<dyn Push<Message<T, C>>>>>>;
 
 /// Wraps a shared list of `Box<Push>` to forward pushes to. Owned by `Stream`.
+///
+/// Each pushed message is cloned for all but the last of the pushers, and the message itself is
+/// pushed at the last pusher. With a single pusher nothing is cloned, and whatever the pusher
+/// leaves in place of the message is returned to the caller, who can reuse its allocation.
 pub struct Tee<T, C> {
     buffer: C,
     shared: PushList<T, C>,
@@ -89,6 +93,11 @@ impl<T, C> TeeHelper<T, C> {
     pub fn add_pusher<P: Push<Message<T, C>>+'static>(&self, pusher: P) {
         self.shared.borrow_mut().push(Box::new(pusher));
     }
+
+    /// The number of `Push` implementors that have been added.
+    pub fn consumers(&self) -> usize {
+        self.shared.borrow().len()
+    }
 }
 
 impl<T, C> Clone for TeeHelper<T, C> {
@@ -112,3 +121,59 @@ impl<T, C> Debug for TeeHelper<T, C> {
         debug.finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    use crate::communication::Push;
+    use crate::dataflow::channels::Message;
+    use super::Tee;
+
+    thread_local! {
+        static CLONES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// A record that counts its clones.
+    struct Counted;
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            CLONES.with(|clones| clones.set(clones.get() + 1));
+            Counted
+        }
+    }
+
+    /// Collects the lengths of pushed messages, and returns an empty container with capacity.
+    struct Collect(Rc<RefCell<Vec<usize>>>);
+
+    impl Push<Message<u64, Vec<Counted>>> for Collect {
+        fn push(&mut self, message: &mut Option<Message<u64, Vec<Counted>>>) {
+            if let Some(message) = message {
+                self.0.borrow_mut().push(message.data.len());
+                message.data.clear();
+            }
+        }
+    }
+
+    #[test]
+    fn tee_clones() {
+        let (mut tee, helper) = Tee::<u64, Vec<Counted>>::new();
+        let lengths = Rc::new(RefCell::new(Vec::new()));
+        helper.add_pusher(Collect(Rc::clone(&lengths)));
+
+        // A single pusher receives the message itself, and returns its container.
+        let mut message = Some(Message::new(0, vec![Counted, Counted, Counted], 0, 0));
+        tee.push(&mut message);
+        assert_eq!(CLONES.with(Cell::get), 0);
+        assert!(message.unwrap().data.capacity() >= 3);
+
+        // Each additional pusher receives a copy.
+        helper.add_pusher(Collect(Rc::clone(&lengths)));
+        tee.push(&mut Some(Message::new(0, vec![Counted, Counted, Counted], 0, 0)));
+        assert_eq!(CLONES.with(Cell::get), 3);
+        assert_eq!(*lengths.borrow(), vec![3, 3, 3]);
+    }
+}
@@ -0,0 +1,306 @@
+//! Keyed state maintained across timestamps, with checkpoints to an external writer.
+use std::hash::Hash;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+
+use crate::ExchangeData;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::channels::pact::Exchange;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::logging::TimelyEvent;
+
+/// Provides the `keyed_store` method.
+///
+/// Maintains a state for each key, updated by the records presented for the key in time order,
+/// and checkpoints the states to a writer once the updates of each time are complete.
+pub trait KeyedStore<S: Scope, K: ExchangeData+Hash+Eq, D: ExchangeData> {
+    /// Tracks a state for each presented key, checkpointing the states of completed times.
+    ///
+    /// Each `(key, data)` record updates the state of its key using `update`, which may create
+    /// the state from `None` or remove it by setting `None`. Updates are applied once their time
+    /// is complete, in time order. For each completed time the operator then emits the new state
+    /// of each key updated at that time, with `None` indicating a removed state, and appends a
+    /// checkpoint of those same states to `writer`.
+    ///
+    /// Checkpoints are only taken of completed times, and each contains the states changed at its
+    /// time, so that the checkpoints up to and including a time reflect exactly the updates of all
+    /// times up to and including it. The operator starts from `states`, which can be restored from
+    /// checkpoints with [`restore_checkpoints`]. If writing a checkpoint fails, the operator logs
+    /// the error as a [`TimelyEvent::Text`] and stops checkpointing, but continues to maintain and
+    /// emit the states.
+    ///
+    /// As each checkpoint is appended, the checkpoints grow with the number of updates over the
+    /// lifetime of the operator rather than with the number of states. They can be rewritten as a
+    /// single checkpoint of the current states with [`compact_checkpoints`], for example before
+    /// restoring from them.
+    ///
+    /// Records are exchanged among workers using `hash` of their key, so that the state of each key
+    /// is maintained by one worker. Each worker should have its own `writer`, and be restored from
+    /// its own checkpoints with the same number of workers and the same `hash`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    /// use timely::dataflow::operators::aggregation::KeyedStore;
+    ///
+    /// timely::example(|scope| {
+    ///
+    ///     (0..10).to_stream(scope)
+    ///            .map(|x| (x % 2, x))
+    ///            .keyed_store(
+    ///                |state: &mut Option<u64>, x| *state = Some(state.unwrap_or(0) + x),
+    ///                |key| *key,
+    ///                Vec::new(),
+    ///                HashMap::new(),
+    ///            )
+    ///            .inspect(|x| assert!(*x == (0, Some(20)) || *x == (1, Some(25))));
+    /// });
+    /// ```
+    fn keyed_store<V, U, H, W>(&self, update: U, hash: H, writer: W, states: HashMap<K, V>) -> Stream<S, (K, Option<V>)>
+    where
+        V: ExchangeData,
+        U: Fn(&mut Option<V>, D)+'static,
+        H: Fn(&K)->u64+'static,
+        W: Write+'static;
+}
+
+impl<S: Scope, K: ExchangeData+Hash+Eq, D: ExchangeData> KeyedStore<S, K, D> for Stream<S, (K, D)> {
+    fn keyed_store<V, U, H, W>(&self, update: U, hash: H, writer: W, mut states: HashMap<K, V>) -> Stream<S, (K, Option<V>)>
+    where
+        V: ExchangeData,
+        U: Fn(&mut Option<V>, D)+'static,
+        H: Fn(&K)->u64+'static,
+        W: Write+'static,
+    {
+        let mut logging = self.scope().logging();
+        let mut writer = Some(writer);
+        let mut pending: HashMap<_, Vec<(K, D)>> = HashMap::new();
+        let mut updated = HashSet::new();
+
+        self.unary_notify(Exchange::new(move |(k, _)| hash(k)), "KeyedStore", vec![], move |input, output, notificator| {
+
+            input.for_each(|time, data| {
+                pending.entry(time.time().clone()).or_default().append(data);
+                notificator.notify_at(time.retain());
+            });
+
+            notificator.for_each(|time,_,_| {
+                if let Some(pend) = pending.remove(time.time()) {
+                    for (key, data) in pend {
+                        let mut state = states.remove(&key);
+                        update(&mut state, data);
+                        if let Some(state) = state {
+                            states.insert(key.clone(), state);
+                        }
+                        updated.insert(key);
+                    }
+
+                    let checkpoint = updated.drain().map(|key| {
+                        let state = states.get(&key).cloned();
+                        (key, state)
+                    }).collect::<Vec<_>>();
+                    output.session(&time).give_iterator(checkpoint.iter().cloned());
+
+                    if let Some(w) = writer.as_mut() {
+                        let result = ::bincode::serialize_into(&mut *w, &checkpoint).and_then(|()| Ok(w.flush()?));
+                        if let Err(error) = result {
+                            if let Some(l) = logging.as_mut() {
+                                l.log(TimelyEvent::Text(format!("KeyedStore stopped checkpointing: {}", error)));
+                            }
+                            writer = None;
+                        }
+                    }
+                }
+            });
+        })
+    }
+}
+
+/// Rewrites the checkpoints written by [`KeyedStore::keyed_store`] that `reader` contains as a
+/// single checkpoint to `writer`, from which the same states are restored.
+///
+/// The checkpoints are read as by [`restore_checkpoints`], whose errors are returned.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::aggregation::keyed_store::compact_checkpoints;
+///
+/// // Two checkpoints, which set and then remove the state of key 0.
+/// let mut checkpoints = Vec::new();
+/// bincode::serialize_into(&mut checkpoints, &vec![(0u64, Some(5u64)), (1, Some(3))]).unwrap();
+/// bincode::serialize_into(&mut checkpoints, &vec![(0u64, None::<u64>)]).unwrap();
+///
+/// let mut compacted = Vec::new();
+/// compact_checkpoints::<u64, u64, _, _>(&checkpoints[..], &mut compacted).unwrap();
+/// let checkpoint: Vec<(u64, Option<u64>)> = bincode::deserialize(&compacted).unwrap();
+/// assert_eq!(checkpoint, vec![(1, Some(3))]);
+/// ```
+pub fn compact_checkpoints<K, V, R, W>(reader: R, mut writer: W) -> Result<(), ::bincode::Error>
+where
+    K: ExchangeData+Hash+Eq,
+    V: ExchangeData,
+    R: Read,
+    W: Write,
+{
+    let checkpoint = restore_checkpoints::<K, V, R>(reader)?.into_iter().map(|(key, state)| (key, Some(state))).collect::<Vec<_>>();
+    ::bincode::serialize_into(&mut writer, &checkpoint)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// The states reflected by the checkpoints written by [`KeyedStore::keyed_store`] that `reader` contains.
+///
+/// The checkpoints are applied in order until the end of `reader`. A checkpoint cut short by the
+/// end, for example by a crash while writing it, is ignored. Other errors, for example from a
+/// corrupt checkpoint, are returned.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::aggregation::keyed_store::restore_checkpoints;
+///
+/// // Two checkpoints, the second of which is cut short.
+/// let mut checkpoints = Vec::new();
+/// bincode::serialize_into(&mut checkpoints, &vec![(0u64, Some(5u64))]).unwrap();
+/// bincode::serialize_into(&mut checkpoints, &vec![(0u64, None::<u64>)]).unwrap();
+/// checkpoints.pop();
+///
+/// let states = restore_checkpoints::<u64, u64, _>(&checkpoints[..]).unwrap();
+/// assert_eq!(states.get(&0), Some(&5));
+/// ```
+pub fn restore_checkpoints<K, V, R>(mut reader: R) -> Result<HashMap<K, V>, ::bincode::Error>
+where
+    K: ExchangeData+Hash+Eq,
+    V: ExchangeData,
+    R: Read,
+{
+    let mut states = HashMap::new();
+    loop {
+        let checkpoint = match ::bincode::deserialize_from::<_, Vec<(K, Option<V>)>>(&mut reader) {
+            Ok(checkpoint) => checkpoint,
+            Err(error) => match *error {
+                ::bincode::ErrorKind::Io(ref error) if error.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(states),
+                _ => return Err(error),
+            },
+        };
+        for (key, state) in checkpoint {
+            match state {
+                Some(state) => { states.insert(key, state); },
+                None => { states.remove(&key); },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Inspect, Probe};
+    use super::{KeyedStore, compact_checkpoints, restore_checkpoints};
+
+    /// A writer appending to a shared buffer.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { self.0.lock().unwrap().write(buf) }
+        fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+    }
+
+    /// A writer that fails to write.
+    struct Failing;
+
+    impl Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> { Err(std::io::ErrorKind::Other.into()) }
+        fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+    }
+
+    /// Counts occurrences of each key, presenting `rounds` of records, and returns the outputs.
+    fn run<W: Write+Send+Sync+'static>(rounds: Vec<Vec<(char, i64)>>, writer: W, states: HashMap<char, i64>) -> Vec<(u64, (char, Option<i64>))> {
+        crate::execute::execute_directly(move |worker| {
+            let outputs = Rc::new(RefCell::new(Vec::new()));
+            let outputs2 = Rc::clone(&outputs);
+            let mut input = InputHandle::new();
+            let probe = worker.dataflow::<u64,_,_>(|scope| {
+                scope
+                    .input_from(&mut input)
+                    .keyed_store(
+                        |count: &mut Option<i64>, diff| {
+                            let new = count.unwrap_or(0) + diff;
+                            *count = if new == 0 { None } else { Some(new) };
+                        },
+                        |key| *key as u64,
+                        writer,
+                        states,
+                    )
+                    .inspect_time(move |time, x| outputs2.borrow_mut().push((*time, *x)))
+                    .probe()
+            });
+            for round in rounds {
+                input.send_batch(&mut round.clone());
+                input.advance_to(input.time() + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+            let mut outputs = outputs.borrow().clone();
+            outputs.sort();
+            outputs
+        })
+    }
+
+    #[test]
+    fn checkpoint_and_restore() {
+        let writer = Shared::default();
+        let outputs = run(vec![vec![('a', 1), ('b', 1), ('a', 1)], vec![('b', -1), ('c', 1)]], writer.clone(), HashMap::new());
+        assert_eq!(outputs, vec![
+            (0, ('a', Some(2))), (0, ('b', Some(1))),
+            (1, ('b', None)), (1, ('c', Some(1))),
+        ]);
+
+        // Recreate the operator from the checkpoint, followed by a partially written checkpoint.
+        let mut checkpoint = writer.0.lock().unwrap().clone();
+        let complete = checkpoint.len();
+        checkpoint.extend_from_within(.. complete / 4);
+        let states = restore_checkpoints(&checkpoint[..]).unwrap();
+        let outputs = run(vec![vec![('a', 1), ('c', 1), ('d', 1)]], Shared::default(), states);
+        assert_eq!(outputs, vec![(0, ('a', Some(3))), (0, ('c', Some(2))), (0, ('d', Some(1)))]);
+    }
+
+    #[test]
+    fn compact_and_restore() {
+        let writer = Shared::default();
+        // The rounds create, update, and remove the state of 'a', which compaction leaves out.
+        let rounds = vec![vec![('a', 1), ('b', 1)], vec![('a', 1)], vec![('a', -2)]];
+        run(rounds, writer.clone(), HashMap::new());
+        let checkpoints = writer.0.lock().unwrap().clone();
+
+        let mut compacted = Vec::new();
+        compact_checkpoints::<char, i64, _, _>(&checkpoints[..], &mut compacted).unwrap();
+        assert!(compacted.len() < checkpoints.len());
+        let states = restore_checkpoints(&compacted[..]).unwrap();
+        let outputs = run(vec![vec![('a', 1), ('b', 1)]], Shared::default(), states);
+        assert_eq!(outputs, vec![(0, ('a', Some(1))), (0, ('b', Some(2)))]);
+    }
+
+    #[test]
+    fn restore_corrupt() {
+        let mut checkpoint = Vec::new();
+        bincode::serialize_into(&mut checkpoint, &vec![('a', Some(1i64))]).unwrap();
+        // Replace the tag of the state, following the length and the key, by an invalid one.
+        checkpoint[9] = 7;
+        assert!(restore_checkpoints::<char, i64, _>(&checkpoint[..]).is_err());
+    }
+
+    #[test]
+    fn failing_writer() {
+        // The states are maintained and emitted even though they cannot be checkpointed.
+        let outputs = run(vec![vec![('a', 1)], vec![('a', 1)]], Failing, HashMap::new());
+        assert_eq!(outputs, vec![(0, ('a', Some(1))), (1, ('a', Some(2)))]);
+    }
+}
@@ -1,6 +1,6 @@
 //! Aggregation operators of various flavors
 //!
-//! Two traits, `Aggregate` and `StateMachine`, which support the accumulation of streamed information.
+//! Traits `Aggregate`, `StateMachine`, `KeyedStore`, `PartialThenFinal`, and `TumblingWindow`, which support the accumulation of streamed information.
 //!
 //! `Aggregate` accumulates records within times, and releases the accumulations once the time is complete.
 //!
@@ -8,11 +8,26 @@
 //! The user logic may produce output records for each transition, and optionally de-register the state to
 //! clean up when appropriate.
 //!
-//! The two methods are often combined, using first `Aggregate` to reduce the volume of information, and then
+//! `KeyedStore` maintains a state for each key like `StateMachine`, checkpointing the states to a writer
+//! as times complete, from which they can be restored.
+//!
+//! `PartialThenFinal` accumulates records within times on each worker, releasing each worker's partial
+//! accumulation once the time is complete, followed by a final accumulation merging the partials.
+//!
+//! `TumblingWindow` accumulates records within disjoint windows of times, and releases the accumulations
+//! once the windows are complete.
+//!
+//! The first two methods are often combined, using first `Aggregate` to reduce the volume of information, and then
 //! `StateMachine` to track an accumulation across timestamps.
 
 pub use self::aggregate::Aggregate;
 pub use self::state_machine::StateMachine;
+pub use self::partial::PartialThenFinal;
+pub use self::keyed_store::KeyedStore;
+pub use self::window::TumblingWindow;
 
 pub mod state_machine;
 pub mod aggregate;
+pub mod partial;
+pub mod keyed_store;
+pub mod window;
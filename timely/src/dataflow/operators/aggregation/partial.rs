@@ -0,0 +1,138 @@
+//! Per-worker partial aggregation followed by a merged final aggregation.
+use std::collections::HashMap;
+
+use crate::{Data, ExchangeData};
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::channels::pact::{Exchange, Pipeline};
+use crate::dataflow::operators::{Concat, Map};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Progressive intra-timestamp aggregation
+///
+/// Extension method producing both per-worker partial aggregates and merged final aggregates.
+pub trait PartialThenFinal<S: Scope, D: Data> {
+    /// Aggregates records within each time, reporting partial and then final results.
+    ///
+    /// Each worker folds its records at each time into a copy of `init` using `local`. Once
+    /// the operator's input frontier passes a time, the worker emits its partial aggregate as
+    /// `(false, partial)` and sends it to worker zero. Once the frontier of worker zero's merging
+    /// operator passes the time, which happens only after every worker has produced its partial,
+    /// worker zero folds the partials into a copy of `init` using `combine` and emits the result
+    /// as `(true, final)`.
+    ///
+    /// Workers that receive no records at a time produce no partial aggregate for it, and a
+    /// final aggregate is produced only for times at which some worker produced a partial.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::aggregation::PartialThenFinal;
+    ///
+    /// timely::example(|scope| {
+    ///
+    ///     (0..10).to_stream(scope)
+    ///         .partial_then_final(0, |sum, x| *sum += x, |sum, part| *sum += part)
+    ///         .inspect(|x| assert_eq!(x.1, 45));
+    /// });
+    /// ```
+    fn partial_then_final<A, F, C>(&self, init: A, local: F, combine: C) -> Stream<S, (bool, A)>
+    where
+        A: ExchangeData,
+        F: Fn(&mut A, D)+'static,
+        C: Fn(&mut A, A)+'static;
+}
+
+impl<S: Scope, D: Data> PartialThenFinal<S, D> for Stream<S, D> {
+
+    fn partial_then_final<A, F, C>(&self, init: A, local: F, combine: C) -> Stream<S, (bool, A)>
+    where
+        A: ExchangeData,
+        F: Fn(&mut A, D)+'static,
+        C: Fn(&mut A, A)+'static,
+    {
+        let init2 = init.clone();
+
+        let mut partials = HashMap::new();
+        let partial = self.unary_notify(Pipeline, "PartialAggregate", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                let agg = partials.entry(time.time().clone()).or_insert_with(|| init.clone());
+                for datum in data.drain(..) {
+                    local(agg, datum);
+                }
+                notificator.notify_at(time.retain());
+            });
+            notificator.for_each(|time,_,_| {
+                if let Some(agg) = partials.remove(time.time()) {
+                    output.session(&time).give(agg);
+                }
+            });
+        });
+
+        let mut finals = HashMap::new();
+        let merged = partial.unary_notify(Exchange::new(|_| 0), "FinalAggregate", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                let agg = finals.entry(time.time().clone()).or_insert_with(|| init2.clone());
+                for part in data.drain(..) {
+                    combine(agg, part);
+                }
+                notificator.notify_at(time.retain());
+            });
+            notificator.for_each(|time,_,_| {
+                if let Some(agg) = finals.remove(time.time()) {
+                    output.session(&time).give(agg);
+                }
+            });
+        });
+
+        partial
+            .map(|agg| (false, agg))
+            .concat(&merged.map(|agg| (true, agg)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Inspect, Probe};
+    use super::PartialThenFinal;
+
+    #[test]
+    fn partials_then_final() {
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let results2 = Arc::clone(&results);
+        crate::execute(crate::Config::process(3), move |worker| {
+            let index = worker.index() as u64;
+            let results = Arc::clone(&results2);
+            let mut input = InputHandle::new();
+            let probe = worker.dataflow::<u64,_,_>(|scope| {
+                scope
+                    .input_from(&mut input)
+                    .partial_then_final(0, |sum, x| *sum += x, |sum, part| *sum += part)
+                    .inspect_time(move |time, x| results.lock().unwrap().push((index, *time, *x)))
+                    .probe()
+            });
+            for round in 0 .. 4u64 {
+                input.send(round + index);
+                input.send(round * 10);
+                input.advance_to(round + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+        }).unwrap();
+
+        let results = results.lock().unwrap();
+        for round in 0 .. 4u64 {
+            // Three partials and one final.
+            let mut at_round = results.iter().filter(|(_, time, _)| *time == round).map(|x| x.2).collect::<Vec<_>>();
+            at_round.sort();
+            let mut expected = (0 .. 3).map(|index| (false, round + index + round * 10)).collect::<Vec<_>>();
+            expected.push((true, 3 * round + 3 + 30 * round));
+            assert_eq!(at_round, expected);
+            // Worker zero observes its own partial before the final.
+            let at_zero = results.iter().filter(|(index, time, _)| *index == 0 && *time == round).map(|x| x.2.0).collect::<Vec<_>>();
+            assert_eq!(at_zero, vec![false, true]);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Aggregation of records into fixed windows of timestamps.
+
+use std::collections::BTreeMap;
+
+use crate::Data;
+use crate::order::PartialOrder;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use crate::dataflow::{Scope, Stream};
+
+/// Provides the `tumbling_window` method.
+pub trait TumblingWindow<S: Scope, D: Data> {
+    /// Aggregates records into disjoint windows of timestamps, producing one result per window.
+    ///
+    /// The window of each record is identified by its upper bound, the result of `assign` applied
+    /// to the record's time, which should be greater than the time. For windows of width `w` over
+    /// integer times, `assign` might be `|t| (t / w + 1) * w`. Records are folded into the state
+    /// of their window using `fold`, starting from a default state, and once the input frontier
+    /// no longer contains times less than the upper bound of a window, `emit` is applied to the
+    /// upper bound and the state of the window, and its result is produced at the upper bound.
+    ///
+    /// The second returned stream contains late records, whose window's upper bound the input
+    /// frontier has already reached when they are received, unchanged and at their times. Records
+    /// that `assign` maps to an upper bound not greater or equal to their time are also late, even
+    /// if the input frontier has not yet reached the upper bound.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::aggregation::TumblingWindow;
+    ///
+    /// timely::example(|scope| {
+    ///     let (sums, late) = (0..10u64)
+    ///         .to_stream(scope)
+    ///         .delay(|x, _| *x)
+    ///         .tumbling_window(
+    ///             |t| (t / 5 + 1) * 5,
+    ///             |sum: &mut u64, x| *sum += x,
+    ///             |upper, sum| (*upper, sum),
+    ///         );
+    ///
+    ///     sums.inspect(|x| assert!(*x == (5, 10) || *x == (10, 35)));
+    ///     late.inspect(|x| panic!("late record: {:?}", x));
+    /// });
+    /// ```
+    fn tumbling_window<W, R, A, F, E>(&self, assign: A, fold: F, emit: E) -> (Stream<S, R>, Stream<S, D>)
+    where
+        W: Default+'static,
+        R: Data,
+        A: Fn(&S::Timestamp)->S::Timestamp+'static,
+        F: Fn(&mut W, D)+'static,
+        E: Fn(&S::Timestamp, W)->R+'static;
+}
+
+impl<S: Scope, D: Data> TumblingWindow<S, D> for Stream<S, D> {
+    fn tumbling_window<W, R, A, F, E>(&self, assign: A, fold: F, emit: E) -> (Stream<S, R>, Stream<S, D>)
+    where
+        W: Default+'static,
+        R: Data,
+        A: Fn(&S::Timestamp)->S::Timestamp+'static,
+        F: Fn(&mut W, D)+'static,
+        E: Fn(&S::Timestamp, W)->R+'static,
+    {
+        let mut builder = OperatorBuilder::new("TumblingWindow".to_owned(), self.scope());
+
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut window_output, window_stream) = builder.new_output();
+        let (mut late_output, late_stream) = builder.new_output();
+
+        builder.build(move |_| {
+
+            // windows by upper bound, with a capability for the upper bound and the window state.
+            let mut windows = BTreeMap::new();
+
+            move |frontiers| {
+                let mut window_handle = window_output.activate();
+                let mut late_handle = late_output.activate();
+
+                let frontier = &frontiers[0];
+                input.for_each(|time, data| {
+                    let upper = assign(time.time());
+                    if time.time().less_equal(&upper) && frontier.less_than(&upper) {
+                        let (_, state) = windows
+                            .entry(upper)
+                            .or_insert_with_key(|upper| (time.delayed_for_output(upper, 0), W::default()));
+                        for datum in data.drain(..) {
+                            fold(state, datum);
+                        }
+                    }
+                    else {
+                        late_handle.session(&time).give_container(data);
+                    }
+                });
+
+                // Produce the windows whose upper bounds the input frontier has reached.
+                let complete = windows.keys().filter(|upper| !frontier.less_than(*upper)).cloned().collect::<Vec<_>>();
+                for upper in complete {
+                    let (capability, state) = windows.remove(&upper).unwrap();
+                    window_handle.session(&capability).give(emit(&upper, state));
+                }
+            }
+        });
+
+        (window_stream, late_stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture, Concat};
+    use crate::dataflow::operators::capture::Extract;
+    use super::TumblingWindow;
+
+    #[test]
+    fn tumbling_window() {
+        let (windows, late) = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (probe, windows, late) = worker.dataflow::<u64,_,_>(|scope| {
+                // Windows of width three, except that time 7 is assigned to the window ending at 6.
+                let (windows, late) = scope.input_from(&mut input).tumbling_window(
+                    |t| if *t == 7 { 6 } else { (t / 3 + 1) * 3 },
+                    |count: &mut usize, _x: u64| *count += 1,
+                    |upper, count| (*upper, count),
+                );
+                (windows.probe(), windows.capture(), late.capture())
+            });
+            for round in 0..9 {
+                input.send(round);
+                input.send(round + 100);
+                input.advance_to(round + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+            (windows, late)
+        });
+
+        assert_eq!(windows.extract(), vec![(3, vec![(3, 6)]), (6, vec![(6, 6)]), (9, vec![(9, 4)])]);
+        assert_eq!(late.extract(), vec![(7, vec![7, 107])]);
+    }
+
+    #[test]
+    fn tumbling_window_upper_before_time() {
+        let (windows, late) = crate::execute::execute_directly(|worker| {
+            let mut input1 = InputHandle::new();
+            let mut input2 = InputHandle::new();
+            let (probe, windows, late) = worker.dataflow::<u64,_,_>(|scope| {
+                // Time 7 is assigned to the window ending at 6, while the second input holds the frontier at 5.
+                let (windows, late) = scope.input_from(&mut input1)
+                    .concat(&scope.input_from(&mut input2))
+                    .tumbling_window(
+                        |t| if *t == 7 { 6 } else { (t / 3 + 1) * 3 },
+                        |count: &mut usize, _x: u64| *count += 1,
+                        |upper, count| (*upper, count),
+                    );
+                (windows.probe(), windows.capture(), late.capture())
+            });
+            input2.advance_to(5);
+            input1.advance_to(7);
+            input1.send(7);
+            input1.advance_to(8);
+            worker.step_while(|| probe.less_than(&5));
+            worker.step();
+            drop((input1, input2));
+            worker.step_while(|| !probe.done());
+            (windows, late)
+        });
+
+        assert_eq!(windows.extract(), vec![]);
+        assert_eq!(late.extract(), vec![(7, vec![7])]);
+    }
+}
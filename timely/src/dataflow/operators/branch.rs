@@ -1,4 +1,6 @@
-//! Operators that separate one stream into two streams based on some condition
+//! Operators that separate one stream into two or three streams based on some condition
+
+use std::cmp::Ordering;
 
 use crate::dataflow::channels::pact::Pipeline;
 use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
@@ -32,6 +34,38 @@ pub trait Branch<S: Scope, D: Data> {
         &self,
         condition: impl Fn(&S::Timestamp, &D) -> bool + 'static,
     ) -> (Stream<S, D>, Stream<S, D>);
+
+    /// Takes one input stream and splits it into three output streams.
+    /// For each record, the supplied closure is called with a reference to
+    /// the data and its time. The record is sent to the first, second, or third
+    /// returned stream if the closure returns `Less`, `Equal`, or `Greater`,
+    /// respectively.
+    ///
+    /// All records are examined by a single operator, and each is sent to
+    /// exactly one of the streams.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Branch, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     let (below, within, above) = (0..10)
+    ///         .to_stream(scope)
+    ///         .branch3(|_time, x| {
+    ///             if *x < 3 { std::cmp::Ordering::Less }
+    ///             else if *x < 7 { std::cmp::Ordering::Equal }
+    ///             else { std::cmp::Ordering::Greater }
+    ///         });
+    ///
+    ///     below.inspect(|x| println!("below range: {:?}", x));
+    ///     within.inspect(|x| println!("within range: {:?}", x));
+    ///     above.inspect(|x| println!("above range: {:?}", x));
+    /// });
+    /// ```
+    fn branch3(
+        &self,
+        condition: impl Fn(&S::Timestamp, &D) -> Ordering + 'static,
+    ) -> (Stream<S, D>, Stream<S, D>, Stream<S, D>);
 }
 
 impl<S: Scope, D: Data> Branch<S, D> for Stream<S, D> {
@@ -66,6 +100,41 @@ impl<S: Scope, D: Data> Branch<S, D> for Stream<S, D> {
 
         (stream1, stream2)
     }
+
+    fn branch3(
+        &self,
+        condition: impl Fn(&S::Timestamp, &D) -> Ordering + 'static,
+    ) -> (Stream<S, D>, Stream<S, D>, Stream<S, D>) {
+        let mut builder = OperatorBuilder::new("Branch3".to_owned(), self.scope());
+
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut output1, stream1) = builder.new_output();
+        let (mut output2, stream2) = builder.new_output();
+        let (mut output3, stream3) = builder.new_output();
+
+        builder.build(move |_| {
+            move |_frontiers| {
+                let mut output1_handle = output1.activate();
+                let mut output2_handle = output2.activate();
+                let mut output3_handle = output3.activate();
+
+                input.for_each(|time, data| {
+                    let mut out1 = output1_handle.session(&time);
+                    let mut out2 = output2_handle.session(&time);
+                    let mut out3 = output3_handle.session(&time);
+                    for datum in data.drain(..) {
+                        match condition(time.time(), &datum) {
+                            Ordering::Less => out1.give(datum),
+                            Ordering::Equal => out2.give(datum),
+                            Ordering::Greater => out3.give(datum),
+                        }
+                    }
+                });
+            }
+        });
+
+        (stream1, stream2, stream3)
+    }
 }
 
 /// Extension trait for `Stream`.
@@ -120,3 +189,23 @@ impl<S: Scope, C: Container + Data> BranchWhen<S::Timestamp> for StreamCore<S, C
         (stream1, stream2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::operators::{ToStream, Branch, Capture};
+    use crate::dataflow::operators::capture::Extract;
+
+    #[test]
+    fn branch3() {
+        let (below, within, above) = crate::example(|scope| {
+            let (below, within, above) = (0..10)
+                .to_stream(scope)
+                .branch3(|_time, x| x.cmp(&5));
+            (below.capture(), within.capture(), above.capture())
+        });
+        assert_eq!(below.extract(), vec![(0, vec![0, 1, 2, 3, 4])]);
+        assert_eq!(within.extract(), vec![(0, vec![5])]);
+        assert_eq!(above.extract(), vec![(0, vec![6, 7, 8, 9])]);
+    }
+}
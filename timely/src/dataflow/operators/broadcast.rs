@@ -1,4 +1,4 @@
-//! Broadcast records to all workers.
+//! Broadcast records to all workers, or to subsets of workers.
 
 use crate::ExchangeData;
 use crate::dataflow::{Stream, Scope};
@@ -19,6 +19,23 @@ pub trait Broadcast<D: ExchangeData> {
     /// });
     /// ```
     fn broadcast(&self) -> Self;
+
+    /// Broadcast records to all workers other than the worker that holds them.
+    ///
+    /// Unlike `broadcast`, a worker does not receive its own records, and so does not need
+    /// to filter them out. With a single worker no records are delivered.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Broadcast, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .broadcast_others()
+    ///            .inspect(|_| panic!("a single worker has no others"));
+    /// });
+    /// ```
+    fn broadcast_others(&self) -> Self;
 }
 
 impl<G: Scope, D: ExchangeData> Broadcast<D> for Stream<G, D> {
@@ -32,4 +49,115 @@ impl<G: Scope, D: ExchangeData> Broadcast<D> for Stream<G, D> {
             .exchange(|ix| ix.0)
             .map(|(_i,x)| x)
     }
+
+    fn broadcast_others(&self) -> Stream<G, D> {
+        let peers = self.scope().peers() as u64;
+        let index = self.scope().index() as u64;
+        self.flat_map(move |x| (0 .. peers).filter(move |i| *i != index).map(move |i| (i,x.clone())))
+            .exchange(|ix| ix.0)
+            .map(|(_i,x)| x)
+    }
+}
+
+/// Replicate records to subsets of workers.
+pub trait Replicate<D: ExchangeData> {
+    /// Replicate each record to `k` distinct workers determined by a key.
+    ///
+    /// The closure supplied should map a reference to a record to a `u64`. The record is
+    /// routed to the worker `key % peers` and to the `k - 1` workers following it, wrapping
+    /// around. The routing depends only on the key, so all workers agree on it. When `k` is
+    /// one this routes records exactly as `exchange` with the same closure, and when `k` is
+    /// the number of peers each record is delivered to all workers, as with `broadcast`.
+    ///
+    /// This method panics if `k` is zero or exceeds the number of peers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Replicate, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .replicate(1, |x| *x)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn replicate<F: FnMut(&D)->u64+'static>(&self, k: usize, route: F) -> Self;
+}
+
+impl<G: Scope, D: ExchangeData> Replicate<D> for Stream<G, D> {
+    fn replicate<F: FnMut(&D)->u64+'static>(&self, k: usize, mut route: F) -> Stream<G, D> {
+
+        let peers = self.scope().peers() as u64;
+        assert!(k > 0, "replication factor must be positive");
+        assert!(k as u64 <= peers, "replication factor {} exceeds the number of peers {}", k, peers);
+        let k = k as u64;
+
+        // Like `broadcast`, pair each copy with its destination so that the exchange
+        // does not need to change the number of records.
+        self.flat_map(move |x| {
+                let first = route(&x) % peers;
+                (0 .. k).map(move |i| ((first + i) % peers, x.clone()))
+            })
+            .exchange(|ix| ix.0)
+            .map(|(_i,x)| x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::dataflow::operators::{ToStream, Inspect};
+    use super::{Broadcast, Replicate};
+
+    #[test]
+    fn broadcast_others() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen2 = Arc::clone(&seen);
+        crate::execute(crate::Config::process(3), move |worker| {
+            let index = worker.index();
+            let seen = Arc::clone(&seen2);
+            worker.dataflow::<u64,_,_>(|scope| {
+                Some(index)
+                    .to_stream(scope)
+                    .broadcast_others()
+                    .inspect(move |x| seen.lock().unwrap().push((*x, index)));
+            });
+        }).unwrap();
+
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn replicate() {
+        for k in 1 ..= 4 {
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            let seen2 = Arc::clone(&seen);
+            crate::execute(crate::Config::process(4), move |worker| {
+                let index = worker.index();
+                let seen = Arc::clone(&seen2);
+                worker.dataflow::<u64,_,_>(|scope| {
+                    (0 .. 20u64)
+                        .filter(move |x| (*x as usize) % 4 == index)
+                        .to_stream(scope)
+                        .replicate(k, |x| *x)
+                        .inspect(move |x| seen.lock().unwrap().push((*x, index)));
+                });
+            }).unwrap();
+
+            let mut seen = seen.lock().unwrap().clone();
+            seen.sort();
+            let mut expected = Vec::new();
+            for x in 0 .. 20u64 {
+                for i in 0 .. k {
+                    expected.push((x, (x as usize + i) % 4));
+                }
+            }
+            expected.sort();
+            assert_eq!(seen, expected);
+        }
+    }
 }
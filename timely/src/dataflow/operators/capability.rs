@@ -115,7 +115,7 @@ impl<T: Timestamp> Capability<T> {
     /// Attempts to make a new capability for a timestamp `new_time` that is
     /// greater or equal to the timestamp of the source capability (`self`).
     ///
-    /// Returns [`None`] `self.time` is not less or equal to `new_time`.
+    /// Returns [`None`] if `self.time` is not less or equal to `new_time`.
     pub fn try_delayed(&self, new_time: &T) -> Option<Capability<T>> {
         if self.time.less_equal(new_time) {
             Some(Self::new(new_time.clone(), self.internal.clone()))
@@ -441,6 +441,41 @@ impl<T: Timestamp> CapabilitySet<T> {
         }
     }
 
+    /// The least time of the capabilities in the set, or `None` if the set is empty.
+    ///
+    /// For partially ordered times this is the least time in the order of `Ord`, which is one
+    /// of the incomparable times of the set.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::operators::CapabilitySet;
+    /// use timely::dataflow::operators::generic::source;
+    ///
+    /// timely::example(|scope| {
+    ///     source::<_, CapacityContainerBuilder<Vec<()>>, _, _>(scope, "example", |default_cap, _info| {
+    ///         let mut cap = CapabilitySet::from_elem(default_cap);
+    ///         assert_eq!(cap.minimum_time(), Some(&0));
+    ///         cap.downgrade(&[3]);
+    ///         assert_eq!(cap.minimum_time(), Some(&3));
+    ///         assert_eq!(cap.frontier().to_vec(), vec![3]);
+    ///         cap.downgrade(Vec::<u64>::new());
+    ///         assert_eq!(cap.minimum_time(), None);
+    ///         |_output| { }
+    ///     });
+    /// });
+    /// ```
+    pub fn minimum_time(&self) -> Option<&T> {
+        self.elements.iter().map(|c| c.time()).min()
+    }
+
+    /// The times of the capabilities in the set, as an antichain.
+    ///
+    /// The antichain is collected from the capabilities on each call.
+    pub fn frontier(&self) -> Antichain<T> {
+        self.elements.iter().map(|c| c.time().clone()).collect()
+    }
+
     /// Creates a new capability to send data at `time`.
     ///
     /// This method panics if there does not exist a capability in `self.elements` less or equal to `time`.
@@ -502,7 +537,7 @@ impl<T: Timestamp> CapabilitySet<T> {
 
     /// Attempts to downgrade the set of capabilities to correspond with the times in `frontier`.
     ///
-    /// Returns [`None`] if any element of `frontier` is not greater or equal to some element of `self.elements`.
+    /// Returns a [DowngradeError] if any element of `frontier` is not greater or equal to some element of `self.elements`.
     ///
     /// **Warning**: If an error is returned the capability set may be in an inconsistent state and can easily
     /// cause logic errors within the program if not properly handled.
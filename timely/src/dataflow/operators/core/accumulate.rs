@@ -0,0 +1,193 @@
+//! Extension trait and implementation for accumulating containers within a time.
+
+use std::collections::HashMap;
+
+use crate::container::{Container, ContainerBuilder, PushInto};
+use crate::Data;
+use crate::dataflow::{Scope, StreamCore};
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::operators::{CapabilitySet, FrontierNotificator};
+
+/// Accumulates containers within a time.
+pub trait Accumulate<G: Scope, C: Container> {
+    /// Folds the containers at each time into a state, and produces the state once the time completes.
+    ///
+    /// Each time starts from a clone of `default`, and `logic` is applied to the state of the
+    /// time and each container received at the time. Once the input frontier no longer contains
+    /// the time, the state is produced exactly once, through the container builder `CB`. Times
+    /// at which no containers are received produce nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::operators::{ToStream, Capture};
+    /// use timely::dataflow::operators::core::Accumulate;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .accumulate::<CapacityContainerBuilder<Vec<_>>, _, _>(0, |sum, data: &Vec<u64>| *sum += data.iter().sum::<u64>())
+    ///            .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![45])]);
+    /// ```
+    fn accumulate<CB, A, L>(&self, default: A, logic: L) -> StreamCore<G, CB::Container>
+    where
+        CB: ContainerBuilder + PushInto<A>,
+        A: Clone + 'static,
+        L: FnMut(&mut A, &C) + 'static;
+
+    /// Counts the records at each time, and produces `(time, count)` once the time completes.
+    ///
+    /// Records are counted with `Container::len`, and the counts of all containers at a time
+    /// are summed before the time is produced. Times at which no records are received produce
+    /// nothing, unless `zeros` is set, in which case each time the operator observes in its input
+    /// frontier also produces its count, even if zero. The operator then holds capabilities for
+    /// its input frontier, and times the frontier passes between schedulings are not observed.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Capture};
+    /// use timely::dataflow::operators::core::Accumulate;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _| *x / 4)
+    ///            .count_by_time(false)
+    ///            .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![(0, 4)]), (1, vec![(1, 4)]), (2, vec![(2, 2)])]);
+    /// ```
+    fn count_by_time(&self, zeros: bool) -> StreamCore<G, Vec<(G::Timestamp, usize)>>;
+}
+
+impl<G: Scope, C: Container + Data> Accumulate<G, C> for StreamCore<G, C> {
+    fn accumulate<CB, A, L>(&self, default: A, mut logic: L) -> StreamCore<G, CB::Container>
+    where
+        CB: ContainerBuilder + PushInto<A>,
+        A: Clone + 'static,
+        L: FnMut(&mut A, &C) + 'static,
+    {
+        let mut accums = HashMap::new();
+        self.unary_notify::<CB, _, _>(Pipeline, "Accumulate", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                logic(accums.entry(time.time().clone()).or_insert_with(|| default.clone()), &*data);
+                notificator.notify_at(time.retain());
+            });
+
+            notificator.for_each(|time,_,_| {
+                if let Some(accum) = accums.remove(&time) {
+                    output.session_with_builder(&time).give(accum);
+                }
+            });
+        })
+    }
+
+    fn count_by_time(&self, zeros: bool) -> StreamCore<G, Vec<(G::Timestamp, usize)>> {
+        self.unary_frontier(Pipeline, "CountByTime", move |capability, _info| {
+            let mut counts = HashMap::new();
+            let mut notificator = FrontierNotificator::default();
+            // To produce times without records, hold capabilities for the input frontier.
+            let mut frontier = CapabilitySet::new();
+            if zeros { frontier.insert(capability); }
+
+            move |input, output| {
+                input.for_each(|time, data| {
+                    *counts.entry(time.time().clone()).or_insert(0) += data.len();
+                    notificator.notify_at(time.retain());
+                });
+
+                if !frontier.is_empty() {
+                    for time in input.frontier().frontier().iter() {
+                        if !counts.contains_key(time) {
+                            counts.insert(time.clone(), 0);
+                            notificator.notify_at(frontier.delayed(time));
+                        }
+                    }
+                    frontier.downgrade(&input.frontier().frontier());
+                }
+
+                notificator.for_each(&[input.frontier()], |time, _| {
+                    if let Some(count) = counts.remove(time.time()) {
+                        if zeros || count > 0 {
+                            output.session(&time).give((time.time().clone(), count));
+                        }
+                    }
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::container::CapacityContainerBuilder;
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::capture::Extract;
+    use super::Accumulate;
+
+    #[test]
+    fn accumulate_once_per_time() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream = scope
+                    .input_from(&mut input)
+                    .accumulate::<CapacityContainerBuilder<Vec<_>>, _, _>((0, 0), |(batches, records), data: &Vec<u64>| {
+                        *batches += 1;
+                        *records += data.len();
+                    });
+                (stream.probe(), stream.capture())
+            });
+            // Batches at a time arriving in several steps are folded into one output.
+            input.send_batch(&mut vec![0, 1]);
+            worker.step();
+            input.send_batch(&mut vec![2]);
+            worker.step();
+            input.advance_to(2);
+            input.send_batch(&mut vec![3, 4, 5]);
+            input.advance_to(3);
+            worker.step_while(|| probe.less_than(input.time()));
+            captured
+        });
+
+        assert_eq!(captured.extract(), vec![(0, vec![(2, 3)]), (2, vec![(1, 3)])]);
+    }
+
+    #[test]
+    fn count_by_time() {
+        for zeros in [false, true] {
+            let captured = crate::execute::execute_directly(move |worker| {
+                let mut input = InputHandle::new();
+                let (probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                    let stream = scope.input_from(&mut input).count_by_time(zeros);
+                    (stream.probe(), stream.capture())
+                });
+                // Records at the first time arrive in several batches, and the second time has none.
+                for (round, batches) in [vec![vec![0, 1], vec![2]], vec![], vec![vec![3, 4, 5]]].into_iter().enumerate() {
+                    for mut batch in batches {
+                        input.send_batch(&mut batch);
+                        worker.step();
+                    }
+                    input.advance_to(round as u64 + 1);
+                    worker.step_while(|| probe.less_than(input.time()));
+                }
+                captured
+            });
+
+            // With zeros, the final time the input frontier reaches before the input closes is produced too.
+            let expected = if zeros {
+                vec![(0, vec![(0, 3)]), (1, vec![(1, 0)]), (2, vec![(2, 3)]), (3, vec![(3, 0)])]
+            } else {
+                vec![(0, vec![(0, 3)]), (2, vec![(2, 3)])]
+            };
+            assert_eq!(captured.extract(), expected);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Extension trait and implementation for holding back data until its time completes.
+
+use std::collections::HashMap;
+
+use crate::{Container, Data};
+use crate::container::{ContainerBuilder, PushInto};
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::FrontierNotificator;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::{Scope, StreamCore};
+
+/// Holds back data until the input frontier passes its time.
+pub trait BufferUntilFrontier<G: Scope, C: Container> {
+    /// Buffers received data by time, and sends all data for a time once the input frontier no
+    /// longer contains it.
+    ///
+    /// Each time's data are sent in one session, after all of them have been received, and no
+    /// data are sent for a time before then. The data for each time are accumulated in a container
+    /// builder of type `CB`, which determines the type of the output containers, and the builder
+    /// is dropped as soon as its time completes. Times complete in the order their notifications
+    /// are delivered, which is an order consistent with the partial order on times.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::core::BufferUntilFrontier;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _| *x / 3)
+    ///            .buffer_until_frontier::<CapacityContainerBuilder<Vec<_>>>()
+    ///            .inspect_batch(|t, xs| println!("complete at {:?}: {:?}", t, xs));
+    /// });
+    /// ```
+    fn buffer_until_frontier<CB>(&self) -> StreamCore<G, CB::Container>
+    where
+        CB: ContainerBuilder + for<'a> PushInto<C::Item<'a>>;
+}
+
+impl<G: Scope, C: Container + Data> BufferUntilFrontier<G, C> for StreamCore<G, C> {
+    fn buffer_until_frontier<CB>(&self) -> StreamCore<G, CB::Container>
+    where
+        CB: ContainerBuilder + for<'a> PushInto<C::Item<'a>>,
+    {
+        self.unary_frontier::<CB, _, _, _>(Pipeline, "BufferUntilFrontier", |_capability, _info| {
+
+            let mut notificator = FrontierNotificator::default();
+            // A builder for each time that has received data but not yet completed.
+            let mut buffers = HashMap::<G::Timestamp, CB>::new();
+
+            move |input, output| {
+                input.for_each(|time, data| {
+                    let builder = buffers
+                        .entry(time.time().clone())
+                        .or_insert_with(|| { notificator.notify_at(time.retain()); CB::default() });
+                    for item in data.drain() {
+                        builder.push_into(item);
+                    }
+                });
+
+                notificator.for_each(&[input.frontier()], |capability, _| {
+                    if let Some(mut builder) = buffers.remove(capability.time()) {
+                        let mut session = output.session_with_builder(&capability);
+                        while let Some(container) = builder.finish() {
+                            session.give_container(container);
+                        }
+                    }
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::container::CapacityContainerBuilder;
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::capture::extract::extract_batches;
+    use super::BufferUntilFrontier;
+
+    #[test]
+    fn buffer_until_frontier() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream = scope.input_from(&mut input)
+                                  .buffer_until_frontier::<CapacityContainerBuilder<Vec<u64>>>();
+                (stream.probe(), stream.capture())
+            });
+            // Data for each time arrive over several steps, which must not be sent until the time completes.
+            for round in 0 .. 3u64 {
+                for _ in 0 .. 3 {
+                    input.send(round);
+                    worker.step();
+                }
+                input.advance_to(round + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+            captured
+        });
+        let messages = extract_batches(captured.try_iter());
+        assert_eq!(messages, vec![(0, vec![0, 0, 0]), (1, vec![1, 1, 1]), (2, vec![2, 2, 2])]);
+    }
+}
@@ -5,6 +5,8 @@
 //! and there are several default implementations, including a linked-list, Rust's MPSC
 //! queue, and a binary serializer wrapping any `W: Write`.
 
+use std::sync::{Arc, Mutex};
+
 use crate::dataflow::{Scope, StreamCore};
 use crate::dataflow::channels::pact::Pipeline;
 use crate::dataflow::channels::pullers::Counter as PullCounter;
@@ -111,6 +113,29 @@ pub trait Capture<T: Timestamp, C: Container + Data> {
         self.capture_into(send);
         recv
     }
+
+    /// Captures a stream into a shared vector of events.
+    ///
+    /// The vector receives both data and progress events, as the stream's capture operator
+    /// produces them, and contains all events of the stream once the dataflow completes.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{Capture, ToStream};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .capture_into_shared()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, (0..10).collect::<Vec<_>>())]);
+    /// ```
+    fn capture_into_shared(&self) -> Arc<Mutex<Vec<Event<T, C>>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        self.capture_into(Arc::clone(&events));
+        events
+    }
 }
 
 impl<S: Scope, C: Container + Data> Capture<S::Timestamp, C> for StreamCore<S, C> {
@@ -147,3 +172,35 @@ impl<S: Scope, C: Container + Data> Capture<S::Timestamp, C> for StreamCore<S, C
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::operators::{Capture, ToStream};
+    use crate::dataflow::operators::capture::Event;
+    use crate::progress::ChangeBatch;
+
+    #[test]
+    fn capture_into_shared() {
+        let captured = crate::example(|scope| {
+            (0..10u64).to_stream(scope).capture_into_shared()
+        });
+
+        // The data are captured, and the progress events take the stream's frontier from the
+        // minimal time to the empty frontier.
+        let events = captured.lock().unwrap();
+        let data = events.iter().filter_map(|event| match event {
+            Event::Messages(_, data) => Some(data.iter().copied()),
+            Event::Progress(_) => None,
+        }).flatten().collect::<Vec<_>>();
+        assert_eq!(data, (0..10).collect::<Vec<_>>());
+        assert!(events.iter().any(|event| matches!(event, Event::Progress(_))));
+        let mut progress: ChangeBatch<u64> = ChangeBatch::new_from(0, 1);
+        for event in events.iter() {
+            if let Event::Progress(updates) = event {
+                progress.extend(updates.iter().cloned());
+            }
+        }
+        assert!(progress.is_empty());
+    }
+}
@@ -42,6 +42,13 @@ impl<T, C> EventPusher<T, C> for ::std::sync::mpsc::Sender<Event<T, C>> {
     }
 }
 
+// implementation for a shared vector of events.
+impl<T, C> EventPusher<T, C> for ::std::sync::Arc<::std::sync::Mutex<Vec<Event<T, C>>>> {
+    fn push(&mut self, event: Event<T, C>) {
+        self.lock().expect("event vector mutex poisoned").push(event);
+    }
+}
+
 /// A linked-list event pusher and iterator.
 pub mod link {
 
@@ -179,3 +186,145 @@ pub mod binary {
         }
     }
 }
+
+/// A length-prefixed binary event pusher and iterator.
+///
+/// Each event is written as a frame: its length in bytes as a little-endian `u64`, followed by
+/// its bincode serialization. Framing lets the reader recognize a frame cut short, which it
+/// treats as the end of the available events rather than as an error.
+pub mod framed {
+
+    use std::borrow::Cow;
+    use std::io::{Read, Write};
+
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{Event, EventPusher, EventIterator};
+
+    /// A wrapper for `W: Write` implementing `EventPusher<T, C>` with length-prefixed frames.
+    pub struct FramedWriter<T, C, W: Write> {
+        stream: W,
+        buffer: Vec<u8>,
+        phant: ::std::marker::PhantomData<(T, C)>,
+    }
+
+    impl<T, C, W: Write> FramedWriter<T, C, W> {
+        /// Allocates a new `FramedWriter` wrapping a supplied writer.
+        pub fn new(w: W) -> Self {
+            Self {
+                stream: w,
+                buffer: Vec::new(),
+                phant: ::std::marker::PhantomData,
+            }
+        }
+    }
+
+    impl<T: Serialize, C: Serialize, W: Write> EventPusher<T, C> for FramedWriter<T, C, W> {
+        fn push(&mut self, event: Event<T, C>) {
+            // TODO: `push` has no mechanism to report errors, so we `unwrap`.
+            self.buffer.clear();
+            ::bincode::serialize_into(&mut self.buffer, &event).expect("Event bincode failed");
+            self.stream.write_all(&(self.buffer.len() as u64).to_le_bytes()).expect("Event frame write failed");
+            self.stream.write_all(&self.buffer).expect("Event frame write failed");
+        }
+    }
+
+    /// A wrapper for `R: Read` implementing `EventIterator<T, C>` over length-prefixed frames.
+    ///
+    /// Bytes of a frame that is not yet complete are retained, and the frame is produced once
+    /// the rest of it can be read. A complete frame that fails to deserialize stops the reader,
+    /// which produces no further events and reports the failure through [`FramedReader::error`].
+    pub struct FramedReader<T, C, R: Read> {
+        reader: R,
+        buffer: Vec<u8>,
+        decoded: Option<Event<T, C>>,
+        error: Option<::bincode::Error>,
+    }
+
+    impl<T, C, R: Read> FramedReader<T, C, R> {
+        /// Allocates a new `FramedReader` wrapping a supplied reader.
+        pub fn new(r: R) -> Self {
+            Self {
+                reader: r,
+                buffer: Vec::new(),
+                decoded: None,
+                error: None,
+            }
+        }
+
+        /// The error deserializing a frame, if one has stopped the reader.
+        pub fn error(&self) -> Option<&::bincode::Error> {
+            self.error.as_ref()
+        }
+    }
+
+    impl<T: DeserializeOwned + Clone, C: DeserializeOwned + Clone, R: Read> EventIterator<T, C> for FramedReader<T, C, R> {
+        fn next(&mut self) -> Option<Cow<'_, Event<T, C>>> {
+            if self.error.is_some() { return None; }
+            loop {
+                let needed = if self.buffer.len() < 8 { 8 } else {
+                    let length = u64::from_le_bytes(self.buffer[.. 8].try_into().unwrap()) as usize;
+                    8 + length
+                };
+                if self.buffer.len() >= 8 && self.buffer.len() >= needed {
+                    match ::bincode::deserialize(&self.buffer[8 .. needed]) {
+                        Ok(event) => { self.decoded = Some(event); },
+                        Err(error) => { self.error = Some(error); },
+                    }
+                    self.buffer.drain(.. needed);
+                    return self.decoded.take().map(Cow::Owned);
+                }
+                // Read the remainder of the frame, or stop if nothing more is available.
+                let missing = (needed - self.buffer.len()) as u64;
+                match (&mut self.reader).take(missing).read_to_end(&mut self.buffer) {
+                    Ok(read) if read > 0 => { },
+                    _ => return None,
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+
+        use std::borrow::Cow;
+
+        use super::{Event, EventPusher, EventIterator, FramedReader, FramedWriter};
+
+        #[test]
+        fn partial_frame() {
+            let mut writer = FramedWriter::new(Vec::new());
+            writer.push(Event::<u64, Vec<u64>>::Messages(0, vec![1, 2, 3]));
+            writer.push(Event::<u64, Vec<u64>>::Progress(vec![(0, -1), (1, 1)]));
+            let bytes = writer.stream;
+
+            let mut reader = FramedReader::<u64, Vec<u64>, _>::new(&bytes[.. bytes.len() - 1]);
+            assert_eq!(reader.next().map(Cow::into_owned), Some(Event::Messages(0, vec![1, 2, 3])));
+            assert!(reader.next().is_none());
+            assert!(reader.next().is_none());
+            assert!(reader.error().is_none());
+
+            let mut reader = FramedReader::<u64, Vec<u64>, _>::new(&bytes[..]);
+            assert_eq!(reader.next().map(Cow::into_owned), Some(Event::Messages(0, vec![1, 2, 3])));
+            assert_eq!(reader.next().map(Cow::into_owned), Some(Event::Progress(vec![(0, -1), (1, 1)])));
+            assert!(reader.next().is_none());
+            assert!(reader.error().is_none());
+        }
+
+        #[test]
+        fn corrupt_frame() {
+            let mut writer = FramedWriter::new(Vec::new());
+            writer.push(Event::<u64, Vec<u64>>::Messages(0, vec![1, 2, 3]));
+            writer.push(Event::<u64, Vec<u64>>::Messages(1, vec![4]));
+            let mut bytes = writer.stream;
+            // An invalid variant index for the first event.
+            bytes[8 .. 12].copy_from_slice(&u32::MAX.to_le_bytes());
+
+            let mut reader = FramedReader::<u64, Vec<u64>, _>::new(&bytes[..]);
+            assert!(reader.next().is_none());
+            assert!(reader.error().is_some());
+            // The reader stops, rather than continue past the corrupt frame.
+            assert!(reader.next().is_none());
+        }
+    }
+}
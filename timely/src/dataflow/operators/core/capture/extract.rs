@@ -7,8 +7,9 @@ use crate::{container::{SizableContainer, PushInto}};
 pub trait Extract<T, C> {
     /// Converts `self` into a sequence of timestamped data.
     ///
-    /// Currently this is only implemented for `Receiver<Event<T, C>>`, and is used only
-    /// to easily pull data out of a timely dataflow computation once it has completed.
+    /// Currently this is only implemented for `Receiver<Event<T, C>>` and for shared vectors of
+    /// events, and is used only to easily pull data out of a timely dataflow computation once it
+    /// has completed.
     ///
     /// # Examples
     ///
@@ -54,29 +55,61 @@ where
     for<'a> C::Item<'a>: Ord,
 {
     fn extract(self) -> Vec<(T, C)> {
-        let mut staged = std::collections::BTreeMap::new();
-        for event in self {
-            if let Event::Messages(time, data) = event {
-                staged.entry(time)
-                      .or_insert_with(Vec::new)
-                      .push(data);
-            }
+        extract_events(self)
+    }
+}
+
+impl<T: Ord, C: SizableContainer> Extract<T, C> for ::std::sync::Arc<::std::sync::Mutex<Vec<Event<T, C>>>>
+where
+    for<'a> C: PushInto<C::Item<'a>>,
+    for<'a> C::Item<'a>: Ord,
+{
+    fn extract(self) -> Vec<(T, C)> {
+        let events = std::mem::take(&mut *self.lock().expect("event vector mutex poisoned"));
+        extract_events(events)
+    }
+}
+
+/// Gathers the data of `events` by time, sorting the data of each time.
+fn extract_events<T: Ord, C, I: IntoIterator<Item=Event<T, C>>>(events: I) -> Vec<(T, C)>
+where
+    C: SizableContainer,
+    for<'a> C: PushInto<C::Item<'a>>,
+    for<'a> C::Item<'a>: Ord,
+{
+    let mut staged = std::collections::BTreeMap::new();
+    for event in events {
+        if let Event::Messages(time, data) = event {
+            staged.entry(time)
+                  .or_insert_with(Vec::new)
+                  .push(data);
         }
-        let mut result = Vec::new();
-        for (time, mut dataz) in staged.into_iter() {
-            let mut to_sort = Vec::new();
-            for data in dataz.iter_mut() {
-                to_sort.extend(data.drain());
-            }
-            to_sort.sort();
-            let mut sorted = C::default();
-            for datum in to_sort.into_iter() {
-                sorted.push(datum);
-            }
-            if !sorted.is_empty() {
-                result.push((time, sorted));
-            }
+    }
+    let mut result = Vec::new();
+    for (time, mut dataz) in staged.into_iter() {
+        let mut to_sort = Vec::new();
+        for data in dataz.iter_mut() {
+            to_sort.extend(data.drain());
+        }
+        to_sort.sort();
+        let mut sorted = C::default();
+        for datum in to_sort.into_iter() {
+            sorted.push(datum);
+        }
+        if !sorted.is_empty() {
+            result.push((time, sorted));
         }
-        result
     }
+    result
+}
+
+/// Collects the batches of data of `events` as they were captured, without gathering them by time.
+///
+/// Unlike [`Extract::extract`], this preserves how the data were batched, for tests of operators that control it.
+#[cfg(test)]
+pub(crate) fn extract_batches<T, C, I: IntoIterator<Item=Event<T, C>>>(events: I) -> Vec<(T, C)> {
+    events.into_iter().filter_map(|event| match event {
+        Event::Messages(time, data) => Some((time, data)),
+        Event::Progress(_) => None,
+    }).collect()
 }
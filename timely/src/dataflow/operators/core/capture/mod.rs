@@ -72,6 +72,10 @@
 //!     })
 //! }).unwrap();
 //! ```
+//!
+//! The types `FramedWriter<T, D, W>` and `FramedReader<T, D, R>` can be used in the same way.
+//! They prefix each serialized event with its length, so that a reader can recognize an event
+//! that has only partly been written, and wait for the rest of it rather than fail.
 
 pub use self::capture::Capture;
 pub use self::replay::Replay;
@@ -80,6 +84,7 @@ pub use self::event::{Event, EventPusher};
 pub use self::event::link::EventLink;
 pub use self::event::binary::EventReader;
 pub use self::event::binary::EventWriter;
+pub use self::event::framed::{FramedReader, FramedWriter};
 
 pub mod capture;
 pub mod replay;
@@ -49,6 +49,11 @@ use super::event::EventIterator;
 use crate::Container;
 
 /// Replay a capture stream into a scope with the same timestamp.
+///
+/// The implementation for iterators of `EventIterator`s replays all of the sources, for
+/// example the streams captured at each of several workers, into one stream. The operator
+/// holds capabilities on behalf of every source, so the frontier of the replayed stream is
+/// the lower bound of the sources' frontiers, and it completes only once all sources have.
 pub trait Replay<T: Timestamp, C> : Sized {
     /// Replays `self` into the provided scope, as a `StreamCore<S, C>`.
     fn replay_into<S: Scope<Timestamp=T>>(self, scope: &mut S) -> StreamCore<S, C> {
@@ -128,3 +133,50 @@ where
         stream
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::rc::Rc;
+
+    use crate::dataflow::operators::{Probe, Capture};
+    use crate::dataflow::operators::capture::{Event, EventLink, EventPusher, Extract};
+    use super::Replay;
+
+    #[test]
+    fn replay_multiple_sources() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let mut source0 = Rc::new(EventLink::<u64, Vec<u64>>::new());
+            let mut source1 = Rc::new(EventLink::<u64, Vec<u64>>::new());
+            let sources = vec![source0.clone(), source1.clone()];
+            let (probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream = sources.replay_into(scope);
+                (stream.probe(), stream.capture())
+            });
+
+            // The first source completes, which must not complete the replayed stream.
+            source0.push(Event::Messages(0, vec![0]));
+            source0.push(Event::Progress(vec![(0, -1)]));
+            worker.step();
+            worker.step();
+            assert!(probe.less_equal(&0));
+
+            // The second source advances, which advances the replayed stream.
+            source1.push(Event::Messages(0, vec![1]));
+            source1.push(Event::Progress(vec![(0, -1), (2, 1)]));
+            worker.step();
+            worker.step();
+            assert!(!probe.less_equal(&1));
+            assert!(probe.less_equal(&2));
+
+            source1.push(Event::Messages(2, vec![2]));
+            source1.push(Event::Progress(vec![(2, -1)]));
+            worker.step();
+            worker.step();
+            assert!(probe.done());
+            captured
+        });
+
+        assert_eq!(captured.extract(), vec![(0, vec![0, 1]), (2, vec![2])]);
+    }
+}
@@ -1,5 +1,6 @@
 //! Merges the contents of multiple streams.
 
+use std::collections::BTreeMap;
 
 use crate::{Container, Data};
 use crate::dataflow::channels::pact::Pipeline;
@@ -33,6 +34,14 @@ impl<G: Scope, C: Container + Data> Concat<G, C> for StreamCore<G, C> {
 pub trait Concatenate<G: Scope, C: Container> {
     /// Merge the contents of multiple streams.
     ///
+    /// All sources feed a single operator with one input per source, which forwards batches
+    /// as they arrive. With no sources, the result is an empty stream.
+    ///
+    /// Each received container is moved to the output rather than copied into a new container,
+    /// so batches pass through unchanged, whatever the container type. This is also the case
+    /// when several sources produce data at the same time, whose containers are forwarded one
+    /// after the other rather than combined.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Concatenate, Inspect};
@@ -94,3 +103,177 @@ impl<G: Scope, C: Container + Data> Concatenate<G, C> for G {
         result
     }
 }
+
+/// Merge the contents of multiple streams in timestamp order.
+pub trait MergeByTime<G: Scope, C: Container> {
+    /// Merge the contents of multiple streams, emitting batches in increasing timestamp order.
+    ///
+    /// Received batches are buffered, and a batch at time `t` is emitted once no input frontier
+    /// contains a time strictly less than `t`. As a consequence, no emitted batch has a timestamp
+    /// strictly less than that of a previously emitted batch, and for totally ordered timestamps
+    /// the output times are non-decreasing. Batches are emitted in the order of `Ord` on their
+    /// timestamps, and batches with the same timestamp are emitted together. Inputs are read in
+    /// rotating order, so that no input is consistently favored among batches with equal times.
+    ///
+    /// Unlike `concatenate`, the operator may hold back data until the inputs' frontiers advance.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::core::concat::MergeByTime;
+    ///
+    /// timely::example(|scope| {
+    ///
+    ///     let streams = vec![(0..10).to_stream(scope).delay(|x, _| *x),
+    ///                        (0..10).to_stream(scope).delay(|x, _| 9 - *x),
+    ///                        (0..10).to_stream(scope).delay(|x, _| *x / 2)];
+    ///
+    ///     scope.merge_by_time(streams)
+    ///          .inspect_time(|t, x| println!("seen: {:?} at {:?}", x, t));
+    /// });
+    /// ```
+    fn merge_by_time<I>(&self, sources: I) -> StreamCore<G, C>
+    where
+        I: IntoIterator<Item=StreamCore<G, C>>;
+}
+
+impl<G: Scope, C: Container + Data> MergeByTime<G, C> for G {
+    fn merge_by_time<I>(&self, sources: I) -> StreamCore<G, C>
+    where
+        I: IntoIterator<Item=StreamCore<G, C>>
+    {
+        use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+        let mut builder = OperatorBuilder::new("MergeByTime".to_string(), self.clone());
+
+        let mut handles = sources.into_iter().map(|s| builder.new_input(&s, Pipeline)).collect::<Vec<_>>();
+        let (mut output, result) = builder.new_output();
+
+        builder.build(move |_capability| {
+
+            // Buffered batches, and a capability for each buffered time.
+            let mut stash = BTreeMap::new();
+            // The input to read first, which rotates with each invocation.
+            let mut rotation = 0;
+
+            move |frontiers| {
+                let count = handles.len();
+                for offset in 0 .. count {
+                    handles[(rotation + offset) % count].for_each(|time, data| {
+                        stash.entry(time.time().clone())
+                             .or_insert_with(|| (time.retain(), Vec::new()))
+                             .1
+                             .push(std::mem::take(data));
+                    });
+                }
+                if count > 0 { rotation = (rotation + 1) % count; }
+
+                let mut output = output.activate();
+                while let Some(entry) = stash.first_entry() {
+                    if frontiers.iter().any(|frontier| frontier.less_than(entry.key())) {
+                        break;
+                    }
+                    let (capability, batches) = entry.remove();
+                    let mut session = output.session(&capability);
+                    for mut batch in batches {
+                        session.give_container(&mut batch);
+                    }
+                }
+            }
+        });
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::Stream;
+    use crate::dataflow::operators::{Input, Probe, Inspect, ToStream, Capture};
+    use crate::dataflow::operators::capture::{Extract, extract::extract_batches};
+    use super::{Concatenate, MergeByTime};
+
+    #[test]
+    fn concatenate_many() {
+        let captured = crate::execute::execute_directly(|worker| {
+            worker.dataflow::<u64,_,_>(|scope| {
+                let streams = (0 .. 100u64).map(|i| (i * 10 .. (i + 1) * 10).to_stream(scope)).collect::<Vec<_>>();
+                scope.concatenate(streams).capture()
+            })
+        });
+        let mut data = captured.extract().into_iter().flat_map(|(_, data)| data).collect::<Vec<_>>();
+        data.sort();
+        assert_eq!(data, (0 .. 1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn concatenate_forwards_batches() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let (mut input0, mut input1, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let (input0, stream0) = scope.new_input::<u64>();
+                let (input1, stream1) = scope.new_input::<u64>();
+                (input0, input1, scope.concatenate(vec![stream0, stream1]).capture())
+            });
+            // One source at a time, and then both sources at the same time.
+            input0.send_batch(&mut vec![0, 1, 2]);
+            input0.send_batch(&mut vec![3, 4]);
+            input0.advance_to(1);
+            input1.advance_to(1);
+            input1.send_batch(&mut vec![5]);
+            input0.advance_to(2);
+            input1.advance_to(2);
+            input0.send_batch(&mut vec![6, 7]);
+            input1.send_batch(&mut vec![8, 9, 10]);
+            captured
+        });
+        let mut batches = extract_batches(captured.try_iter());
+        batches.sort();
+        assert_eq!(batches, vec![(0, vec![0, 1, 2]), (0, vec![3, 4]), (1, vec![5]), (2, vec![6, 7]), (2, vec![8, 9, 10])]);
+    }
+
+    #[test]
+    fn concatenate_empty() {
+        crate::execute::execute_directly(|worker| {
+            let probe = worker.dataflow::<u64,_,_>(|scope| {
+                scope.concatenate(Vec::<Stream<_, u64>>::new()).probe()
+            });
+            worker.step_while(|| !probe.done());
+        });
+    }
+
+    #[test]
+    fn merge_by_time() {
+        crate::execute(crate::Config::thread(), |worker| {
+            let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+            let seen2 = seen.clone();
+            let (mut inputs, probe) = worker.dataflow::<u64,_,_>(|scope| {
+                let (input0, stream0) = scope.new_input::<u64>();
+                let (input1, stream1) = scope.new_input::<u64>();
+                let (input2, stream2) = scope.new_input::<u64>();
+                let probe = scope.merge_by_time(vec![stream0, stream1, stream2])
+                                 .inspect_time(move |t, x| seen2.borrow_mut().push((*t, *x)))
+                                 .probe();
+                (vec![input0, input1, input2], probe)
+            });
+
+            // Each input races ahead by a different amount, so data arrive out of time order.
+            for round in 0 .. 10u64 {
+                for (index, input) in inputs.iter_mut().enumerate() {
+                    let lead = 3 * index as u64;
+                    input.advance_to(round + lead);
+                    input.send(round + lead);
+                }
+                worker.step();
+            }
+            inputs.clear();
+            while !probe.done() {
+                worker.step();
+            }
+
+            let seen = seen.borrow();
+            assert_eq!(seen.len(), 30);
+            assert!(seen.windows(2).all(|w| w[0].0 <= w[1].0));
+            assert!(seen.iter().all(|(t, x)| t == x));
+        }).unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+//! Operators acting on timestamps to logically delay containers of records.
+
+use std::collections::HashMap;
+
+use crate::{Container, Data};
+use crate::order::PartialOrder;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{StreamCore, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Methods to advance the timestamps of containers of records.
+pub trait Delay<G: Scope, C: Container> {
+    /// Advances the timestamp of containers of records using a supplied function.
+    ///
+    /// The function is called once for each received container, with its time, and the
+    /// container is moved to the returned time without inspecting or rebuilding it. Containers
+    /// delayed to the same time are sent at that time, in the order they were received.
+    ///
+    /// The operator will test that the new timestamp is greater or equal to the
+    /// old timestamp, and will assert if it is not.
+    ///
+    /// # Examples
+    ///
+    /// The following example takes the sequence `0..10` at time `0`
+    /// and delays each batch (there is just one) to time `1`.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Operator};
+    /// use timely::dataflow::operators::core::Delay;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay_batch(|time| time + 1)
+    ///            .sink(Pipeline, "example", |input| {
+    ///                input.for_each(|time, data| {
+    ///                    assert_eq!(time.time(), &1);
+    ///                });
+    ///            });
+    /// });
+    /// ```
+    fn delay_batch<L: FnMut(&G::Timestamp)->G::Timestamp+'static>(&self, func: L) -> Self;
+}
+
+impl<G: Scope, C: Container + Data> Delay<G, C> for StreamCore<G, C> {
+    fn delay_batch<L: FnMut(&G::Timestamp)->G::Timestamp+'static>(&self, mut func: L) -> Self {
+        let mut elements = HashMap::new();
+        self.unary_notify(Pipeline, "Delay", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                let new_time = func(&time);
+                assert!(time.time().less_equal(&new_time));
+                elements.entry(new_time.clone())
+                        .or_insert_with(|| { notificator.notify_at(time.delayed(&new_time)); Vec::new() })
+                        .push(std::mem::take(data));
+            });
+
+            // for each available notification, send corresponding set
+            notificator.for_each(|time,_,_| {
+                if let Some(datas) = elements.remove(&time) {
+                    let mut session = output.session(&time);
+                    for mut data in datas {
+                        session.give_container(&mut data);
+                    }
+                }
+            });
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::capture::Extract;
+    use super::Delay;
+
+    #[test]
+    fn delay_batch_merges_times() {
+        let output = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (probe, output) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream = scope.input_from(&mut input).delay_batch(|time| (time / 2 + 1) * 2);
+                (stream.probe(), stream.capture())
+            });
+            for round in 0 .. 4u64 {
+                input.send_batch(&mut vec![round, round + 10]);
+                input.advance_to(round + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+            output
+        });
+
+        assert_eq!(output.extract(), vec![(2, vec![0, 1, 10, 11]), (4, vec![2, 3, 12, 13])]);
+    }
+}
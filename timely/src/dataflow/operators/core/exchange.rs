@@ -26,6 +26,52 @@ pub trait Exchange<C: Container> {
     fn exchange<F>(&self, route: F) -> Self
     where
         for<'a> F: FnMut(&C::Item<'a>) -> u64 + 'static;
+
+    /// Exchange records between workers in round-robin order, independent of their contents.
+    ///
+    /// Each worker sends its records to the workers in turn, starting from the worker after
+    /// itself, which balances the number of records each worker receives even when their
+    /// contents are skewed. Records with equal contents may be routed to different workers,
+    /// and downstream operators must not rely on any affinity between records and workers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .exchange_balanced()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn exchange_balanced(&self) -> Self;
+
+    /// Exchange records between workers by `route`, spreading each route across several workers.
+    ///
+    /// A salt from a counter, cycling through `0 .. 2^salt_bits`, is added to the result of `route`
+    /// for each record, so that records with the same route are sent in turn to up to `2^salt_bits`
+    /// workers rather than to one. This relieves the workers that would receive frequent routes,
+    /// but records with the same key no longer meet at the same worker: a downstream aggregation
+    /// must first aggregate each worker's partial results, and then re-combine the partial results
+    /// for a key by exchanging them by `route` alone. With `salt_bits` equal to zero, this is
+    /// the same as `exchange(route)`.
+    ///
+    /// # Panics
+    /// Panics if `salt_bits` is 64 or greater.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .exchange_skew_aware(|x| *x % 2, 2)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn exchange_skew_aware<F>(&self, route: F, salt_bits: u32) -> Self
+    where
+        for<'a> F: FnMut(&C::Item<'a>) -> u64 + 'static;
 }
 
 impl<G: Scope, C> Exchange<C> for StreamCore<G, C>
@@ -46,4 +92,72 @@ where
             }
         })
     }
+
+    fn exchange_balanced(&self) -> StreamCore<G, C> {
+        let mut counter = self.scope().index() as u64;
+        self.exchange(move |_| {
+            counter = counter.wrapping_add(1);
+            counter
+        })
+    }
+
+    fn exchange_skew_aware<F>(&self, mut route: F, salt_bits: u32) -> StreamCore<G, C>
+    where
+        for<'a> F: FnMut(&C::Item<'a>) -> u64 + 'static,
+    {
+        assert!(salt_bits < 64, "exchange_skew_aware: salt_bits must be less than 64, found {}", salt_bits);
+        let mask = (1u64 << salt_bits) - 1;
+        let mut counter = self.scope().index() as u64;
+        self.exchange(move |item| {
+            counter = counter.wrapping_add(1);
+            route(item).wrapping_add(counter & mask)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::dataflow::operators::{ToStream, Inspect};
+    use super::Exchange;
+
+    #[test]
+    fn exchange_balanced() {
+        let received = Arc::new(Mutex::new(vec![0; 4]));
+        let received2 = Arc::clone(&received);
+        crate::execute(crate::Config::process(4), move |worker| {
+            let index = worker.index();
+            let received = Arc::clone(&received2);
+            worker.dataflow::<u64,_,_>(|scope| {
+                // Entirely skewed data, which `exchange` would send to one worker.
+                (0 .. 100).map(|_| 0u64)
+                    .to_stream(scope)
+                    .exchange_balanced()
+                    .inspect(move |_| received.lock().unwrap()[index] += 1);
+            });
+        }).unwrap();
+
+        assert_eq!(*received.lock().unwrap(), vec![100; 4]);
+    }
+
+    #[test]
+    fn exchange_skew_aware() {
+        let received = Arc::new(Mutex::new(vec![0; 4]));
+        let received2 = Arc::clone(&received);
+        crate::execute(crate::Config::process(4), move |worker| {
+            let index = worker.index();
+            let received = Arc::clone(&received2);
+            worker.dataflow::<u64,_,_>(|scope| {
+                // A single key, which is spread across two workers.
+                (0 .. 100).map(|_| 0u64)
+                    .to_stream(scope)
+                    .exchange_skew_aware(|x| *x, 1)
+                    .inspect(move |_| received.lock().unwrap()[index] += 1);
+            });
+        }).unwrap();
+
+        assert_eq!(*received.lock().unwrap(), vec![200, 200, 0, 0]);
+    }
 }
@@ -8,6 +8,7 @@ use crate::dataflow::operators::generic::OutputWrapper;
 use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use crate::dataflow::scopes::child::Iterative;
 use crate::dataflow::{StreamCore, Scope};
+use crate::logging::TimelyEvent;
 use crate::order::Product;
 use crate::progress::frontier::Antichain;
 use crate::progress::{Timestamp, PathSummary};
@@ -65,6 +66,34 @@ pub trait LoopVariable<'a, G: Scope, T: Timestamp> {
     /// });
     /// ```
     fn loop_variable<C: Container + Data>(&mut self, summary: T::Summary) -> (Handle<Iterative<'a, G, T>, C>, StreamCore<Iterative<'a, G, T>, C>);
+
+    /// Creates a `StreamCore` and a `Handle` to later bind the source of that `StreamCore`, which
+    /// drops records whose iteration would exceed `limit`.
+    ///
+    /// As for `loop_variable`, containers passed through the stream have their timestamps advanced
+    /// by `summary`. Records whose advanced loop coordinate is not less or equal to the result of
+    /// `limit` applied to the minimal loop coordinate are dropped instead, so that a loop terminates
+    /// even if its logic does not converge. Each drop is reported as a `TimelyEvent::Text` event
+    /// to the timely logger, if one is registered.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::Scope;
+    /// use timely::dataflow::operators::{LoopVariable, ConnectLoop, ToStream, Concat, Inspect, Map};
+    ///
+    /// timely::example(|scope| {
+    ///     // circulate 0..10 for 100 iterations, although the loop logic does not terminate.
+    ///     scope.iterative::<usize,_,_>(|inner| {
+    ///         let (handle, cycle) = inner.loop_variable_with_limit(1, 100);
+    ///         (0..10).to_stream(inner)
+    ///                .concat(&cycle)
+    ///                .inspect_time(|t, _x| assert!(t.inner <= 100))
+    ///                .map(|x| x + 1)
+    ///                .connect_loop(handle);
+    ///     });
+    /// });
+    /// ```
+    fn loop_variable_with_limit<C: Container + Data>(&mut self, summary: T::Summary, limit: T::Summary) -> LoopHandleStream<'a, G, T, C>;
 }
 
 impl<G: Scope> Feedback<G> for G {
@@ -74,7 +103,7 @@ impl<G: Scope> Feedback<G> for G {
         let mut builder = OperatorBuilder::new("Feedback".to_owned(), self.clone());
         let (output, stream) = builder.new_output();
 
-        (Handle { builder, summary, output }, stream)
+        (Handle { builder, summary, output, limit: None }, stream)
     }
 }
 
@@ -82,8 +111,20 @@ impl<'a, G: Scope, T: Timestamp> LoopVariable<'a, G, T> for Iterative<'a, G, T>
     fn loop_variable<C: Container + Data>(&mut self, summary: T::Summary) -> (Handle<Iterative<'a, G, T>, C>, StreamCore<Iterative<'a, G, T>, C>) {
         self.feedback(Product::new(Default::default(), summary))
     }
+
+    fn loop_variable_with_limit<C: Container + Data>(&mut self, summary: T::Summary, limit: T::Summary) -> LoopHandleStream<'a, G, T, C> {
+        let (mut handle, stream) = self.loop_variable(summary);
+        // A summary that does not produce a time imposes no limit.
+        if let Some(limit) = limit.results_in(&T::minimum()) {
+            handle.limit = Some(Box::new(move |time: &Product<G::Timestamp, T>| time.inner.less_equal(&limit)));
+        }
+        (handle, stream)
+    }
 }
 
+/// A handle to a loop variable in an iterative scope, and the stream of its contents.
+type LoopHandleStream<'a, G, T, C> = (Handle<Iterative<'a, G, T>, C>, StreamCore<Iterative<'a, G, T>, C>);
+
 /// Connect a `Stream` to the input of a loop variable.
 pub trait ConnectLoop<G: Scope, C: Container + Data> {
     /// Connect a `Stream` to be the input of a loop variable.
@@ -112,6 +153,9 @@ impl<G: Scope, C: Container + Data> ConnectLoop<G, C> for StreamCore<G, C> {
         let mut builder = handle.builder;
         let summary = handle.summary;
         let mut output = handle.output;
+        let limit = handle.limit;
+        let index = builder.global();
+        let logging = self.scope().logging();
 
         let mut input = builder.new_input_connection(self, Pipeline, vec![Antichain::from_elem(summary.clone())]);
 
@@ -119,10 +163,15 @@ impl<G: Scope, C: Container + Data> ConnectLoop<G, C> for StreamCore<G, C> {
             let mut output = output.activate();
             input.for_each(|cap, data| {
                 if let Some(new_time) = summary.results_in(cap.time()) {
-                    let new_cap = cap.delayed(&new_time);
-                    output
-                        .session(&new_cap)
-                        .give_container(data);
+                    if limit.as_ref().map_or(true, |limit| limit(&new_time)) {
+                        let new_cap = cap.delayed(&new_time);
+                        output
+                            .session(&new_cap)
+                            .give_container(data);
+                    }
+                    else if let Some(logger) = logging.as_ref() {
+                        logger.log(TimelyEvent::Text(format!("Feedback operator {}: dropped {} records beyond the iteration limit at {:?}", index, data.len(), new_time)));
+                    }
                 }
             });
         });
@@ -130,9 +179,65 @@ impl<G: Scope, C: Container + Data> ConnectLoop<G, C> for StreamCore<G, C> {
 }
 
 /// A handle used to bind the source of a loop variable.
-#[derive(Debug)]
 pub struct Handle<G: Scope, C: Container + Data> {
     builder: OperatorBuilder<G>,
     summary: <G::Timestamp as Timestamp>::Summary,
     output: OutputWrapper<G::Timestamp, CapacityContainerBuilder<C>, Tee<G::Timestamp, C>>,
+    /// Indicates whether records may be fed back at a time, if the loop is limited.
+    limit: Option<LoopLimit<G::Timestamp>>,
+}
+
+/// Indicates whether records may be fed back at a time.
+type LoopLimit<T> = Box<dyn Fn(&T) -> bool>;
+
+impl<G: Scope + std::fmt::Debug, C: Container + Data + std::fmt::Debug> std::fmt::Debug for Handle<G, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handle")
+            .field("builder", &self.builder)
+            .field("summary", &self.summary)
+            .field("output", &self.output)
+            .field("limited", &self.limit.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::logging::{TimelyEvent, capture_timely_events};
+    use crate::dataflow::Scope;
+    use crate::dataflow::operators::{LoopVariable, ConnectLoop, ToStream, Concat, Inspect, Map};
+
+    #[test]
+    fn loop_variable_with_limit() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen2 = Arc::clone(&seen);
+        let events = crate::execute::execute_directly(move |worker| {
+            let events = capture_timely_events(worker);
+            worker.dataflow::<u64,_,_>(|scope| {
+                scope.iterative::<u64,_,_>(|inner| {
+                    // the loop logic never stops circulating records.
+                    let (handle, cycle) = inner.loop_variable_with_limit(1, 3);
+                    (0..2u64).to_stream(inner)
+                             .concat(&cycle)
+                             .inspect_time(move |t, x| seen2.lock().unwrap().push((t.inner, *x)))
+                             .map(|x| x + 10)
+                             .connect_loop(handle);
+                });
+            });
+            events
+        });
+
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, vec![(0, 0), (0, 1), (1, 10), (1, 11), (2, 20), (2, 21), (3, 30), (3, 31)]);
+        let events = events.lock().unwrap();
+        let texts = events.iter().filter_map(|event| match event {
+            TimelyEvent::Text(text) => Some(text),
+            _ => None,
+        }).collect::<Vec<_>>();
+        assert!(texts.iter().any(|text| text.contains("dropped 2 records beyond the iteration limit")), "{:?}", texts);
+    }
 }
@@ -399,13 +399,18 @@ impl<T: Timestamp, CB: ContainerBuilder> Handle<T, CB> {
     /// Closes the current epoch, flushing if needed, shutting if needed, and updating the frontier.
     // TODO: Find a better name for this function.
     fn close_epoch(&mut self) {
+        self.flush_and_activate();
+        for progress in self.progress.iter() {
+            progress.borrow_mut().update(self.now_at.clone(), -1);
+        }
+    }
+
+    /// Flushes buffered records and downstream pushers, and activates the input operators.
+    fn flush_and_activate(&mut self) {
         self.flush();
         for pusher in self.pushers.iter_mut() {
             pusher.done();
         }
-        for progress in self.progress.iter() {
-            progress.borrow_mut().update(self.now_at.clone(), -1);
-        }
         // Alert worker of each active input operator.
         for activate in self.activate.iter() {
             activate.activate();
@@ -415,6 +420,8 @@ impl<T: Timestamp, CB: ContainerBuilder> Handle<T, CB> {
     /// Sends a batch of records into the corresponding timely dataflow [StreamCore], at the current epoch.
     ///
     /// This method flushes single elements previously sent with `send`, to keep the insertion order.
+    /// The container is sent as is, without pushing its records through the container builder,
+    /// and is left empty, so that it can be reused for the next batch.
     ///
     /// # Examples
     /// ```
@@ -465,6 +472,43 @@ impl<T: Timestamp, CB: ContainerBuilder> Handle<T, CB> {
         }
     }
 
+    /// Flushes records buffered at the current epoch, and then advances the current epoch to `next`.
+    ///
+    /// Unlike `advance_to`, this method flushes even if `next` equals the current epoch, and it
+    /// can be called repeatedly with the same time. All records sent before the call are sent at
+    /// the current epoch, and records sent after the call are sent at `next`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::core::{Input, Inspect};
+    /// use timely::dataflow::operators::core::input::Handle;
+    ///
+    /// timely::execute_directly(|worker| {
+    ///     let mut input = Handle::new();
+    ///     worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .inspect_batch(|t, x: &Vec<u64>| assert_eq!(x, &[*t]));
+    ///     });
+    ///
+    ///     for round in 0..10 {
+    ///         input.send(round);
+    ///         input.advance_to_and_flush(round);
+    ///         worker.step();
+    ///         input.advance_to_and_flush(round + 1);
+    ///     }
+    /// });
+    /// ```
+    pub fn advance_to_and_flush(&mut self, next: T) {
+        // Assert that we do not rewind time, before sending anything.
+        assert!(self.now_at.less_equal(&next));
+        if self.now_at.eq(&next) {
+            self.flush_and_activate();
+        }
+        else {
+            self.advance_to(next);
+        }
+    }
+
     /// Closes the input.
     ///
     /// This method allows timely dataflow to issue all progress notifications blocked by this input
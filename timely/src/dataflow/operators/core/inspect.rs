@@ -1,6 +1,7 @@
 //! Extension trait and implementation for observing and action on streamed data.
 
 use crate::{Container, Data};
+use crate::order::PartialOrder;
 use crate::dataflow::channels::pact::Pipeline;
 use crate::dataflow::{Scope, StreamCore};
 use crate::dataflow::operators::generic::Operator;
@@ -27,6 +28,21 @@ pub trait Inspect<G: Scope, C: Container>: InspectCore<G, C> + Sized {
         })
     }
 
+    /// Runs a supplied closure on each observed data element, in an operator named `name`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .inspect_named("Print", |x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn inspect_named<F>(&self, name: &str, func: F) -> Self
+    where
+        F: for<'a> FnMut(C::ItemRef<'a>) + 'static;
+
     /// Runs a supplied closure on each observed data element and associated time.
     ///
     /// # Examples
@@ -49,7 +65,9 @@ pub trait Inspect<G: Scope, C: Container>: InspectCore<G, C> + Sized {
         })
     }
 
-    /// Runs a supplied closure on each observed data batch (time and data slice).
+    /// Runs a supplied closure on each observed data batch (time and container).
+    ///
+    /// The closure is called once per container, which is passed through without being cloned.
     ///
     /// # Examples
     /// ```
@@ -68,6 +86,41 @@ pub trait Inspect<G: Scope, C: Container>: InspectCore<G, C> + Sized {
         })
     }
 
+    /// Runs a supplied closure on the number of records in each observed batch.
+    ///
+    /// The closure is called once per container with its time, the number of records it holds
+    /// as reported by `Container::len`, and the number of records observed at that time so far,
+    /// including this container. Comparing the counts of streams before and after an operator
+    /// reveals how many records it dropped. Counts are forgotten once the input frontier passes
+    /// their time.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Filter, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .inspect_counts(|t, _, total| println!("passed at {:?}: {:?}", t, total))
+    ///            .filter(|x| *x < 5)
+    ///            .inspect_counts(|t, _, total| println!("kept at {:?}: {:?}", t, total));
+    /// });
+    /// ```
+    fn inspect_counts(&self, mut func: impl FnMut(&G::Timestamp, usize, usize)+'static) -> Self {
+        let mut totals = std::collections::BTreeMap::<G::Timestamp, usize>::new();
+        self.inspect_core(move |event| {
+            match event {
+                Ok((time, data)) => {
+                    let total = totals.entry(time.clone()).or_insert(0);
+                    *total += data.len();
+                    func(time, data.len(), *total);
+                },
+                Err(frontier) => {
+                    totals.retain(|time, _| frontier.iter().any(|t| t.less_equal(time)));
+                },
+            }
+        })
+    }
+
     /// Runs a supplied closure on each observed data batch, and each frontier advancement.
     ///
     /// Rust's `Result` type is used to distinguish the events, with `Ok` for time and data,
@@ -94,6 +147,17 @@ impl<G: Scope, C: Container + Data> Inspect<G, C> for StreamCore<G, C> {
     fn inspect_core<F>(&self, func: F) -> Self where F: FnMut(Result<(&G::Timestamp, &C), &[G::Timestamp]>) + 'static {
         self.inspect_container(func)
     }
+
+    fn inspect_named<F>(&self, name: &str, mut func: F) -> Self
+    where
+        F: for<'a> FnMut(C::ItemRef<'a>) + 'static,
+    {
+        self.inspect_container_named(name, move |event| {
+            if let Ok((_, data)) = event {
+                for datum in data.iter() { func(datum); }
+            }
+        })
+    }
 }
 
 /// Inspect containers
@@ -103,6 +167,11 @@ pub trait InspectCore<G: Scope, C: Container> {
     /// Rust's `Result` type is used to distinguish the events, with `Ok` for time and data,
     /// and `Err` for frontiers. Frontiers are only presented when they change.
     ///
+    /// Each container is presented once, by reference, and then passed along unchanged; it is
+    /// neither cloned nor drained, and its elements are not visited unless `func` visits them.
+    /// This makes the method suitable for inexpensive container-level metrics, such as counts
+    /// of records or batches.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Map, InspectCore};
@@ -117,17 +186,37 @@ pub trait InspectCore<G: Scope, C: Container> {
     ///             });
     /// });
     /// ```
-    fn inspect_container<F>(&self, func: F) -> StreamCore<G, C> where F: FnMut(Result<(&G::Timestamp, &C), &[G::Timestamp]>)+'static;
+    fn inspect_container<F>(&self, func: F) -> StreamCore<G, C> where F: FnMut(Result<(&G::Timestamp, &C), &[G::Timestamp]>)+'static {
+        self.inspect_container_named("InspectBatch", func)
+    }
+
+    /// Runs a supplied closure on each observed container, and each frontier advancement, in an
+    /// operator named `name`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, InspectCore};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .inspect_container_named("Monitor", |event| {
+    ///                if let Ok((time, data)) = event {
+    ///                    println!("seen at: {:?}\t{:?} records", time, data.len());
+    ///                }
+    ///             });
+    /// });
+    /// ```
+    fn inspect_container_named<F>(&self, name: &str, func: F) -> StreamCore<G, C> where F: FnMut(Result<(&G::Timestamp, &C), &[G::Timestamp]>)+'static;
 }
 
 impl<G: Scope, C: Container + Data> InspectCore<G, C> for StreamCore<G, C> {
 
-    fn inspect_container<F>(&self, mut func: F) -> StreamCore<G, C>
+    fn inspect_container_named<F>(&self, name: &str, mut func: F) -> StreamCore<G, C>
         where F: FnMut(Result<(&G::Timestamp, &C), &[G::Timestamp]>)+'static
     {
         use crate::progress::timestamp::Timestamp;
         let mut frontier = crate::progress::Antichain::from_elem(G::Timestamp::minimum());
-        self.unary_frontier(Pipeline, "InspectBatch", move |_,_| move |input, output| {
+        self.unary_frontier(Pipeline, name, move |_,_| move |input, output| {
             if input.frontier.frontier() != frontier.borrow() {
                 frontier.clear();
                 frontier.extend(input.frontier.frontier().iter().cloned());
@@ -140,3 +229,43 @@ impl<G: Scope, C: Container + Data> InspectCore<G, C> for StreamCore<G, C> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Filter, Inspect, Probe};
+
+    #[test]
+    fn inspect_counts() {
+        let counts = crate::execute::execute_directly(|worker| {
+            let counts = Rc::new(RefCell::new(Vec::new()));
+            let (passed, kept) = (Rc::clone(&counts), Rc::clone(&counts));
+            let mut input = InputHandle::new();
+            let probe = worker.dataflow::<u64,_,_>(|scope| {
+                scope.input_from(&mut input)
+                    .inspect_counts(move |t, count, total| passed.borrow_mut().push(("passed", *t, count, total)))
+                    .filter(|x: &u64| x & 1 == 0)
+                    .inspect_counts(move |t, count, total| kept.borrow_mut().push(("kept", *t, count, total)))
+                    .probe()
+            });
+            // Two batches at the first time, and one at the second.
+            input.send_batch(&mut vec![0, 1, 2]);
+            input.send_batch(&mut vec![3, 4]);
+            input.advance_to(1);
+            input.send_batch(&mut vec![5, 6, 7]);
+            input.advance_to(2);
+            worker.step_while(|| probe.less_than(input.time()));
+            let counts = counts.borrow().clone();
+            counts
+        });
+
+        let passed: Vec<_> = counts.iter().filter(|c| c.0 == "passed").map(|c| (c.1, c.2, c.3)).collect();
+        let kept: Vec<_> = counts.iter().filter(|c| c.0 == "kept").map(|c| (c.1, c.2, c.3)).collect();
+        assert_eq!(passed, vec![(0, 3, 3), (0, 2, 5), (1, 3, 3)]);
+        assert_eq!(kept, vec![(0, 2, 2), (0, 1, 3), (1, 1, 1)]);
+    }
+}
@@ -1,6 +1,6 @@
 //! Extension methods for `StreamCore` based on record-by-record transformation.
 
-use crate::container::{Container, SizableContainer, PushInto};
+use crate::container::{CapacityContainerBuilder, Container, ContainerBuilder, IterMutContainer, SizableContainer, PushInto};
 use crate::Data;
 use crate::dataflow::{Scope, StreamCore};
 use crate::dataflow::channels::pact::Pipeline;
@@ -22,13 +22,57 @@ pub trait Map<S: Scope, C: Container> {
     ///            .inspect(|x| println!("seen: {:?}", x));
     /// });
     /// ```
-    fn map<C2, D2, L>(&self, mut logic: L) -> StreamCore<S, C2>
+    fn map<C2, D2, L>(&self, logic: L) -> StreamCore<S, C2>
     where
         C2: SizableContainer + PushInto<D2> + Data,
         L: FnMut(C::Item<'_>)->D2 + 'static,
     {
-        self.flat_map(move |x| std::iter::once(logic(x)))
+        self.map_named("FlatMap", logic)
     }
+    /// Consumes each element of the stream and yields a new element, in an operator named `name`.
+    ///
+    /// The name identifies the operator in logged events, which helps to tell apart the
+    /// operators of a dataflow when profiling it.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::core::{Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .map_named("AddOne", |x| x + 1)
+    ///            .container::<Vec<_>>()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn map_named<C2, D2, L>(&self, name: &str, logic: L) -> StreamCore<S, C2>
+    where
+        C2: SizableContainer + PushInto<D2> + Data,
+        L: FnMut(C::Item<'_>)->D2 + 'static,
+    ;
+    /// Updates each element of the stream, in place.
+    ///
+    /// Containers are passed downstream after their elements are updated, without allocating
+    /// new containers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::core::{Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .container::<Vec<_>>()
+    ///            .map_in_place(|x| *x += 1)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn map_in_place<L>(&self, logic: L) -> StreamCore<S, C>
+    where
+        C: IterMutContainer,
+        L: FnMut(C::ItemMut<'_>) + 'static,
+    ;
     /// Consumes each element of the stream and yields some number of new elements.
     ///
     /// # Examples
@@ -48,23 +92,168 @@ pub trait Map<S: Scope, C: Container> {
         I: IntoIterator,
         C2: SizableContainer + PushInto<I::Item> + Data,
         L: FnMut(C::Item<'_>)->I + 'static,
+    {
+        self.flat_map_builder::<CapacityContainerBuilder<C2>, _, _>(logic)
+    }
+    /// Consumes each element of the stream and yields some number of new elements, which are
+    /// assembled into containers by the container builder `CB`.
+    ///
+    /// Elements may yield no new elements, in which case nothing is produced for them. New
+    /// elements are produced at the time of the element that yielded them.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::DedupContainerBuilder;
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::core::{Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .flat_map_builder::<DedupContainerBuilder<_>, _, _>(|x| (0..x).map(|y| y % 3))
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn flat_map_builder<CB, I, L>(&self, logic: L) -> StreamCore<S, CB::Container>
+    where
+        I: IntoIterator,
+        CB: ContainerBuilder + PushInto<I::Item>,
+        L: FnMut(C::Item<'_>)->I + 'static,
+    ;
+    /// Converts the stream to one of containers of type `C2`.
+    ///
+    /// Each received container is drained, and its elements pushed into containers of type `C2`
+    /// at the same time. The number of elements is preserved, and `C2` must accept exactly the
+    /// elements that `C` drains.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::columnation::TimelyStack;
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::core::{Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .container::<Vec<_>>()
+    ///            .reformat::<TimelyStack<_>>()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn reformat<C2>(&self) -> StreamCore<S, C2>
+    where
+        C2: SizableContainer + for<'a> PushInto<C::Item<'a>> + Data,
     ;
 }
 
 impl<S: Scope, C: Container + Data> Map<S, C> for StreamCore<S, C> {
+    fn map_named<C2, D2, L>(&self, name: &str, mut logic: L) -> StreamCore<S, C2>
+    where
+        C2: SizableContainer + PushInto<D2> + Data,
+        L: FnMut(C::Item<'_>)->D2 + 'static,
+    {
+        self.unary(Pipeline, name, move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                output.session(&time).give_iterator(data.drain().map(&mut logic));
+            });
+        })
+    }
+    fn map_in_place<L>(&self, mut logic: L) -> StreamCore<S, C>
+    where
+        C: IterMutContainer,
+        L: FnMut(C::ItemMut<'_>) + 'static,
+    {
+        self.unary(Pipeline, "MapInPlace", move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                for datum in data.iter_mut() { logic(datum); }
+                output.session(&time).give_container(data);
+            });
+        })
+    }
+    fn reformat<C2>(&self) -> StreamCore<S, C2>
+    where
+        C2: SizableContainer + for<'a> PushInto<C::Item<'a>> + Data,
+    {
+        self.unary(Pipeline, "Reformat", move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                output.session(&time).give_iterator(data.drain());
+            });
+        })
+    }
     // TODO : This would be more robust if it captured an iterator and then pulled an appropriate
     // TODO : number of elements from the iterator. This would allow iterators that produce many
     // TODO : records without taking arbitrarily long and arbitrarily much memory.
-    fn flat_map<C2, I, L>(&self, mut logic: L) -> StreamCore<S, C2>
+    fn flat_map_builder<CB, I, L>(&self, mut logic: L) -> StreamCore<S, CB::Container>
     where
         I: IntoIterator,
-        C2: SizableContainer + PushInto<I::Item> + Data,
+        CB: ContainerBuilder + PushInto<I::Item>,
         L: FnMut(C::Item<'_>)->I + 'static,
     {
-        self.unary(Pipeline, "FlatMap", move |_,_| move |input, output| {
+        self.unary::<CB, _, _, _>(Pipeline, "FlatMap", move |_,_| move |input, output| {
             input.for_each(|time, data| {
-                output.session(&time).give_iterator(data.drain().flat_map(&mut logic));
+                output.session_with_builder(&time).give_iterator(data.drain().flat_map(&mut logic));
             });
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::container::DedupContainerBuilder;
+    use crate::dataflow::operators::{ToStream, Capture, Delay};
+    use crate::dataflow::operators::capture::Extract;
+    use super::Map;
+
+    #[test]
+    fn flat_map_builder() {
+        let captured = crate::example(|scope| {
+            (0..6u64).to_stream(scope)
+                .delay(|x, _| *x / 2)
+                // Odd records yield nothing, and even records yield duplicates.
+                .flat_map_builder::<DedupContainerBuilder<_>, _, _>(|x| if x % 2 == 0 { vec![x, x] } else { vec![] })
+                .capture()
+        });
+        assert_eq!(captured.extract(), vec![(0, vec![0]), (1, vec![2]), (2, vec![4])]);
+    }
+
+    #[test]
+    fn map_named() {
+        use crate::logging::{TimelyEvent, capture_timely_events};
+        let (captured, events) = crate::execute::execute_directly(|worker| {
+            let events = capture_timely_events(worker);
+            let captured = worker.dataflow::<u64,_,_>(|scope| {
+                (0..3u64).to_stream(scope)
+                    .map_named::<Vec<_>, _, _>("AddOne", |x| x + 1)
+                    .capture()
+            });
+            (captured, events)
+        });
+        assert_eq!(captured.extract(), vec![(0, vec![1, 2, 3])]);
+        assert!(events.lock().unwrap().iter().any(|event| matches!(event, TimelyEvent::Operates(operates) if operates.name == "AddOne")));
+    }
+
+    #[test]
+    fn map_in_place() {
+        let captured = crate::example(|scope| {
+            (0..6u64).to_stream(scope)
+                .delay(|x, _| *x / 2)
+                .container::<Vec<_>>()
+                .map_in_place(|x| *x *= 10)
+                .capture()
+        });
+        assert_eq!(captured.extract(), vec![(0, vec![0, 10]), (1, vec![20, 30]), (2, vec![40, 50])]);
+    }
+
+    #[test]
+    fn reformat() {
+        use crate::container::columnation::TimelyStack;
+        let captured = crate::example(|scope| {
+            (0..6u64).to_stream(scope)
+                .delay(|x, _| *x / 2)
+                .container::<Vec<_>>()
+                .reformat::<TimelyStack<_>>()
+                .reformat::<Vec<_>>()
+                .capture()
+        });
+        assert_eq!(captured.extract(), vec![(0, vec![0, 1]), (1, vec![2, 3]), (2, vec![4, 5])]);
+    }
+}
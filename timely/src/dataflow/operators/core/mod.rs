@@ -1,8 +1,11 @@
 //! Extension traits for `StreamCore` implementing various operators that
 //! are independent of specific container types.
 
+pub mod accumulate;
+pub mod barrier;
 pub mod capture;
 pub mod concat;
+pub mod delay;
 pub mod enterleave;
 pub mod exchange;
 pub mod feedback;
@@ -11,15 +14,21 @@ pub mod input;
 pub mod inspect;
 pub mod map;
 pub mod ok_err;
+pub mod ordered;
 pub mod partition;
 pub mod probe;
 pub mod rc;
 pub mod reclock;
+pub mod spy;
+pub mod timeout;
 pub mod to_stream;
 pub mod unordered_input;
 
+pub use accumulate::Accumulate;
+pub use barrier::BufferUntilFrontier;
 pub use capture::Capture;
-pub use concat::{Concat, Concatenate};
+pub use concat::{Concat, Concatenate, MergeByTime};
+pub use delay::Delay;
 pub use enterleave::{Enter, Leave};
 pub use exchange::Exchange;
 pub use feedback::{Feedback, LoopVariable, ConnectLoop};
@@ -28,8 +37,11 @@ pub use input::Input;
 pub use inspect::{Inspect, InspectCore};
 pub use map::Map;
 pub use ok_err::OkErr;
+pub use ordered::AssertOrdered;
 pub use partition::Partition;
 pub use probe::Probe;
 pub use to_stream::{ToStream, ToStreamBuilder};
 pub use reclock::Reclock;
+pub use spy::Spy;
+pub use timeout::Timeout;
 pub use unordered_input::{UnorderedInput, UnorderedHandle};
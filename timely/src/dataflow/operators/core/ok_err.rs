@@ -1,5 +1,8 @@
 //! Operators that separate one stream into two streams based on some condition
 
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+
 use crate::container::{Container, SizableContainer, PushInto};
 use crate::Data;
 use crate::dataflow::channels::pact::Pipeline;
@@ -37,6 +40,47 @@ pub trait OkErr<S: Scope, C: Container> {
         C2: SizableContainer + PushInto<D2> + Data,
         L: FnMut(C::Item<'_>) -> Result<D1,D2>+'static
     ;
+
+    /// Takes one input stream and splits it into two output streams, catching panics.
+    ///
+    /// For each record, the supplied closure is called with the data, as for `ok_err`.
+    /// If it returns `Ok(x)`, then `x` will be sent to the first returned stream; if it
+    /// returns `Err(e)`, then `e` will be sent to the second. If it panics, the panic is
+    /// caught and its message is sent to the second stream, and the operator continues
+    /// with the next record.
+    ///
+    /// The panic hook still runs for caught panics, and panics cannot be caught if the
+    /// program is compiled with `panic = "abort"`. A panic may leave state the closure
+    /// captures by mutable reference inconsistent, and it is the responsibility of the
+    /// closure to be correct in that case.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::core::{OkErr, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     let (ok, err) = (0..10)
+    ///         .to_stream(scope)
+    ///         .map_fallible(|x| {
+    ///             if x == 3 { panic!("three"); }
+    ///             if x == 5 { return Err("five".to_string()); }
+    ///             Ok(x)
+    ///         });
+    ///
+    ///     ok.container::<Vec<_>>().inspect(|x| assert!(*x != 3 && *x != 5));
+    ///     err.container::<Vec<String>>().inspect(|x| assert!(x == "three" || x == "five"));
+    /// });
+    /// ```
+    fn map_fallible<C1, D1, C2, L>(
+        &self,
+        logic: L,
+    ) -> (StreamCore<S, C1>, StreamCore<S, C2>)
+    where
+        C1: SizableContainer + PushInto<D1> + Data,
+        C2: SizableContainer + PushInto<String> + Data,
+        L: FnMut(C::Item<'_>) -> Result<D1,String>+UnwindSafe+'static
+    ;
 }
 
 impl<S: Scope, C: Container + Data> OkErr<S, C> for StreamCore<S, C> {
@@ -75,4 +119,31 @@ impl<S: Scope, C: Container + Data> OkErr<S, C> for StreamCore<S, C> {
 
         (stream1, stream2)
     }
+
+    fn map_fallible<C1, D1, C2, L>(
+        &self,
+        mut logic: L,
+    ) -> (StreamCore<S, C1>, StreamCore<S, C2>)
+    where
+        C1: SizableContainer + PushInto<D1> + Data,
+        C2: SizableContainer + PushInto<String> + Data,
+        L: FnMut(C::Item<'_>) -> Result<D1,String>+UnwindSafe+'static
+    {
+        self.ok_err(move |datum| {
+            // `logic` is `UnwindSafe`, but is called through a mutable reference, which is not.
+            panic::catch_unwind(AssertUnwindSafe(|| logic(datum)))
+                .unwrap_or_else(|payload| Err(panic_message(payload)))
+        })
+    }
+}
+
+/// Extracts the message of a panic from its payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Box<dyn Any>".to_string(),
+        },
+    }
 }
@@ -0,0 +1,93 @@
+//! Extension trait and implementation for checking that containers arrive in time order.
+
+use crate::{Container, Data};
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::{Scope, StreamCore};
+use crate::order::PartialOrder;
+
+/// Checks that containers arrive in time order.
+pub trait AssertOrdered<G: Scope, C: Container> {
+    /// Passes containers through unchanged, and panics if one arrives at a time less than that of
+    /// a container before it.
+    ///
+    /// Timely dataflow does not deliver containers in time order, and operators that assume it
+    /// can use this operator to detect violations of the assumption. The check costs a comparison
+    /// per container. Containers at times incomparable to earlier times are not violations. The
+    /// panic message names the two times and the address of the operator.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::core::AssertOrdered;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _| *x)
+    ///            .assert_ordered()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn assert_ordered(&self) -> StreamCore<G, C>;
+}
+
+impl<G: Scope, C: Container + Data> AssertOrdered<G, C> for StreamCore<G, C> {
+    fn assert_ordered(&self) -> StreamCore<G, C> {
+        self.unary(Pipeline, "AssertOrdered", |_capability, info| {
+            let mut latest: Option<G::Timestamp> = None;
+            move |input, output| {
+                input.for_each(|time, data| {
+                    if let Some(latest) = latest.as_ref() {
+                        if time.time().less_than(latest) {
+                            panic!(
+                                "AssertOrdered at operator {:?}: received a container at time {:?} after one at time {:?}",
+                                info.address, time.time(), latest,
+                            );
+                        }
+                    }
+                    latest = Some(time.time().clone());
+                    output.session(&time).give_container(data);
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, UnorderedInput};
+    use super::AssertOrdered;
+
+    #[test]
+    fn assert_ordered() {
+        crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let probe = worker.dataflow::<u64,_,_>(|scope| {
+                scope.input_from(&mut input).assert_ordered().probe()
+            });
+            for round in 0..3 {
+                input.send(round);
+                input.advance_to(round + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "received a container at time 1 after one at time 2")]
+    fn assert_ordered_violation() {
+        crate::execute::execute_directly(|worker| {
+            let (mut input, cap) = worker.dataflow::<u64,_,_>(|scope| {
+                let (input, stream) = scope.new_unordered_input::<u64>();
+                stream.assert_ordered();
+                input
+            });
+            input.session(cap.delayed(&2)).give(2);
+            worker.step();
+            input.session(cap.delayed(&1)).give(1);
+            worker.step();
+        });
+    }
+}
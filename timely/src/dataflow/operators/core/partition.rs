@@ -11,6 +11,8 @@ use crate::Data;
 pub trait Partition<G: Scope, C: Container> {
     /// Produces `parts` output streams, containing records produced and assigned by `route`.
     ///
+    /// The operator panics if `route` assigns a record to a part that is not less than `parts`.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::ToStream;
@@ -64,6 +66,7 @@ impl<G: Scope, C: Container + Data> Partition<G, C> for StreamCore<G, C> {
 
                     for datum in data.drain() {
                         let (part, datum2) = route(datum);
+                        assert!(part < parts, "partition: route produced part {} for {} parts", part, parts);
                         sessions[part as usize].give(datum2);
                     }
                 });
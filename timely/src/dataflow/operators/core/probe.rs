@@ -3,8 +3,8 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 
-use crate::progress::Timestamp;
-use crate::progress::frontier::{AntichainRef, MutableAntichain};
+use crate::progress::{ChangeBatch, Timestamp};
+use crate::progress::frontier::{Antichain, AntichainRef, MutableAntichain};
 use crate::dataflow::channels::pushers::Counter as PushCounter;
 use crate::dataflow::channels::pushers::buffer::Buffer as PushBuffer;
 use crate::dataflow::channels::pact::Pipeline;
@@ -12,7 +12,9 @@ use crate::dataflow::channels::pullers::Counter as PullCounter;
 use crate::dataflow::operators::generic::builder_raw::OperatorBuilder;
 
 
+use crate::communication::Allocate;
 use crate::dataflow::{StreamCore, Scope};
+use crate::worker::Worker;
 use crate::{Container, Data};
 
 /// Monitors progress at a `Stream`.
@@ -77,6 +79,36 @@ pub trait Probe<G: Scope, C: Container> {
     /// }).unwrap();
     /// ```
     fn probe_with(&self, handle: &Handle<G::Timestamp>) -> StreamCore<G, C>;
+
+    /// Inserts a progress probe in a stream, which calls `notify` when its frontier changes.
+    ///
+    /// The closure is called when the operator is scheduled and finds that the frontier has
+    /// changed since it was last called, including once when it is first scheduled, with the
+    /// new frontier. It is called with the empty frontier exactly once, when the stream completes.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::*;
+    /// use timely::dataflow::operators::{Input, Probe};
+    ///
+    /// timely::execute(Config::thread(), |worker| {
+    ///
+    ///     let mut input = worker.dataflow::<usize,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input::<String>();
+    ///         stream.probe_notify(|frontier| println!("frontier: {:?}", frontier));
+    ///         input
+    ///     });
+    ///
+    ///     for round in 0..10 {
+    ///         input.send(format!("round {}", round));
+    ///         input.advance_to(round + 1);
+    ///         worker.step();
+    ///     }
+    /// }).unwrap();
+    /// ```
+    fn probe_notify<F>(&self, notify: F) -> StreamCore<G, C>
+    where
+        F: FnMut(AntichainRef<G::Timestamp>)+'static;
 }
 
 impl<G: Scope, C: Container + Data> Probe<G, C> for StreamCore<G, C> {
@@ -88,47 +120,74 @@ impl<G: Scope, C: Container + Data> Probe<G, C> for StreamCore<G, C> {
         handle
     }
     fn probe_with(&self, handle: &Handle<G::Timestamp>) -> StreamCore<G, C> {
-
-        let mut builder = OperatorBuilder::new("Probe".to_owned(), self.scope());
-        let mut input = PullCounter::new(builder.new_input(self, Pipeline));
-        let (tee, stream) = builder.new_output();
-        let mut output = PushBuffer::new(PushCounter::new(tee));
-
         let shared_frontier = Rc::downgrade(&handle.frontier);
-        let mut started = false;
-
-        builder.build(
-            move |progress| {
-
-                // surface all frontier changes to the shared frontier.
-                if let Some(shared_frontier) = shared_frontier.upgrade() {
-                    let mut borrow = shared_frontier.borrow_mut();
-                    borrow.update_iter(progress.frontiers[0].drain());
-                }
-
-                if !started {
-                    // discard initial capability.
-                    progress.internals[0].update(G::Timestamp::minimum(), -1);
-                    started = true;
-                }
-
-                while let Some(message) = input.next() {
-                    let time = &message.time;
-                    let data = &mut message.data;
-                    output.session(time).give_container(data);
-                }
-                output.cease();
-
-                // extract what we know about progress from the input and output adapters.
-                input.consumed().borrow_mut().drain_into(&mut progress.consumeds[0]);
-                output.inner().produced().borrow_mut().drain_into(&mut progress.produceds[0]);
-
-                false
-            },
-        );
-
-        stream
+        probe_operator(self, "Probe", move |changes| {
+            // surface all frontier changes to the shared frontier.
+            if let Some(shared_frontier) = shared_frontier.upgrade() {
+                let mut borrow = shared_frontier.borrow_mut();
+                borrow.update_iter(changes.drain());
+            }
+        })
     }
+    fn probe_notify<F>(&self, mut notify: F) -> StreamCore<G, C>
+    where
+        F: FnMut(AntichainRef<G::Timestamp>)+'static,
+    {
+        let mut frontier = MutableAntichain::new();
+        // The frontier last reported, or `None` before the first report.
+        let mut reported: Option<Antichain<G::Timestamp>> = None;
+        probe_operator(self, "ProbeNotify", move |changes| {
+            frontier.update_iter(changes.drain());
+            if reported.as_ref().map(|r| r.borrow() != frontier.frontier()).unwrap_or(true) {
+                notify(frontier.frontier());
+                reported = Some(frontier.frontier().to_owned());
+            }
+        })
+    }
+}
+
+/// Builds an operator named `name` that passes `stream` through, and presents the changes to
+/// its input frontier to `observe` each time it is scheduled.
+fn probe_operator<G, C, F>(stream: &StreamCore<G, C>, name: &str, mut observe: F) -> StreamCore<G, C>
+where
+    G: Scope,
+    C: Container + Data,
+    F: FnMut(&mut ChangeBatch<G::Timestamp>)+'static,
+{
+    let mut builder = OperatorBuilder::new(name.to_owned(), stream.scope());
+    let mut input = PullCounter::new(builder.new_input(stream, Pipeline));
+    let (tee, output_stream) = builder.new_output();
+    let mut output = PushBuffer::new(PushCounter::new(tee));
+
+    let mut started = false;
+
+    builder.build(
+        move |progress| {
+
+            observe(&mut progress.frontiers[0]);
+
+            if !started {
+                // discard initial capability.
+                progress.internals[0].update(G::Timestamp::minimum(), -1);
+                started = true;
+            }
+
+            while let Some(message) = input.next() {
+                let time = &message.time;
+                let data = &mut message.data;
+                output.session(time).give_container(data);
+            }
+            output.cease();
+
+            // extract what we know about progress from the input and output adapters.
+            input.consumed().borrow_mut().drain_into(&mut progress.consumeds[0]);
+            output.inner().produced().borrow_mut().drain_into(&mut progress.produceds[0]);
+
+            false
+        },
+    );
+
+    output_stream
 }
 
 /// Reports information about progress at the probe.
@@ -164,6 +223,44 @@ impl<T: Timestamp> Handle<T> {
     pub fn with_frontier<R, F: FnMut(AntichainRef<T>)->R>(&self, mut function: F) -> R {
         function(self.frontier.borrow().frontier())
     }
+
+    /// Steps `worker` until the frontier has passed `time`, returning the number of steps taken.
+    ///
+    /// The frontier has passed `time` once it is no longer less than or equal to `time`, which
+    /// means that `time` is complete at the probe. If this is already the case, the method
+    /// returns zero without stepping the worker.
+    ///
+    /// The worker parks between steps while it has no work to do, rather than spin. This method
+    /// blocks forever if the frontier never passes `time`, for example if an input that feeds the
+    /// probe is not advanced past `time`, or is advanced by this thread only after the call.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::*;
+    /// use timely::dataflow::operators::{Input, Probe};
+    ///
+    /// timely::execute(Config::thread(), |worker| {
+    ///
+    ///     let (mut input, probe) = worker.dataflow::<usize,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input::<String>();
+    ///         (input, stream.probe())
+    ///     });
+    ///
+    ///     for round in 0..10 {
+    ///         input.send(format!("round {}", round));
+    ///         input.advance_to(round + 1);
+    ///         probe.wait_until(worker, &round);
+    ///     }
+    /// }).unwrap();
+    /// ```
+    pub fn wait_until<A: Allocate>(&self, worker: &mut Worker<A>, time: &T) -> usize {
+        let mut steps = 0;
+        while self.less_equal(time) {
+            worker.step_or_park(None);
+            steps += 1;
+        }
+        steps
+    }
 }
 
 impl<T: Timestamp> Clone for Handle<T> {
@@ -222,4 +319,53 @@ mod tests {
         }).unwrap();
     }
 
+    #[test]
+    fn probe_notify() {
+
+        let reports = crate::execute::execute_directly(|worker| {
+            let reports = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+            let reports2 = reports.clone();
+            let mut input = worker.dataflow::<usize,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<String>();
+                stream.probe_notify(move |frontier| reports2.borrow_mut().push(frontier.to_vec()));
+                input
+            });
+
+            for round in 0..3 {
+                input.advance_to(round + 1);
+                worker.step();
+            }
+            input.close();
+            for _ in 0..5 {
+                worker.step();
+            }
+            let reports = reports.borrow().clone();
+            reports
+        });
+
+        assert_eq!(reports, vec![vec![0], vec![1], vec![2], vec![3], vec![]]);
+    }
+
+    #[test]
+    fn wait_until() {
+
+        crate::execute::execute_directly(|worker| {
+            let (mut input, probe) = worker.dataflow::<usize,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<String>();
+                (input, stream.probe())
+            });
+
+            input.advance_to(3);
+            assert!(probe.wait_until(worker, &1) > 0);
+            assert!(!probe.less_equal(&2));
+            assert!(probe.less_equal(&3));
+            // already passed, so no steps are needed.
+            assert_eq!(probe.wait_until(worker, &2), 0);
+
+            input.close();
+            probe.wait_until(worker, &usize::MAX);
+            assert!(probe.done());
+            assert_eq!(probe.wait_until(worker, &5), 0);
+        });
+    }
 }
@@ -38,11 +38,48 @@ impl<S: Scope, C: Container + Data> SharedStream<S, C> for StreamCore<S, C> {
     }
 }
 
+/// Convert a stream of shared containers into a stream of owned containers
+pub trait UnsharedStream<S: Scope, C: Container> {
+    /// Convert a stream of shared data into a stream of owned data
+    ///
+    /// Each container is unwrapped if its `Rc` is not shared, and cloned otherwise, for example
+    /// if the stream of shared data has other consumers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::rc::{SharedStream, UnsharedStream};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .shared()
+    ///            .unshared()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn unshared(&self) -> StreamCore<S, C>;
+}
+
+impl<S: Scope, C: Container + Data> UnsharedStream<S, C> for StreamCore<S, Rc<C>> {
+    fn unshared(&self) -> StreamCore<S, C> {
+        self.unary(Pipeline, "Unshared", move |_, _| {
+            move |input, output| {
+                input.for_each(|time, data| {
+                    let mut data = Rc::try_unwrap(std::mem::take(data)).unwrap_or_else(|data| (*data).clone());
+                    output
+                        .session(&time)
+                        .give_container(&mut data);
+                });
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::dataflow::channels::pact::Pipeline;
     use crate::dataflow::operators::capture::Extract;
-    use crate::dataflow::operators::rc::SharedStream;
+    use crate::dataflow::operators::rc::{SharedStream, UnsharedStream};
     use crate::dataflow::operators::{Capture, Concatenate, Operator, ToStream};
 
     #[test]
@@ -74,4 +111,18 @@ mod test {
         output.dedup();
         assert_eq!(output.len(), 1);
     }
+
+    #[test]
+    fn test_unshared() {
+        let output = crate::example(|scope| {
+            let shared = (0..10).to_stream(scope).container::<Vec<_>>().shared();
+            // the shared containers have two consumers, so at least one must clone them.
+            let unwrapped = shared.unshared();
+            let cloned = shared.unshared();
+            scope.concatenate([unwrapped, cloned]).capture()
+        });
+        let mut output = output.extract().into_iter().flat_map(|(_, data)| data).collect::<Vec<_>>();
+        output.sort();
+        assert_eq!(output, (0..10).flat_map(|x| [x, x]).collect::<Vec<_>>());
+    }
 }
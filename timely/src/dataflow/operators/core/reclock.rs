@@ -46,10 +46,59 @@ pub trait Reclock<S: Scope> {
     /// assert_eq!(extracted[2], (8, vec![6,7,8]));
     /// ```
     fn reclock<TC: Container + Data>(&self, clock: &StreamCore<S, TC>) -> Self;
+
+    /// Delays records until an input is observed on the `clock` input, reporting the times it maps.
+    ///
+    /// The output is that of `reclock`. In addition, when data are sent at a clock time,
+    /// `logic` is called with the original time of the data and the clock time. The call is made
+    /// once for each batch of data, and so may be repeated for the same pair of times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Map, Reclock, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = std::sync::mpsc::channel();
+    /// let captured = timely::example(move |scope| {
+    ///
+    ///     // produce data 0..10 at times 0..10.
+    ///     let data = (0..10).to_stream(scope)
+    ///                       .delay(|x,t| *x);
+    ///
+    ///     // product clock ticks at three times.
+    ///     let clock = vec![3, 5, 8].into_iter()
+    ///                              .to_stream(scope)
+    ///                              .delay(|x,t| *x)
+    ///                              .map(|_| ());
+    ///
+    ///     // reclock the data, reporting the mapping.
+    ///     data.reclock_with_log(&clock, move |time, clock_time| send.send((*time, *clock_time)).unwrap())
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract().len(), 3);
+    /// let mut mapping = recv.try_iter().collect::<Vec<_>>();
+    /// mapping.sort();
+    /// mapping.dedup();
+    /// assert_eq!(mapping, vec![(0,3), (1,3), (2,3), (3,3), (4,5), (5,5), (6,8), (7,8), (8,8)]);
+    /// ```
+    fn reclock_with_log<TC, L>(&self, clock: &StreamCore<S, TC>, logic: L) -> Self
+    where
+        TC: Container + Data,
+        L: FnMut(&S::Timestamp, &S::Timestamp)+'static;
 }
 
 impl<S: Scope, C: Container + Data> Reclock<S> for StreamCore<S, C> {
     fn reclock<TC: Container + Data>(&self, clock: &StreamCore<S, TC>) -> StreamCore<S, C> {
+        self.reclock_with_log(clock, |_, _| { })
+    }
+
+    fn reclock_with_log<TC, L>(&self, clock: &StreamCore<S, TC>, mut logic: L) -> StreamCore<S, C>
+    where
+        TC: Container + Data,
+        L: FnMut(&S::Timestamp, &S::Timestamp)+'static,
+    {
 
         let mut stash = vec![];
 
@@ -70,6 +119,7 @@ impl<S: Scope, C: Container + Data> Reclock<S> for StreamCore<S, C> {
                 let mut session = output.session(&cap);
                 for &mut (ref t, ref mut data) in &mut stash {
                     if t.less_equal(cap.time()) {
+                        logic(t, cap.time());
                         session.give_container(data);
                     }
                 }
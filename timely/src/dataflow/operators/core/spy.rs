@@ -0,0 +1,69 @@
+//! Extension trait and implementation for observing containers with a user-supplied pusher.
+
+use crate::communication::Push;
+use crate::{Container, Data};
+use crate::dataflow::channels::Message;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::{Scope, StreamCore};
+
+/// Observes containers with a user-supplied pusher.
+pub trait Spy<G: Scope, C: Container> {
+    /// Passes containers through unchanged, and pushes a copy of each at `pusher`.
+    ///
+    /// Each container is cloned into a message at its time and pushed at `pusher`, which is
+    /// flushed with `done()` after each scheduling in which it receives messages. The messages
+    /// do not participate in progress tracking, and their `from` and `seq` fields are zero.
+    ///
+    /// The pusher is called synchronously on the worker thread, and a slow pusher delays the
+    /// operator and so the containers it passes downstream.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use timely::communication::Push;
+    /// use timely::dataflow::channels::Message;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::core::Spy;
+    ///
+    /// /// Counts the records pushed at it.
+    /// struct Counter(Arc<AtomicUsize>);
+    /// impl Push<Message<u64, Vec<u64>>> for Counter {
+    ///     fn push(&mut self, message: &mut Option<Message<u64, Vec<u64>>>) {
+    ///         if let Some(message) = message {
+    ///             self.0.fetch_add(message.data.len(), Ordering::SeqCst);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let count = Arc::new(AtomicUsize::new(0));
+    /// let count2 = Arc::clone(&count);
+    /// timely::example(move |scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .spy(Counter(count2))
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// assert_eq!(count.load(Ordering::SeqCst), 10);
+    /// ```
+    fn spy<P: Push<Message<G::Timestamp, C>>+'static>(&self, pusher: P) -> StreamCore<G, C>;
+}
+
+impl<G: Scope, C: Container + Data> Spy<G, C> for StreamCore<G, C> {
+    fn spy<P: Push<Message<G::Timestamp, C>>+'static>(&self, mut pusher: P) -> StreamCore<G, C> {
+        // A container reused for copies, which the pusher may replace.
+        let mut buffer = C::default();
+        self.unary(Pipeline, "Spy", move |_, _| move |input, output| {
+            let mut pushed = false;
+            input.for_each(|time, data| {
+                buffer.clone_from(data);
+                Message::push_at(&mut buffer, time.time().clone(), &mut pusher);
+                pushed = true;
+                output.session(&time).give_container(data);
+            });
+            if pushed {
+                pusher.done();
+            }
+        })
+    }
+}
@@ -0,0 +1,121 @@
+//! Extension trait and implementation for emitting markers when a stream falls silent.
+
+use std::time::{Duration, Instant};
+
+use crate::{Container, Data};
+use crate::container::{SizableContainer, PushInto};
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::CapabilitySet;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::{Scope, StreamCore};
+
+/// Emits markers when no data arrive for some time.
+pub trait Timeout<G: Scope, C: Container> {
+    /// Passes data through unchanged, and emits a marker whenever no data arrive for `duration`.
+    ///
+    /// The deadline for the next marker is reset when data arrive and when a marker is emitted, so
+    /// data arriving before the deadline cancel the pending marker. The operator relies on a timed
+    /// activation to be scheduled at the deadline, and so measures wall-clock time only approximately.
+    ///
+    /// A marker is produced by `make_marker` for each time in the input frontier, and is sent at
+    /// that time, which is the earliest time at which data may still arrive. Once the input
+    /// frontier is empty, no further markers are emitted.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::core::Timeout;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .timeout(Duration::from_secs(1), |time| *time + 1000)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn timeout<D, L>(&self, duration: Duration, make_marker: L) -> StreamCore<G, C>
+    where
+        C: SizableContainer + PushInto<D>,
+        L: FnMut(&G::Timestamp) -> D + 'static;
+}
+
+impl<G: Scope, C: Container + Data> Timeout<G, C> for StreamCore<G, C> {
+    fn timeout<D, L>(&self, duration: Duration, mut make_marker: L) -> StreamCore<G, C>
+    where
+        C: SizableContainer + PushInto<D>,
+        L: FnMut(&G::Timestamp) -> D + 'static,
+    {
+        let scope = self.scope();
+        self.unary_frontier(Pipeline, "Timeout", move |capability, info| {
+
+            let activator = scope.activator_for(info.address);
+            // Capabilities for the input frontier, at which markers are sent.
+            let mut capabilities = CapabilitySet::from_elem(capability);
+            let mut deadline = Instant::now() + duration;
+            activator.activate_after(duration);
+
+            move |input, output| {
+                input.for_each(|time, data| {
+                    deadline = Instant::now() + duration;
+                    output.session(&time).give_container(data);
+                });
+
+                capabilities.downgrade(&input.frontier().frontier());
+                if !capabilities.is_empty() {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        for capability in capabilities.iter() {
+                            output.session(capability).give(make_marker(capability.time()));
+                        }
+                        deadline = now + duration;
+                    }
+                    // Timed activations coalesce, so this does not accumulate pending activations.
+                    activator.activate_after(deadline - now);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Inspect, Probe};
+    use super::Timeout;
+
+    #[test]
+    fn timeout() {
+        crate::execute::execute_directly(|worker| {
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            let seen2 = Rc::clone(&seen);
+            let mut input = InputHandle::new();
+            let probe = worker.dataflow::<u64,_,_>(|scope| {
+                scope.input_from(&mut input)
+                     .timeout(Duration::from_millis(100), |time| 1000 + *time)
+                     .inspect_time(move |time, x| seen2.borrow_mut().push((*time, *x)))
+                     .probe()
+            });
+
+            // Data that arrive before the deadline pass through, and cancel the pending marker.
+            input.send(0);
+            input.advance_to(3);
+            worker.step_while(|| probe.less_than(input.time()));
+
+            // Without data, a marker is emitted at the input frontier, after the data. Markers may
+            // already have been emitted if the steps above were slow, so only the order is checked.
+            while seen.borrow().len() < 2 {
+                worker.step_or_park(Some(Duration::from_millis(10)));
+            }
+            assert_eq!(seen.borrow()[..2], [(0, 0), (3, 1003)]);
+
+            // Closing the input stops markers, and lets the dataflow complete.
+            drop(input);
+            worker.step_while(|| !probe.done());
+        });
+    }
+}
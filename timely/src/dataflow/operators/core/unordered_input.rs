@@ -17,7 +17,7 @@ use crate::dataflow::channels::pushers::buffer::{Buffer as PushBuffer, Autoflush
 
 use crate::dataflow::operators::{ActivateCapability, Capability};
 
-use crate::dataflow::{Scope, StreamCore};
+use crate::dataflow::{Scope, ScopeParent, StreamCore};
 
 /// Create a new `Stream` and `Handle` through which to supply input.
 pub trait UnorderedInput<G: Scope> {
@@ -76,16 +76,69 @@ pub trait UnorderedInput<G: Scope> {
     ///     assert_eq!(extract[i], (i, vec![i]));
     /// }
     /// ```
-    fn new_unordered_input<CB: ContainerBuilder>(&mut self) -> ((UnorderedHandle<G::Timestamp, CB>, ActivateCapability<G::Timestamp>), StreamCore<G, CB::Container>);
+    fn new_unordered_input<CB: ContainerBuilder>(&mut self) -> UnorderedInputParts<G, CB>;
+
+    /// Create a new capability-based [StreamCore] and [UnorderedHandle] through which to supply input,
+    /// whose capability is for `time` rather than the default value of the timestamp type.
+    ///
+    /// The input never holds a capability for times not greater or equal to `time`, and the
+    /// frontiers downstream of the input start at `time`. This allows a computation to resume
+    /// from a time other than the minimum, for example after a restart.
+    ///
+    /// Each worker must supply the same `time` when constructing the same dataflow. The input
+    /// accounts for the initial capabilities of all workers as copies of its own, and so workers
+    /// that supply different times leave the progress tracking of the dataflow inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{Probe, capture::Extract, Capture};
+    /// use timely::dataflow::operators::core::UnorderedInput;
+    ///
+    /// let extract = timely::execute_directly(|worker| {
+    ///
+    ///     // create an unordered input starting at time 5.
+    ///     let ((mut input, cap), probe, captured) = worker.dataflow::<usize,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_unordered_input_at(5);
+    ///         let stream = stream.container::<Vec<_>>();
+    ///         (input, stream.probe(), stream.capture())
+    ///     });
+    ///
+    ///     // downstream frontiers start at time 5, rather than at the minimum.
+    ///     worker.step();
+    ///     assert!(!probe.less_than(&5));
+    ///     assert!(probe.less_equal(&5));
+    ///
+    ///     input.session(cap.delayed(&7)).give(7);
+    ///     drop(cap);
+    ///     worker.step_while(|| !probe.done());
+    ///     captured
+    /// });
+    ///
+    /// assert_eq!(extract.extract(), vec![(7, vec![7])]);
+    /// ```
+    fn new_unordered_input_at<CB: ContainerBuilder>(&mut self, time: G::Timestamp) -> UnorderedInputParts<G, CB>;
 }
 
+/// A handle and capability through which to supply input, and the stream of the input.
+type UnorderedInputParts<G, CB> = (
+    (UnorderedHandle<<G as ScopeParent>::Timestamp, CB>, ActivateCapability<<G as ScopeParent>::Timestamp>),
+    StreamCore<G, <CB as ContainerBuilder>::Container>,
+);
+
 impl<G: Scope> UnorderedInput<G> for G {
-    fn new_unordered_input<CB: ContainerBuilder>(&mut self) -> ((UnorderedHandle<G::Timestamp, CB>, ActivateCapability<G::Timestamp>), StreamCore<G, CB::Container>) {
+    fn new_unordered_input<CB: ContainerBuilder>(&mut self) -> UnorderedInputParts<G, CB> {
+        self.new_unordered_input_at(G::Timestamp::minimum())
+    }
+
+    fn new_unordered_input_at<CB: ContainerBuilder>(&mut self, time: G::Timestamp) -> UnorderedInputParts<G, CB> {
 
         let (output, registrar) = Tee::<G::Timestamp, CB::Container>::new();
         let internal = Rc::new(RefCell::new(ChangeBatch::new()));
         // let produced = Rc::new(RefCell::new(ChangeBatch::new()));
-        let cap = Capability::new(G::Timestamp::minimum(), internal.clone());
+        // The capability is reported as the operator's initial internal capability, from which
+        // the initial frontiers downstream are derived.
+        let cap = Capability::new(time, internal.clone());
         let counter = Counter::new(output);
         let produced = counter.produced().clone();
         let peers = self.peers();
@@ -7,6 +7,7 @@ use crate::order::{PartialOrder, TotalOrder};
 use crate::dataflow::channels::pact::Pipeline;
 use crate::dataflow::{Stream, Scope};
 use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::operators::core::{Delay as DelayCore};
 
 /// Methods to advance the timestamps of records or batches of records.
 pub trait Delay<G: Scope, D: Data> {
@@ -70,7 +71,8 @@ pub trait Delay<G: Scope, D: Data> {
     ///
     /// The operator will test that the new timestamp is greater or equal to the
     /// old timestamp, and will assert if it is not. The batch version does not
-    /// consult the data, and may only view the timestamp itself.
+    /// consult the data, and may only view the timestamp itself; each batch is
+    /// moved to its new time without being rebuilt.
     ///
     /// # Examples
     ///
@@ -123,25 +125,7 @@ impl<G: Scope, D: Data> Delay<G, D> for Stream<G, D> {
         self.delay(func)
     }
 
-    fn delay_batch<L: FnMut(&G::Timestamp)->G::Timestamp+'static>(&self, mut func: L) -> Self {
-        let mut elements = HashMap::new();
-        self.unary_notify(Pipeline, "Delay", vec![], move |input, output, notificator| {
-            input.for_each(|time, data| {
-                let new_time = func(&time);
-                assert!(time.time().less_equal(&new_time));
-                elements.entry(new_time.clone())
-                        .or_insert_with(|| { notificator.notify_at(time.delayed(&new_time)); Vec::new() })
-                        .push(std::mem::take(data));
-            });
-
-            // for each available notification, send corresponding set
-            notificator.for_each(|time,_,_| {
-                if let Some(mut datas) = elements.remove(&time) {
-                    for mut data in datas.drain(..) {
-                        output.session(&time).give_container(&mut data);
-                    }
-                }
-            });
-        })
+    fn delay_batch<L: FnMut(&G::Timestamp)->G::Timestamp+'static>(&self, func: L) -> Self {
+        DelayCore::delay_batch(self, func)
     }
 }
@@ -0,0 +1,90 @@
+//! Removes duplicate records within each time.
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Removes duplicate records within each time.
+pub trait Distinct<G: Scope, D: Data+Hash+Eq> {
+    /// Produces each record at most once at each time.
+    ///
+    /// A record is produced when it is first received at a time, and further copies of it at the
+    /// same time are discarded. Copies at different times are each produced. The records seen at
+    /// a time are retained until the time is complete, and then released.
+    ///
+    /// Records are only compared with records received by the same worker. To remove duplicates
+    /// across workers, first exchange records by a hash of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Distinct, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _| x / 5)
+    ///            .distinct()
+    ///            .capture()
+    /// });
+    ///
+    /// let extracted = captured.extract();
+    /// assert_eq!(extracted, vec![(0, vec![0, 1, 2, 3, 4]), (1, vec![5, 6, 7, 8, 9])]);
+    /// ```
+    fn distinct(&self) -> Stream<G, D>;
+}
+
+impl<G: Scope, D: Data+Hash+Eq> Distinct<G, D> for Stream<G, D> {
+    fn distinct(&self) -> Stream<G, D> {
+
+        let mut seen = HashMap::new();
+        self.unary_notify(Pipeline, "Distinct", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                let seen = seen.entry(time.time().clone()).or_insert_with(HashSet::new);
+                data.retain(|datum| seen.insert(datum.clone()));
+                output.session(&time).give_container(data);
+                notificator.notify_at(time.retain());
+            });
+            // Release the records of completed times.
+            notificator.for_each(|time, _, _| {
+                seen.remove(time.time());
+            });
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::capture::Extract;
+    use super::Distinct;
+
+    #[test]
+    fn distinct() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream = scope.input_from(&mut input).distinct();
+                (stream.probe(), stream.capture())
+            });
+            // Duplicates arrive within and across batches at a time, and again at the next time.
+            for round in 0..2 {
+                input.send_batch(&mut vec!['a', 'b', 'a']);
+                worker.step();
+                input.send_batch(&mut vec!['b', 'c']);
+                input.advance_to(round + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+            captured
+        });
+
+        let mut extracted = captured.extract();
+        for (_, data) in extracted.iter_mut() { data.sort(); }
+        assert_eq!(extracted, vec![(0, vec!['a', 'b', 'c']), (1, vec!['a', 'b', 'c'])]);
+    }
+}
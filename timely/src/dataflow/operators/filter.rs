@@ -19,12 +19,28 @@ pub trait Filter<D: Data> {
     ///            .inspect(|x| println!("seen: {:?}", x));
     /// });
     /// ```
-    fn filter<P: FnMut(&D)->bool+'static>(&self, predicate: P) -> Self;
+    fn filter<P: FnMut(&D)->bool+'static>(&self, predicate: P) -> Self where Self: Sized {
+        self.filter_named("Filter", predicate)
+    }
+    /// Returns a new instance of `self` containing only records satisfying `predicate`, in an
+    /// operator named `name`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Filter, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .filter_named("Evens", |x| *x % 2 == 0)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn filter_named<P: FnMut(&D)->bool+'static>(&self, name: &str, predicate: P) -> Self;
 }
 
 impl<G: Scope, D: Data> Filter<D> for Stream<G, D> {
-    fn filter<P: FnMut(&D)->bool+'static>(&self, mut predicate: P) -> Stream<G, D> {
-        self.unary(Pipeline, "Filter", move |_,_| move |input, output| {
+    fn filter_named<P: FnMut(&D)->bool+'static>(&self, name: &str, mut predicate: P) -> Stream<G, D> {
+        self.unary(Pipeline, name, move |_,_| move |input, output| {
             input.for_each(|time, data| {
                 data.retain(|x| predicate(x));
                 if !data.is_empty() {
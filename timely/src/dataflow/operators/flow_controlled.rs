@@ -1,9 +1,12 @@
-//! Methods to construct flow-controlled sources.
+//! Methods to construct flow-controlled sources and streams.
+
+use std::collections::{BTreeMap, VecDeque};
 
 use crate::Data;
 use crate::order::{PartialOrder, TotalOrder};
 use crate::progress::timestamp::Timestamp;
-use crate::dataflow::operators::generic::operator::source;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::operator::{source, Operator};
 use crate::dataflow::operators::probe::Handle;
 use crate::dataflow::{Stream, Scope};
 
@@ -124,3 +127,225 @@ pub fn iterator_source<
         }
     })
 }
+
+/// Extension trait to bound the number of epochs in flight downstream of a stream.
+pub trait LimitEpochs<G: Scope, D: Data> {
+    /// Holds back data for new epochs while too many released epochs are incomplete at `probe`.
+    ///
+    /// An epoch is in flight if the operator has released data at its timestamp, and `probe`
+    /// has not yet passed that timestamp. Data at a new timestamp are only released while fewer
+    /// than `max_epochs` epochs are in flight; otherwise they are stashed, with a capability
+    /// retained for their timestamp, and released in timestamp order as in-flight epochs complete.
+    /// Data at timestamps not greater than some in-flight epoch are always released, as they
+    /// must complete before that epoch can.
+    ///
+    /// The `probe` should be placed downstream of the output, or the operator may wait forever.
+    /// While data are stashed the operator re-activates itself to observe the probe.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{Input, Inspect, Probe};
+    /// use timely::dataflow::operators::flow_controlled::LimitEpochs;
+    /// use timely::dataflow::operators::probe::Handle;
+    ///
+    /// timely::execute(timely::Config::thread(), |worker| {
+    ///     let probe = Handle::new();
+    ///     let mut input = worker.dataflow::<u64,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input::<u64>();
+    ///         stream.limit_epochs(2, &probe)
+    ///               .inspect(|x| println!("seen: {:?}", x))
+    ///               .probe_with(&probe);
+    ///         input
+    ///     });
+    ///
+    ///     // the source races ahead, but at most two epochs are in flight.
+    ///     for round in 0..10 {
+    ///         input.send(round);
+    ///         input.advance_to(round + 1);
+    ///     }
+    ///     input.close();
+    /// }).unwrap();
+    /// ```
+    fn limit_epochs(&self, max_epochs: usize, probe: &Handle<G::Timestamp>) -> Stream<G, D>;
+}
+
+impl<G: Scope, D: Data> LimitEpochs<G, D> for Stream<G, D> where G::Timestamp: TotalOrder {
+    fn limit_epochs(&self, max_epochs: usize, probe: &Handle<G::Timestamp>) -> Stream<G, D> {
+        assert!(max_epochs > 0, "limit_epochs requires a positive number of epochs");
+        let probe = probe.clone();
+        let scope = self.scope();
+        self.unary(Pipeline, "LimitEpochs", move |_capability, info| {
+            let activator = scope.activator_for(info.address);
+            let mut stash = BTreeMap::new();
+            // Released timestamps not yet passed by `probe`, in increasing order.
+            let mut in_flight: Vec<G::Timestamp> = Vec::new();
+            move |input, output| {
+                input.for_each(|time, data| {
+                    stash.entry(time.time().clone())
+                         .or_insert_with(|| (time.retain(), Vec::new()))
+                         .1
+                         .push(std::mem::take(data));
+                });
+
+                in_flight.retain(|time| probe.less_equal(time));
+                while let Some(entry) = stash.first_entry() {
+                    let admit =
+                    in_flight.len() < max_epochs ||
+                    in_flight.last().map(|last| entry.key().less_equal(last)).unwrap_or(false);
+                    if !admit { break; }
+                    let (time, (capability, batches)) = entry.remove_entry();
+                    let mut session = output.session(&capability);
+                    for mut batch in batches {
+                        session.give_container(&mut batch);
+                    }
+                    if let Err(position) = in_flight.binary_search(&time) {
+                        in_flight.insert(position, time);
+                    }
+                }
+
+                if !stash.is_empty() {
+                    activator.activate();
+                }
+            }
+        })
+    }
+}
+
+/// Extension trait to bound the number of records a stream emits per activation.
+pub trait Throttle<G: Scope, D: Data> {
+    /// Emits at most `limit` records each time the operator is scheduled, queueing the rest.
+    ///
+    /// Received batches are queued in arrival order, each with a capability retained for its
+    /// timestamp. The capability is only released once all of its batch's records have been
+    /// emitted, so that downstream frontiers do not advance past queued records. While records
+    /// are queued the operator re-activates itself, and so emits up to `limit` records in each
+    /// subsequent `worker.step()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::flow_controlled::Throttle;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..100).to_stream(scope)
+    ///             .throttle(10)
+    ///             .inspect_batch(|t, xs| assert!(xs.len() <= 10));
+    /// });
+    /// ```
+    fn throttle(&self, limit: usize) -> Stream<G, D>;
+}
+
+impl<G: Scope, D: Data> Throttle<G, D> for Stream<G, D> {
+    fn throttle(&self, limit: usize) -> Stream<G, D> {
+        assert!(limit > 0, "throttle requires a positive number of records");
+        let scope = self.scope();
+        self.unary(Pipeline, "Throttle", move |_capability, info| {
+            let activator = scope.activator_for(info.address);
+            let mut queue = VecDeque::new();
+            move |input, output| {
+                input.for_each(|time, data| {
+                    // A `VecDeque` drains records from its front in time proportional to their number.
+                    queue.push_back((time.retain(), VecDeque::from(std::mem::take(data))));
+                });
+
+                let mut budget = limit;
+                while budget > 0 {
+                    let Some((capability, batch)) = queue.front_mut() else { break };
+                    let count = std::cmp::min(budget, batch.len());
+                    output.session(capability).give_iterator(batch.drain(.. count));
+                    budget -= count;
+                    if batch.is_empty() {
+                        queue.pop_front();
+                    }
+                }
+
+                if !queue.is_empty() {
+                    activator.activate();
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    use crate::dataflow::operators::{Input, Inspect, Probe, Delay};
+    use crate::dataflow::operators::probe::Handle;
+    use super::{LimitEpochs, Throttle};
+
+    #[test]
+    fn limit_epochs() {
+        crate::execute(crate::Config::thread(), |worker| {
+            let probe = Handle::new();
+            let released = Rc::new(RefCell::new(Vec::new()));
+            let released2 = released.clone();
+            let mut input = worker.dataflow::<u64,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<u64>();
+                stream.limit_epochs(3, &probe)
+                      .inspect_time(move |time, _| released2.borrow_mut().push(*time))
+                      // a slow sink, which holds each epoch back for a while.
+                      .delay_batch(|time| time + 5)
+                      .probe_with(&probe);
+                input
+            });
+
+            // a fast source, which introduces all of its epochs at once.
+            for round in 0..100 {
+                input.send(round);
+                input.advance_to(round + 1);
+            }
+            input.close();
+
+            while !probe.done() {
+                worker.step();
+                let mut open = released.borrow().iter().filter(|t| probe.less_equal(t)).cloned().collect::<Vec<_>>();
+                open.sort();
+                open.dedup();
+                assert!(open.len() <= 3, "too many open epochs: {:?}", open);
+            }
+            assert_eq!(released.borrow().len(), 100);
+        }).unwrap();
+    }
+
+    #[test]
+    fn throttle() {
+        crate::execute(crate::Config::thread(), |worker| {
+            let probe = Handle::new();
+            let emitted = Rc::new(RefCell::new(Vec::new()));
+            let emitted2 = emitted.clone();
+            let mut input = worker.dataflow::<u64,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<u64>();
+                stream.throttle(10)
+                      .inspect_batch(move |time, data| emitted2.borrow_mut().push((*time, data.len())))
+                      .probe_with(&probe);
+                input
+            });
+
+            // a burst of records at two times.
+            for round in 0..2 {
+                for record in 0..25 {
+                    input.send(record);
+                }
+                input.advance_to(round + 1);
+            }
+            input.close();
+
+            let mut steps = 0;
+            while !probe.done() {
+                worker.step();
+                steps += 1;
+                let emitted = emitted.borrow();
+                let total = emitted.iter().map(|(_, count)| count).sum::<usize>();
+                assert!(total <= 10 * steps, "emitted {} records in {} steps", total, steps);
+                // time 0 must not complete before all of its records are emitted.
+                let at_zero = emitted.iter().filter(|(time, _)| *time == 0).map(|(_, count)| count).sum::<usize>();
+                assert!(at_zero == 25 || probe.less_equal(&0));
+            }
+            assert_eq!(emitted.borrow().iter().map(|(_, count)| count).sum::<usize>(), 50);
+        }).unwrap();
+    }
+}
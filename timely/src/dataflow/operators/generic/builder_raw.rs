@@ -6,7 +6,7 @@
 
 use std::default::Default;
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use crate::scheduling::{Schedule, Activations};
 
@@ -61,6 +61,7 @@ pub struct OperatorBuilder<G: Scope> {
     address: Rc<[usize]>,    // path to the operator (ending with index).
     shape: OperatorShape,
     summary: Vec<Vec<Antichain<<G::Timestamp as Timestamp>::Summary>>>,
+    relaxations: Rc<Cell<usize>>,
 }
 
 impl<G: Scope> OperatorBuilder<G> {
@@ -80,6 +81,7 @@ impl<G: Scope> OperatorBuilder<G> {
             address,
             shape: OperatorShape::new(name, peers),
             summary: vec![],
+            relaxations: Rc::new(Cell::new(0)),
         }
     }
 
@@ -103,6 +105,14 @@ impl<G: Scope> OperatorBuilder<G> {
         self.shape.notify = notify;
     }
 
+    /// The number of times the operator has been asked to relax, shared with the built operator.
+    ///
+    /// An operator with outputs is activated whenever it is asked to relax, and can then release
+    /// resources it retains for reuse, for example by relaxing its output buffers.
+    pub fn relaxations(&self) -> Rc<Cell<usize>> {
+        Rc::clone(&self.relaxations)
+    }
+
     /// Adds a new input to a generic operator builder, returning the `Pull` implementor to use.
     pub fn new_input<C: Container, P>(&mut self, stream: &StreamCore<G, C>, pact: P) -> P::Puller
         where
@@ -167,6 +177,7 @@ impl<G: Scope> OperatorBuilder<G> {
             logic,
             shared_progress: Rc::new(RefCell::new(SharedProgress::new(inputs, outputs))),
             summary: self.summary,
+            relaxations: self.relaxations,
         };
 
         self.scope.add_operator_with_indices(Box::new(operator), self.index, self.global);
@@ -189,6 +200,7 @@ where
     shared_progress: Rc<RefCell<SharedProgress<T>>>,
     activations: Rc<RefCell<Activations>>,
     summary: Vec<Vec<Antichain<T::Summary>>>,
+    relaxations: Rc<Cell<usize>>,
 }
 
 impl<T, L> Schedule for OperatorCore<T, L>
@@ -202,6 +214,13 @@ where
         let shared_progress = &mut *self.shared_progress.borrow_mut();
         (self.logic)(shared_progress)
     }
+    fn relax(&mut self) {
+        self.relaxations.set(self.relaxations.get() + 1);
+        // Only outputs retain resources, which they release when next activated.
+        if self.shape.outputs > 0 {
+            self.activations.borrow_mut().activate(&self.address[..]);
+        }
+    }
 }
 
 impl<T, L> Operate<T> for OperatorCore<T, L>
@@ -126,7 +126,7 @@ impl<G: Scope> OperatorBuilder<G> {
             summary.borrow_mut().push(connection.clone());
         }
 
-        (OutputWrapper::new(buffer, internal), stream)
+        (OutputWrapper::new(buffer, internal).with_relaxations(self.builder.relaxations()), stream)
     }
 
     /// Creates an operator implementation from supplied logic constructor.
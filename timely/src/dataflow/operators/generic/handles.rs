@@ -4,7 +4,7 @@
 //! the operator would with its input and output streams.
 
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use crate::progress::Antichain;
 use crate::progress::Timestamp;
@@ -169,6 +169,8 @@ pub fn new_input_handle<T: Timestamp, C: Container, P: Pull<Message<T, C>>>(
 pub struct OutputWrapper<T: Timestamp, CB: ContainerBuilder, P: Push<Message<T, CB::Container>>> {
     push_buffer: Buffer<T, CB, PushCounter<T, CB::Container, P>>,
     internal_buffer: Rc<RefCell<ChangeBatch<T>>>,
+    /// The operator's requests to relax, and the number of them the output has acted on.
+    relaxations: Option<(Rc<Cell<usize>>, usize)>,
 }
 
 impl<T: Timestamp, CB: ContainerBuilder, P: Push<Message<T, CB::Container>>> OutputWrapper<T, CB, P> {
@@ -177,13 +179,29 @@ impl<T: Timestamp, CB: ContainerBuilder, P: Push<Message<T, CB::Container>>> Out
         OutputWrapper {
             push_buffer,
             internal_buffer,
+            relaxations: None,
         }
     }
+
+    /// Relaxes the push buffer when next activated after each increment of `relaxations`.
+    pub(crate) fn with_relaxations(mut self, relaxations: Rc<Cell<usize>>) -> Self {
+        let seen = relaxations.get();
+        self.relaxations = Some((relaxations, seen));
+        self
+    }
     /// Borrows the push buffer into a handle, which can be used to send records.
     ///
     /// This method ensures that the only access to the push buffer is through the `OutputHandle`
     /// type which ensures the use of capabilities, and which calls `cease` when it is dropped.
+    /// If the operator has been asked to relax since the last activation, the push buffer is
+    /// relaxed first.
     pub fn activate(&mut self) -> OutputHandleCore<T, CB, P> {
+        if let Some((relaxations, seen)) = self.relaxations.as_mut() {
+            if relaxations.get() != *seen {
+                *seen = relaxations.get();
+                self.push_buffer.relax();
+            }
+        }
         OutputHandleCore {
             push_buffer: &mut self.push_buffer,
             internal_buffer: &self.internal_buffer,
@@ -233,6 +251,14 @@ impl<'a, T: Timestamp, CB: ContainerBuilder, P: Push<Message<T, CB::Container>>>
     pub fn cease(&mut self) {
         self.push_buffer.cease();
     }
+
+    /// Flushes all pending data, and releases resources held for building containers.
+    ///
+    /// Intended for operators that expect their output to be idle for a while, as the output may
+    /// need to allocate again when next used.
+    pub fn relax(&mut self) {
+        self.push_buffer.relax();
+    }
 }
 
 impl<'a, T: Timestamp, C: Container + Data, P: Push<Message<T, C>>> OutputHandleCore<'a, T, CapacityContainerBuilder<C>, P> {
@@ -11,5 +11,5 @@ mod operator_info;
 pub use self::handles::{InputHandle, InputHandleCore, FrontieredInputHandle, FrontieredInputHandleCore, OutputHandle, OutputHandleCore, OutputWrapper};
 pub use self::notificator::{Notificator, FrontierNotificator};
 
-pub use self::operator::{Operator, source};
+pub use self::operator::{Operator, source, source_set};
 pub use self::operator_info::OperatorInfo;
@@ -1,5 +1,5 @@
 use crate::progress::frontier::{AntichainRef, MutableAntichain};
-use crate::progress::Timestamp;
+use crate::progress::{PathSummary, Timestamp};
 use crate::dataflow::operators::Capability;
 use crate::logging::TimelyLogger as Logger;
 
@@ -75,6 +75,46 @@ impl<'a, T: Timestamp> Notificator<'a, T> {
         self.inner.notify_at_frontiered(cap, self.frontiers);
     }
 
+    /// Requests a notification at the time `summary` advances the time of `cap` to.
+    ///
+    /// This is `notify_at(cap.delayed(&time))` for the advanced time, except that no further
+    /// notification is requested if one is already requested at that time, and that it returns
+    /// `false` rather than panicking if `summary` does not advance the time of `cap` to a time
+    /// greater or equal to it.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .unary_notify(Pipeline, "example", None, |input, output, notificator| {
+    ///                input.for_each(|cap, data| {
+    ///                    output.session(&cap).give_container(data);
+    ///                    // notify once the window of five times starting at `cap` has passed.
+    ///                    let cap = cap.retain();
+    ///                    assert!(notificator.notify_at_frontier(&cap, &5));
+    ///                });
+    ///                notificator.for_each(|cap, count, _| {
+    ///                    assert_eq!((*cap.time(), count), (5, 1));
+    ///                });
+    ///            });
+    /// });
+    /// ```
+    pub fn notify_at_frontier<S: PathSummary<T>>(&mut self, cap: &Capability<T>, summary: &S) -> bool {
+        match summary.results_in(cap.time()).and_then(|time| cap.try_delayed(&time)) {
+            Some(delayed) => {
+                if !self.inner.requested(delayed.time()) {
+                    self.notify_at(delayed);
+                }
+                true
+            },
+            None => false,
+        }
+    }
+
     /// Repeatedly calls `logic` until exhaustion of the available notifications.
     ///
     /// `logic` receives a capability for `t`, the timestamp being notified and a `count`
@@ -175,6 +215,33 @@ fn notificator_delivers_notifications_in_topo_order() {
     }
 }
 
+#[test]
+fn frontier_notificator_available_yields_ready_times_once() {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::progress::ChangeBatch;
+    use crate::progress::frontier::MutableAntichain;
+    use crate::dataflow::operators::capability::Capability;
+
+    let mut frontier = MutableAntichain::new_bottom(0);
+    let root_capability = Capability::new(0, Rc::new(RefCell::new(ChangeBatch::new())));
+    let mut notificator = FrontierNotificator::from([4, 1, 3, 1, 2].iter().map(|t| root_capability.delayed(t)));
+
+    // nothing is available while the frontier is at zero.
+    assert_eq!(notificator.available(&[&frontier]).count(), 0);
+
+    // times strictly less than the frontier are available, once each and in order.
+    frontier.update_iter(vec![(0, -1), (3, 1)]);
+    let times = notificator.available(&[&frontier]).map(|cap| *cap.time()).collect::<Vec<_>>();
+    assert_eq!(times, vec![1, 2]);
+    assert_eq!(notificator.available(&[&frontier]).count(), 0);
+
+    frontier.update_iter(vec![(3, -1)]);
+    let times = notificator.available(&[&frontier]).map(|cap| *cap.time()).collect::<Vec<_>>();
+    assert_eq!(times, vec![3, 4]);
+    assert_eq!(notificator.pending().count(), 0);
+}
+
 /// Tracks requests for notification and delivers available notifications.
 ///
 /// `FrontierNotificator` is meant to manage the delivery of requested notifications in the
@@ -375,6 +442,48 @@ impl<T: Timestamp> FrontierNotificator<T> {
         }
     }
 
+    /// Drains the notifications made available by inspecting the frontiers.
+    ///
+    /// The returned iterator yields a capability for each requested time not `less_equal` to any
+    /// element of `frontiers`, once per time and in non-decreasing order, as `for_each` would.
+    /// Notifications the iterator does not yield remain available, to be delivered by later calls.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, FrontierNotificator};
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .unary_frontier(Pipeline, "example", |_, _| {
+    ///                let mut notificator = FrontierNotificator::default();
+    ///                move |input, output| {
+    ///                    input.for_each(|cap, data| {
+    ///                        output.session(&cap).give_container(data);
+    ///                        notificator.notify_at(cap.retain());
+    ///                    });
+    ///                    let mut ready = notificator.available(&[input.frontier()]).collect::<Vec<_>>();
+    ///                    ready.reverse();
+    ///                    for cap in ready {
+    ///                        println!("done with time: {:?}", cap.time());
+    ///                    }
+    ///                }
+    ///            });
+    /// });
+    /// ```
+    pub fn available<'a>(&'a mut self, frontiers: &'a [&'a MutableAntichain<T>]) -> impl Iterator<Item=Capability<T>> + 'a {
+        self.make_available(frontiers);
+        ::std::iter::from_fn(move || {
+            self.available.pop().map(|front| {
+                while self.available.peek() == Some(&front) {
+                    self.available.pop();
+                }
+                front.element
+            })
+        })
+    }
+
     /// Creates a notificator session in which delivered notification will be non-decreasing.
     ///
     /// This implementation can be emulated with judicious use of `make_available` and `notify_at_frontiered`,
@@ -384,6 +493,12 @@ impl<T: Timestamp> FrontierNotificator<T> {
         Notificator::new(frontiers, self, logging)
     }
 
+    /// Indicates whether a notification is requested at `time` and not yet delivered.
+    fn requested(&self, time: &T) -> bool {
+        self.pending.iter().any(|(cap, _)| cap.time() == time) ||
+        self.available.iter().any(|x| x.element.time() == time)
+    }
+
     /// Iterates over pending capabilities and their count. The count represents how often a
     /// capability has been requested.
     ///
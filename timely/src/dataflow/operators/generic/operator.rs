@@ -1,11 +1,13 @@
 
 //! Methods to construct generic streaming and blocking unary operators.
 
+use std::collections::HashMap;
+
 use crate::dataflow::channels::pushers::Tee;
 use crate::dataflow::channels::pact::ParallelizationContract;
 
 use crate::dataflow::operators::generic::handles::{InputHandleCore, FrontieredInputHandleCore, OutputHandleCore};
-use crate::dataflow::operators::capability::Capability;
+use crate::dataflow::operators::capability::{Capability, CapabilitySet};
 
 use crate::dataflow::{Scope, StreamCore};
 
@@ -97,6 +99,45 @@ pub trait Operator<G: Scope, C1: Container> {
              P: ParallelizationContract<G::Timestamp, C1>>
              (&self, pact: P, name: &str, init: impl IntoIterator<Item=G::Timestamp>, logic: L) -> StreamCore<G, CB::Container>;
 
+    /// Creates a new dataflow operator that partitions its input stream by a parallelization
+    /// strategy `pact`, stashes the received data by time, and invokes `logic`, the function returned
+    /// by the function passed as `constructor`, once for each time when the input frontier passes it.
+    ///
+    /// `logic` receives a capability for the completed time, all data received at that time, and the
+    /// output handle. The capability may be used to send at the time, or retained or delayed to send
+    /// at later times, and `constructor` receives the operator's default capability for the same
+    /// purpose. Times complete in an order consistent with the partial order on times.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::generic::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0u64..10)
+    ///         .to_stream(scope)
+    ///         .delay(|x, _| *x / 3)
+    ///         .unary_frontier_notify(Pipeline, "example", |_default_cap, _info| {
+    ///             let mut total = 0;
+    ///             move |time, data, output| {
+    ///                 total += data.iter().flatten().sum::<u64>();
+    ///                 output.session(&time).give(total);
+    ///             }
+    ///         })
+    ///         .container::<Vec<_>>()
+    ///         .inspect_time(|time, total| println!("total through {:?}: {:?}", time, total));
+    /// });
+    /// ```
+    fn unary_frontier_notify<CB, B, L, P>(&self, pact: P, name: &str, constructor: B) -> StreamCore<G, CB::Container>
+    where
+        CB: ContainerBuilder,
+        B: FnOnce(Capability<G::Timestamp>, OperatorInfo) -> L,
+        L: FnMut(Capability<G::Timestamp>,
+                 Vec<C1>,
+                 &mut OutputHandleCore<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>)+'static,
+        P: ParallelizationContract<G::Timestamp, C1>;
+
     /// Creates a new dataflow operator that partitions its input stream by a parallelization
     /// strategy `pact`, and repeatedly invokes `logic`, the function returned by the function passed as `constructor`.
     /// `logic` can read from the input stream, and write to the output stream.
@@ -363,6 +404,34 @@ impl<G: Scope, C1: Container + Data> Operator<G, C1> for StreamCore<G, C1> {
         })
     }
 
+    fn unary_frontier_notify<CB, B, L, P>(&self, pact: P, name: &str, constructor: B) -> StreamCore<G, CB::Container>
+    where
+        CB: ContainerBuilder,
+        B: FnOnce(Capability<G::Timestamp>, OperatorInfo) -> L,
+        L: FnMut(Capability<G::Timestamp>,
+                 Vec<C1>,
+                 &mut OutputHandleCore<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>)+'static,
+        P: ParallelizationContract<G::Timestamp, C1> {
+
+        self.unary_frontier(pact, name, move |capability, info| {
+            let mut notificator = FrontierNotificator::default();
+            // Received data for each time that has not yet completed.
+            let mut stash = HashMap::<G::Timestamp, Vec<C1>>::new();
+            let mut logic = constructor(capability, info);
+            move |input, output| {
+                input.for_each(|time, data| {
+                    stash.entry(time.time().clone())
+                         .or_insert_with(|| { notificator.notify_at(time.retain()); Vec::new() })
+                         .push(std::mem::take(data));
+                });
+                notificator.for_each(&[input.frontier()], |capability, _| {
+                    let data = stash.remove(capability.time()).unwrap_or_default();
+                    logic(capability, data, output);
+                });
+            }
+        })
+    }
+
     fn unary<CB, B, L, P>(&self, pact: P, name: &str, constructor: B) -> StreamCore<G, CB::Container>
     where
         CB: ContainerBuilder,
@@ -566,6 +635,60 @@ where
     stream
 }
 
+/// Creates a new data stream source for a scope, which holds its capabilities in a `CapabilitySet`.
+///
+/// The source is defined by a name, and a constructor which returns a method that is repeatedly
+/// called with the source's capabilities and an output handle. The capabilities start as a set
+/// containing only the default capability, and the method may insert, downgrade, and remove
+/// capabilities to send data at several incomparable or concurrently open times. Once the set is
+/// empty the source holds no capabilities, and its output frontier becomes empty.
+///
+/// # Examples
+/// ```
+/// use timely::scheduling::Scheduler;
+/// use timely::dataflow::operators::Inspect;
+/// use timely::dataflow::operators::generic::operator::source_set;
+/// use timely::dataflow::Scope;
+///
+/// timely::example(|scope| {
+///
+///     source_set(scope, "Source", |info| {
+///
+///         let activator = scope.activator_for(info.address);
+///
+///         move |capabilities, output| {
+///             // send data at each held time.
+///             for capability in capabilities.iter() {
+///                 output.session(capability).give(*capability.time());
+///             }
+///             // downgrade the capabilities, and release them all after time 20.
+///             if let Some(time) = capabilities.minimum_time().map(|t| t + 1) {
+///                 if time > 20 { capabilities.downgrade(Vec::<u64>::new()); }
+///                 else         { capabilities.downgrade(&[time]); }
+///             }
+///
+///             if !capabilities.is_empty() { activator.activate(); }
+///         }
+///     })
+///     .container::<Vec<_>>()
+///     .inspect_time(|t, x| println!("number: {:?} at {:?}", x, t));
+/// });
+/// ```
+pub fn source_set<G: Scope, CB, B, L>(scope: &G, name: &str, constructor: B) -> StreamCore<G, CB::Container>
+where
+    CB: ContainerBuilder,
+    B: FnOnce(OperatorInfo) -> L,
+    L: FnMut(&mut CapabilitySet<G::Timestamp>, &mut OutputHandleCore<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>)+'static {
+
+    source(scope, name, move |capability, info| {
+        let mut capabilities = CapabilitySet::from_elem(capability);
+        let mut logic = constructor(info);
+        move |output| {
+            logic(&mut capabilities, output);
+        }
+    })
+}
+
 /// Constructs an empty stream.
 ///
 /// This method is useful in patterns where an input is required, but there is no
@@ -591,3 +714,54 @@ pub fn empty<G: Scope, C: Container + Data>(scope: &G) -> StreamCore<G, C> {
         // drop capability, do nothing
     })
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::channels::pact::Pipeline;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::Capability;
+    use crate::dataflow::operators::capture::extract::extract_batches;
+    use super::Operator;
+
+    #[test]
+    fn unary_frontier_notify() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (received, probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream = scope.input_from(&mut input);
+                let received = stream.probe();
+                let stream = stream.unary_frontier_notify(Pipeline, "Test", |_capability, _info| {
+                    // The count of the last completed time, and the capability for that time.
+                    let mut previous: Option<(Capability<u64>, usize)> = None;
+                    move |time, data: Vec<Vec<u64>>, output| {
+                        let count = data.iter().map(|d| d.len()).sum::<usize>();
+                        // Report the previous count at this time, with the capability retained for it.
+                        if let Some((capability, previous)) = previous.take() {
+                            output.session(&capability.delayed(time.time())).give(previous);
+                        }
+                        if *time.time() < 2 { previous = Some((time, count)); }
+                        else { output.session(&time).give(count); }
+                    }
+                });
+                (received, stream.probe(), stream.capture())
+            });
+            for round in 0 .. 3u64 {
+                // Data for each time arrive over several steps, and are presented together.
+                for _ in 0 .. round + 1 {
+                    input.send(round);
+                    worker.step();
+                }
+                input.advance_to(round + 1);
+                worker.step_while(|| received.less_than(input.time()));
+                worker.step();
+                // The retained capability holds back the output until the next time completes.
+                if round < 2 { assert!(probe.less_equal(&round)); }
+            }
+            captured
+        });
+        let messages = extract_batches(captured.try_iter());
+        assert_eq!(messages, vec![(1, vec![1]), (2, vec![2, 3])]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Joins of keyed streams within times.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::ExchangeData;
+use crate::dataflow::{Scope, Stream};
+use crate::dataflow::channels::pact::Exchange;
+use crate::dataflow::operators::FrontierNotificator;
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Extension trait for `Stream`.
+pub trait JoinByKey<S: Scope, K: ExchangeData+Hash+Eq, V1: ExchangeData> {
+    /// Joins two streams of `(key, val)` data, producing the product of the values of each key at each time.
+    ///
+    /// Records of both streams are routed by the result of `hash` applied to their keys, and are
+    /// buffered until their time is complete in both inputs. For each key present in both inputs at
+    /// the time, the operator then produces `(key, (val1, val2))` for each pair of values of the
+    /// key, and releases the records of the time. Keys present in only one input produce nothing.
+    ///
+    /// Records only join with records at the same time. For joins across times, consider the
+    /// `differential-dataflow` crate.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    /// use timely::dataflow::operators::join::JoinByKey;
+    ///
+    /// timely::example(|scope| {
+    ///     let vals = (0..10u64).to_stream(scope).map(|x| (x % 4, x));
+    ///     let names = (0..3u64).to_stream(scope).map(|x| (x, format!("key {}", x)));
+    ///     vals.join_by_key(&names, |key| *key)
+    ///         .inspect(|(key, (val, name))| assert!(val % 4 == *key && name == &format!("key {}", key)));
+    /// });
+    /// ```
+    fn join_by_key<V2, H>(&self, other: &Stream<S, (K, V2)>, hash: H) -> Stream<S, (K, (V1, V2))>
+    where
+        V2: ExchangeData,
+        H: Fn(&K)->u64+'static;
+}
+
+impl<S: Scope, K: ExchangeData+Hash+Eq, V1: ExchangeData> JoinByKey<S, K, V1> for Stream<S, (K, V1)> {
+    fn join_by_key<V2, H>(&self, other: &Stream<S, (K, V2)>, hash: H) -> Stream<S, (K, (V1, V2))>
+    where
+        V2: ExchangeData,
+        H: Fn(&K)->u64+'static,
+    {
+        let hash1 = Rc::new(hash);
+        let hash2 = Rc::clone(&hash1);
+        let pact1 = Exchange::new(move |(k, _): &(K, V1)| hash1(k));
+        let pact2 = Exchange::new(move |(k, _): &(K, V2)| hash2(k));
+
+        self.binary_frontier(other, pact1, pact2, "JoinByKey", |_capability, _info| {
+
+            // records of each input by time and key.
+            let mut stash = HashMap::<_, (HashMap<_, Vec<_>>, HashMap<_, Vec<_>>)>::new();
+            let mut notificator = FrontierNotificator::default();
+
+            move |input1, input2, output| {
+
+                input1.for_each(|time, data| {
+                    let (stash1, _) = stash.entry(time.time().clone()).or_default();
+                    for (key, val) in data.drain(..) {
+                        stash1.entry(key).or_default().push(val);
+                    }
+                    notificator.notify_at(time.retain());
+                });
+
+                input2.for_each(|time, data| {
+                    let (_, stash2) = stash.entry(time.time().clone()).or_default();
+                    for (key, val) in data.drain(..) {
+                        stash2.entry(key).or_default().push(val);
+                    }
+                    notificator.notify_at(time.retain());
+                });
+
+                notificator.for_each(&[input1.frontier(), input2.frontier()], |time, _| {
+                    if let Some((stash1, mut stash2)) = stash.remove(time.time()) {
+                        let mut session = output.session(&time);
+                        for (key, vals1) in stash1 {
+                            if let Some(vals2) = stash2.remove(&key) {
+                                for val1 in vals1.iter() {
+                                    for val2 in vals2.iter() {
+                                        session.give((key.clone(), (val1.clone(), val2.clone())));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::capture::Extract;
+    use super::JoinByKey;
+
+    #[test]
+    fn join_by_key() {
+        let captured = crate::execute::execute_directly(|worker| {
+            let mut input1 = InputHandle::new();
+            let mut input2 = InputHandle::new();
+            let (probe, captured) = worker.dataflow::<u64,_,_>(|scope| {
+                let stream1 = scope.input_from(&mut input1);
+                let stream2 = scope.input_from(&mut input2);
+                let joined = stream1.join_by_key(&stream2, |key: &char| *key as u64);
+                (joined.probe(), joined.capture())
+            });
+            // Keys join within times, and `c` is present in only one input at each time.
+            input1.send_batch(&mut vec![('a', 1), ('a', 2), ('b', 3), ('c', 4)]);
+            input2.send_batch(&mut vec![('a', 10), ('b', 20), ('b', 30)]);
+            input1.advance_to(1);
+            input2.advance_to(1);
+            input1.send_batch(&mut vec![('a', 5)]);
+            input2.send_batch(&mut vec![('a', 50), ('c', 60)]);
+            input1.advance_to(2);
+            input2.advance_to(2);
+            worker.step_while(|| probe.less_than(input1.time()));
+            captured
+        });
+
+        let mut extracted = captured.extract();
+        for (_, data) in extracted.iter_mut() { data.sort(); }
+        assert_eq!(extracted, vec![
+            (0, vec![('a', (1, 10)), ('a', (2, 10)), ('b', (3, 20)), ('b', (3, 30))]),
+            (1, vec![('a', (5, 50))]),
+        ]);
+    }
+}
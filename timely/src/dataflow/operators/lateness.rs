@@ -0,0 +1,150 @@
+//! Operators that measure the lateness of records relative to a watermark.
+
+use std::collections::BTreeMap;
+use std::ops::Sub;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use crate::dataflow::{Scope, ScopeParent, Stream};
+use crate::order::TotalOrder;
+use crate::progress::Timestamp;
+
+/// Lateness statistics for the records at one time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LatenessStats<T> {
+    /// The number of records whose event time was behind the watermark on arrival.
+    pub late_count: usize,
+    /// The largest amount by which a record's event time was behind the watermark on arrival.
+    pub max_lateness: T,
+}
+
+/// A stream of lateness statistics.
+pub type LatenessStream<S> = Stream<S, LatenessStats<<S as ScopeParent>::Timestamp>>;
+
+/// Extension trait for `Stream`.
+pub trait WatermarkLateness<S: Scope, D: Data> {
+    /// Passes records through, and reports per-time statistics about records arriving late.
+    ///
+    /// The watermark is the operator's input frontier, which reflects the progress of the
+    /// computation upstream. A record is late if its event time, as determined by `event_time`,
+    /// is less than the watermark when the operator receives the record, and its lateness is
+    /// the difference between the two. If the input frontier is empty when a record arrives,
+    /// the watermark is the record's time.
+    ///
+    /// The first returned stream contains all records, unchanged. The second contains one
+    /// `LatenessStats` for each time at which records were received, produced once the input
+    /// frontier has passed the time.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::lateness::WatermarkLateness;
+    ///
+    /// timely::example(|scope| {
+    ///     let (data, stats) = (0..10u64)
+    ///         .to_stream(scope)
+    ///         .delay(|x, _| *x)
+    ///         .watermark_lateness(|x| x.saturating_sub(1));
+    ///
+    ///     data.inspect(|x| println!("data: {:?}", x));
+    ///     stats.inspect(|x| println!("stats: {:?}", x));
+    /// });
+    /// ```
+    fn watermark_lateness<F>(&self, event_time: F) -> (Stream<S, D>, LatenessStream<S>)
+    where
+        F: Fn(&D)->S::Timestamp+'static;
+}
+
+impl<S, D> WatermarkLateness<S, D> for Stream<S, D>
+where
+    S: Scope,
+    S::Timestamp: TotalOrder+Sub<Output=S::Timestamp>,
+    D: Data,
+{
+    fn watermark_lateness<F>(&self, event_time: F) -> (Stream<S, D>, LatenessStream<S>)
+    where
+        F: Fn(&D)->S::Timestamp+'static,
+    {
+        let mut builder = OperatorBuilder::new("WatermarkLateness".to_owned(), self.scope());
+
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut data_output, data_stream) = builder.new_output();
+        let (mut stats_output, stats_stream) = builder.new_output();
+
+        builder.build(move |_| {
+
+            let mut pending = BTreeMap::new();
+
+            move |frontiers| {
+                let mut data_handle = data_output.activate();
+                let mut stats_handle = stats_output.activate();
+
+                let frontier = &frontiers[0];
+                input.for_each(|time, data| {
+                    let watermark = frontier.frontier().first().cloned().unwrap_or_else(|| time.time().clone());
+                    let (_, stats) = pending
+                        .entry(time.time().clone())
+                        .or_insert_with(|| (time.delayed_for_output(time.time(), 1), LatenessStats { late_count: 0, max_lateness: S::Timestamp::minimum() }));
+                    for datum in data.iter() {
+                        let event = event_time(datum);
+                        if event < watermark {
+                            let lateness = watermark.clone() - event;
+                            stats.late_count += 1;
+                            if stats.max_lateness < lateness {
+                                stats.max_lateness = lateness;
+                            }
+                        }
+                    }
+                    data_handle.session(&time).give_container(data);
+                });
+
+                // Report statistics for times the input frontier has passed.
+                while pending.first_key_value().is_some_and(|(time, _)| !frontier.less_equal(time)) {
+                    let (_, (capability, stats)) = pending.pop_first().unwrap();
+                    stats_handle.session(&capability).give(stats);
+                }
+            }
+        });
+
+        (data_stream, stats_stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::InputHandle;
+    use crate::dataflow::operators::{Input, Probe, Capture};
+    use crate::dataflow::operators::capture::Extract;
+    use super::{LatenessStats, WatermarkLateness};
+
+    #[test]
+    fn watermark_lateness() {
+        let (data, stats) = crate::execute::execute_directly(|worker| {
+            let mut input = InputHandle::new();
+            let (probe, data, stats) = worker.dataflow::<u64,_,_>(|scope| {
+                let (data, stats) = scope.input_from(&mut input).watermark_lateness(|x: &u64| *x);
+                (stats.probe(), data.capture(), stats.capture())
+            });
+            // Records carry their event time; those less than the time are late.
+            for (round, records) in [vec![0, 0], vec![1, 0], vec![], vec![3, 1, 2, 0]].into_iter().enumerate() {
+                for record in records {
+                    input.send(record);
+                }
+                input.advance_to(round as u64 + 1);
+                worker.step_while(|| probe.less_than(input.time()));
+            }
+            (data, stats)
+        });
+
+        assert_eq!(data.extract(), vec![(0, vec![0, 0]), (1, vec![0, 1]), (3, vec![0, 1, 2, 3])]);
+        assert_eq!(stats.extract(), vec![
+            (0, vec![LatenessStats { late_count: 0, max_lateness: 0 }]),
+            (1, vec![LatenessStats { late_count: 1, max_lateness: 1 }]),
+            (3, vec![LatenessStats { late_count: 3, max_lateness: 3 }]),
+        ]);
+    }
+}
@@ -20,9 +20,25 @@ pub trait Map<S: Scope, D: Data> {
     ///            .inspect(|x| println!("seen: {:?}", x));
     /// });
     /// ```
-    fn map<D2: Data, L: FnMut(D)->D2+'static>(&self, mut logic: L) -> Stream<S, D2> {
-        self.flat_map(move |x| std::iter::once(logic(x)))
+    fn map<D2: Data, L: FnMut(D)->D2+'static>(&self, logic: L) -> Stream<S, D2> {
+        self.map_named("FlatMap", logic)
     }
+    /// Consumes each element of the stream and yields a new element, in an operator named `name`.
+    ///
+    /// The name identifies the operator in logged events, which helps to tell apart the
+    /// operators of a dataflow when profiling it.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .map_named("AddOne", |x| x + 1)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn map_named<D2: Data, L: FnMut(D)->D2+'static>(&self, name: &str, logic: L) -> Stream<S, D2>;
     /// Updates each element of the stream and yields the element, re-using memory where possible.
     ///
     /// # Examples
@@ -52,6 +68,13 @@ pub trait Map<S: Scope, D: Data> {
 }
 
 impl<S: Scope, D: Data> Map<S, D> for Stream<S, D> {
+    fn map_named<D2: Data, L: FnMut(D)->D2+'static>(&self, name: &str, mut logic: L) -> Stream<S, D2> {
+        self.unary(Pipeline, name, move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                output.session(&time).give_iterator(data.drain(..).map(&mut logic));
+            })
+        })
+    }
     fn map_in_place<L: FnMut(&mut D)+'static>(&self, mut logic: L) -> Stream<S, D> {
         self.unary(Pipeline, "MapInPlace", move |_,_| move |input, output| {
             input.for_each(|time, data| {
@@ -67,3 +90,34 @@ impl<S: Scope, D: Data> Map<S, D> for Stream<S, D> {
         MapCore::flat_map(self, logic)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::logging::{TimelyEvent, capture_timely_events};
+    use crate::dataflow::operators::{ToStream, Map, Filter, Inspect};
+
+    #[test]
+    fn named_operators() {
+        let events = crate::execute::execute_directly(|worker| {
+            let events = capture_timely_events(worker);
+            worker.dataflow::<u64,_,_>(|scope| {
+                (0..10u64).to_stream(scope)
+                          .map(|x| x + 1)
+                          .map_named("AddOne", |x| x + 1)
+                          .filter_named("Evens", |x| x % 2 == 0)
+                          .inspect_named("Print", |x| println!("seen: {:?}", x));
+            });
+            events
+        });
+
+        let events = events.lock().unwrap();
+        let names = events.iter().filter_map(|event| match event {
+            TimelyEvent::Operates(operates) => Some(operates.name.clone()),
+            _ => None,
+        }).collect::<Vec<_>>();
+        for name in ["FlatMap", "AddOne", "Evens", "Print"] {
+            assert!(names.iter().any(|n| n == name), "{} missing from {:?}", name, names);
+        }
+    }
+}
@@ -10,14 +10,17 @@
 
 pub use self::input::Input;
 pub use self::unordered_input::UnorderedInput;
-pub use self::partition::Partition;
+pub use self::partition::{Partition, PartitionBy};
 pub use self::map::Map;
 pub use self::inspect::{Inspect, InspectCore};
 pub use self::filter::Filter;
+pub use self::distinct::Distinct;
 pub use self::delay::Delay;
 pub use self::exchange::Exchange;
-pub use self::broadcast::Broadcast;
+pub use self::broadcast::{Broadcast, Replicate};
 pub use self::branch::{Branch, BranchWhen};
+pub use self::lateness::WatermarkLateness;
+pub use self::join::JoinByKey;
 pub use self::result::ResultStream;
 pub use self::to_stream::ToStream;
 
@@ -35,11 +38,12 @@ pub mod input;
 pub mod flow_controlled;
 pub mod unordered_input;
 pub use self::core::feedback::{self, Feedback, LoopVariable, ConnectLoop};
-pub use self::core::concat::{self, Concat, Concatenate};
+pub use self::core::concat::{self, Concat, Concatenate, MergeByTime};
 pub mod partition;
 pub mod map;
 pub use self::core::inspect;
 pub mod filter;
+pub mod distinct;
 pub mod delay;
 pub use self::core::exchange;
 pub mod broadcast;
@@ -47,6 +51,8 @@ pub use self::core::probe::{self, Probe};
 pub mod to_stream;
 pub use self::core::capture::{self, Capture};
 pub mod branch;
+pub mod lateness;
+pub mod join;
 pub use self::core::ok_err::{self, OkErr};
 pub use self::core::rc;
 pub mod result;
@@ -30,3 +30,69 @@ impl<G: Scope, D: Data, D2: Data, F: Fn(D)->(u64, D2)+'static> Partition<G, D, D
         PartitionCore::partition::<CapacityContainerBuilder<_>, _, _>(self, parts, route)
     }
 }
+
+/// Partition a stream of records into multiple streams by an index.
+pub trait PartitionBy<G: Scope, D: Data> {
+    /// Produces `parts` output streams, with each record sent to the stream indicated by `route`.
+    ///
+    /// The returned vector contains exactly `parts` streams. The operator panics if `route`
+    /// produces an index that is not less than `parts`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, PartitionBy, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     let streams = (0..10).to_stream(scope)
+    ///                          .partition_by(3, |x| x % 3);
+    ///
+    ///     for (index, stream) in streams.into_iter().enumerate() {
+    ///         stream.inspect(move |x| println!("seen {}: {:?}", index, x));
+    ///     }
+    /// });
+    /// ```
+    fn partition_by<F: Fn(&D)->usize+'static>(&self, parts: usize, route: F) -> Vec<Stream<G, D>>;
+}
+
+impl<G: Scope, D: Data> PartitionBy<G, D> for Stream<G, D> {
+    fn partition_by<F: Fn(&D)->usize+'static>(&self, parts: usize, route: F) -> Vec<Stream<G, D>> {
+        PartitionCore::partition::<CapacityContainerBuilder<_>, _, _>(self, parts as u64, move |datum| {
+            let part = route(&datum);
+            assert!(part < parts, "partition_by: route produced index {} for {} parts", part, parts);
+            (part as u64, datum)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::dataflow::operators::{ToStream, Capture};
+    use crate::dataflow::operators::capture::Extract;
+    use super::PartitionBy;
+
+    #[test]
+    fn partition_by() {
+        let captures = crate::example(|scope| {
+            (0..10).to_stream(scope)
+                   .partition_by(3, |x| x % 3)
+                   .iter()
+                   .map(|stream| stream.capture())
+                   .collect::<Vec<_>>()
+        });
+        let parts = captures.into_iter().map(|capture| capture.extract()).collect::<Vec<_>>();
+        assert_eq!(parts, vec![
+            vec![(0, vec![0, 3, 6, 9])],
+            vec![(0, vec![1, 4, 7])],
+            vec![(0, vec![2, 5, 8])],
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "route produced index 3 for 3 parts")]
+    fn partition_by_out_of_range() {
+        crate::example(|scope| {
+            (0..10).to_stream(scope).partition_by(3, |x| x % 4);
+        });
+    }
+}
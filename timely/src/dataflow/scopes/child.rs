@@ -1,9 +1,11 @@
 //! A child dataflow scope, used to build nested dataflow scopes.
 
+use std::any::Any;
 use std::rc::Rc;
 use std::cell::RefCell;
 
 use crate::communication::{Exchangeable, Push, Pull};
+use crate::communication::allocator::Endpoints;
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
 use crate::scheduling::Scheduler;
 use crate::scheduling::activate::Activations;
@@ -61,9 +63,15 @@ where
     fn allocate<D: Exchangeable>(&mut self, identifier: usize, address: Rc<[usize]>) -> (Vec<Box<dyn Push<D>>>, Box<dyn Pull<D>>) {
         self.parent.allocate(identifier, address)
     }
+    fn allocate_local<D: Send+Any>(&mut self, identifier: usize, address: Rc<[usize]>) -> Result<Endpoints<D>, String> {
+        self.parent.allocate_local(identifier, address)
+    }
     fn pipeline<D: 'static>(&mut self, identifier: usize, address: Rc<[usize]>) -> (ThreadPusher<D>, ThreadPuller<D>) {
         self.parent.pipeline(identifier, address)
     }
+    fn prioritize(&mut self, identifier: usize) {
+        self.parent.prioritize(identifier)
+    }
     fn new_identifier(&mut self) -> usize {
         self.parent.new_identifier()
     }
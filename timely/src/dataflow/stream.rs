@@ -64,6 +64,18 @@ impl<S: Scope, C: Container> StreamCore<S, C> {
 
         self.scope.add_edge(self.name, target);
         self.ports.add_pusher(pusher);
+
+        if let Some(limit) = self.scope.config().tee_consumer_limit {
+            let consumers = self.ports.consumers();
+            if consumers > limit {
+                let mut path = self.scope.addr().to_vec();
+                path.push(self.name.node);
+                panic!(
+                    "output {} of the operator at path {:?} has {} consumers, more than the limit of {}; each consumer after the first receives a copy of the data",
+                    self.name.port, path, consumers, limit,
+                );
+            }
+        }
     }
     /// Allocates a `Stream` from a supplied `Source` name and rendezvous point.
     pub fn new(source: Source, output: TeeHelper<S::Timestamp, C>, scope: S) -> Self {
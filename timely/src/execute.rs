@@ -1,9 +1,23 @@
 //! Starts a timely dataflow execution from configuration information and per-worker logic.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
 use crate::communication::{initialize_from, Allocator, allocator::AllocateBuilder, WorkerGuards};
+use crate::dataflow::Stream;
+use crate::dataflow::operators::Capture;
+use crate::dataflow::operators::capture::Extract;
 use crate::dataflow::scopes::Child;
+use crate::logging::TimelyEventBuilder;
+use crate::progress::timestamp::Refines;
 use crate::worker::Worker;
-use crate::{CommunicationConfig, WorkerConfig};
+use crate::{CommunicationConfig, Data, WorkerConfig};
 
 /// Configures the execution of a timely dataflow computation.
 #[derive(Clone, Debug)]
@@ -228,6 +242,41 @@ where
     execute_from(allocators, other, config.worker, func)
 }
 
+/// Executes a timely dataflow from a configuration and per-communicator logic, and collects the results.
+///
+/// This method calls [`execute`](execute()), and then blocks until all local workers complete,
+/// returning the results of their closures in order of worker index. An error is returned if the
+/// computation could not be initialized, or if any worker panicked, in which case the error names
+/// the first such worker and the panic, as reported by [`WorkerGuards::join`].
+///
+/// # Examples
+/// ```rust
+/// use timely::dataflow::operators::{ToStream, Inspect};
+///
+/// // execute a timely dataflow using three worker threads, and collect their indices.
+/// let indices = timely::execute_collect(timely::Config::process(3), |worker| {
+///     worker.dataflow::<(),_,_>(|scope| {
+///         (0..10).to_stream(scope)
+///                .inspect(|x| println!("seen: {:?}", x));
+///     });
+///     worker.index()
+/// }).unwrap();
+///
+/// assert_eq!(indices, vec![0, 1, 2]);
+/// ```
+pub fn execute_collect<T, F>(config: Config, func: F) -> Result<Vec<T>,String>
+where
+    T:Send+'static,
+    F: Fn(&mut Worker<Allocator>)->T+Send+Sync+'static,
+{
+    execute(config, func)?
+        .join()
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| result.map_err(|error| format!("worker {} panicked: {}", index, error)))
+        .collect()
+}
+
 /// Executes a timely dataflow from supplied arguments and per-communicator logic.
 ///
 /// The `execute` method takes arguments (typically `std::env::args()`) and spins up some number of
@@ -252,7 +301,7 @@ where
 ///
 /// `-h, --hostfile`: a text file whose lines are "hostname:port" in order of process identity.
 /// If not specified, `localhost` will be used, with port numbers increasing from 2101 (chosen
-/// arbitrarily).
+/// arbitrarily). Lines may instead all be "unix:path", to connect through Unix domain sockets.
 ///
 /// This method is only available if the `getopts` feature is enabled, which
 /// it is by default.
@@ -322,9 +371,247 @@ where
     initialize_from(builders, others, move |allocator| {
         let mut worker = Worker::new(worker_config.clone(), allocator);
         let result = func(&mut worker);
-        while worker.has_dataflows() {
+        while worker.has_dataflows() || worker.backlog() > 0 {
             worker.step_or_park(None);
         }
         result
     })
 }
+
+/// Executes a timely dataflow, writing each worker's "timely" log stream to a file.
+///
+/// This behaves as [`execute`](execute()), but first registers the "timely" log stream on each
+/// worker, which reports operator and channel construction, scheduling, message, and progress
+/// propagation events. Other log streams are not recorded: neither the "timely/progress" and
+/// "timely/summary" streams, whose events depend on the timestamp types of dataflows, nor the
+/// "communication" stream. Worker `i` writes its events to the file named by `path` with `.i`
+/// appended, as a sequence of bincode-serialized `Vec<(Duration, TimelyEvent)>` batches, which
+/// can be read back with `bincode::deserialize_from` until it fails at the end of the file. The
+/// logger is removed and the file flushed once the worker's dataflows have completed.
+///
+/// The files of the workers of this process are created before the workers start, and an error
+/// is returned if any cannot be. Each worker returns the result of `func` together with the
+/// outcome of writing its file: a worker that fails to write stops logging, while its dataflows
+/// continue to run, and returns the error once they complete.
+///
+/// # Examples
+/// ```rust,no_run
+/// use timely::dataflow::operators::{ToStream, Inspect};
+///
+/// // writes "timely.log.0" and "timely.log.1".
+/// let guards = timely::execute::execute_with_timely_logging_to_file(timely::Config::process(2), "timely.log", |worker| {
+///     worker.dataflow::<(),_,_>(|scope| {
+///         (0..10).to_stream(scope)
+///                .inspect(|x| println!("seen: {:?}", x));
+///     })
+/// }).unwrap();
+///
+/// for result in guards.join() {
+///     let ((), logged) = result.unwrap();
+///     logged.unwrap();
+/// }
+/// ```
+pub fn execute_with_timely_logging_to_file<T, P, F>(config: Config, path: P, func: F) -> Result<WorkerGuards<Logged<T>>,String>
+where
+    T: Send+'static,
+    P: AsRef<Path>,
+    F: Fn(&mut Worker<Allocator>)->T+Send+Sync+'static,
+{
+    let path = path.as_ref();
+    let mut files = HashMap::new();
+    for index in local_workers(&config.communication) {
+        let file = worker_log_path(path, index);
+        let writer = File::create(&file).map_err(|error| format!("failed to create log file {}: {}", file.display(), error))?;
+        files.insert(index, (file, writer));
+    }
+    let files = Mutex::new(files);
+
+    execute(config, move |worker| {
+        let (file, writer) = files.lock().expect("log files poisoned").remove(&worker.index()).expect("log file missing");
+        let status = Rc::new(RefCell::new(Ok(())));
+        let mut writer = Some(BufWriter::new(writer));
+        let logger_status = Rc::clone(&status);
+        worker.log_register().insert::<TimelyEventBuilder,_>("timely", move |_time, data| {
+            // Once writing fails, the remaining events are discarded.
+            let result = match (writer.as_mut(), data) {
+                (Some(writer), Some(data)) => ::bincode::serialize_into(writer, &*data).map_err(|e| e.to_string()),
+                (Some(writer), None) => writer.flush().map_err(|e| e.to_string()),
+                (None, _) => Ok(()),
+            };
+            if let Err(error) = result {
+                *logger_status.borrow_mut() = Err(format!("failed to write log file {}: {}", file.display(), error));
+                writer = None;
+            }
+        });
+        let result = func(worker);
+        while worker.has_dataflows() || worker.backlog() > 0 {
+            worker.step_or_park(None);
+        }
+        worker.log_register().remove("timely");
+        let status = status.replace(Ok(()));
+        (result, status)
+    })
+}
+
+/// The result of a worker of [`execute_with_timely_logging_to_file`], together with the outcome of
+/// writing its log file.
+pub type Logged<T> = (T, Result<(), String>);
+
+/// The indices of the workers that `config` runs in this process.
+fn local_workers(config: &CommunicationConfig) -> std::ops::Range<usize> {
+    match config {
+        CommunicationConfig::Thread => 0 .. 1,
+        CommunicationConfig::Process(threads) |
+        CommunicationConfig::ProcessBinary(threads) |
+        CommunicationConfig::ProcessBounded { threads, .. } => 0 .. *threads,
+        CommunicationConfig::Cluster { threads, process, .. } |
+        CommunicationConfig::ClusterWithOptions { threads, process, .. } => process * threads .. (process + 1) * threads,
+    }
+}
+
+/// The log file of the worker with index `index`, for logs written to `path`.
+fn worker_log_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Executes a dataflow twice and asserts that both executions produce the same output.
+///
+/// The `build` closure is called on each of `workers` worker threads, in each of two separate
+/// executions, and should construct a dataflow including its input, returning the stream whose
+/// contents should be compared. The outputs of all workers are captured, and for each timestamp
+/// the records are sorted before comparison, so that the order in which records are produced
+/// within a timestamp, or the worker that produced them, does not matter.
+///
+/// This is a testing utility, meant to catch accidental nondeterminism, for example that due to
+/// the iteration order of a `HashMap` or to unseeded randomness. It panics if the two executions
+/// produce different outputs, or if either execution fails.
+///
+/// # Examples
+/// ```rust
+/// use timely::dataflow::operators::{ToStream, Map, Exchange};
+///
+/// timely::execute::run_twice_and_compare::<u64,_,_>(2, |scope| {
+///     (0..10u64).to_stream(scope)
+///               .exchange(|x| *x)
+///               .map(|x| x + 1)
+/// });
+/// ```
+pub fn run_twice_and_compare<T, D, F>(workers: usize, build: F)
+where
+    T: Refines<()> + Ord + Send,
+    D: Data + Ord + Send + Debug,
+    F: for<'a> Fn(&mut Child<'a, Worker<Allocator>, T>)->Stream<Child<'a, Worker<Allocator>, T>, D>+Send+Sync+'static,
+{
+    let build = Arc::new(build);
+    let first = run_and_extract(workers, build.clone());
+    let second = run_and_extract(workers, build);
+    assert_eq!(first, second, "dataflow produced different outputs across executions");
+}
+
+/// Executes `build` on `workers` workers and extracts the captured output.
+fn run_and_extract<T, D, F>(workers: usize, build: Arc<F>) -> Vec<(T, Vec<D>)>
+where
+    T: Refines<()> + Ord + Send,
+    D: Data + Ord + Send,
+    F: for<'a> Fn(&mut Child<'a, Worker<Allocator>, T>)->Stream<Child<'a, Worker<Allocator>, T>, D>+Send+Sync+'static,
+{
+    let (send, recv) = ::std::sync::mpsc::channel();
+    let send = Arc::new(Mutex::new(send));
+    let guards = execute(Config::process(workers), move |worker| {
+        let send = send.lock().unwrap().clone();
+        worker.dataflow::<T,_,_>(|scope| build(scope).capture_into(send));
+    }).expect("failed to start computation");
+    for result in guards.join() {
+        result.expect("worker panicked");
+    }
+    recv.extract()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use crate::logging::TimelyEvent;
+    use crate::dataflow::channels::pact::Pipeline;
+    use crate::dataflow::operators::{ToStream, Map, Operator};
+    use super::{run_twice_and_compare, execute_with_timely_logging_to_file, worker_log_path, Config};
+
+    #[test]
+    fn deterministic() {
+        run_twice_and_compare::<u64,_,_>(3, |scope| {
+            (0..100u64).to_stream(scope)
+                       .map(|x| (x % 7, x))
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "different outputs")]
+    fn nondeterministic() {
+        run_twice_and_compare::<u64,_,_>(1, |scope| {
+            (0..100u64).to_stream(scope)
+                       .unary(Pipeline, "HashMapOrder", |_, _| move |input, output| {
+                           input.for_each(|time, data| {
+                               let counts: HashMap<u64, u64> = data.drain(..).map(|x| (x, 1)).collect();
+                               // The position of each key reveals `HashMap` iteration order.
+                               output.session(&time).give_iterator(counts.into_keys().enumerate());
+                           });
+                       })
+        });
+    }
+
+    #[test]
+    fn execute_collect() {
+        let results = super::execute_collect(Config::process(2), |worker| worker.index() * 10).unwrap();
+        assert_eq!(results, vec![0, 10]);
+
+        let error = super::execute_collect(Config::process(2), |worker| {
+            if worker.index() == 1 { panic!("worker failure"); }
+        }).unwrap_err();
+        assert!(error.starts_with("worker 1 panicked"));
+    }
+
+    #[test]
+    fn logging_to_file() {
+        let path = std::env::temp_dir().join(format!("timely-execute-log-{}", std::process::id()));
+        let guards = execute_with_timely_logging_to_file(Config::process(2), &path, |worker| {
+            worker.dataflow::<u64,_,_>(|scope| {
+                (0..100u64).to_stream(scope)
+                           .map(|x| x + 1);
+            });
+        }).unwrap();
+        for result in guards.join() {
+            let ((), logged) = result.unwrap();
+            logged.unwrap();
+        }
+
+        for index in 0 .. 2 {
+            let file = worker_log_path(&path, index);
+            let bytes = std::fs::read(&file).unwrap();
+            std::fs::remove_file(&file).unwrap();
+            assert!(!bytes.is_empty());
+
+            let mut reader = &bytes[..];
+            let mut events = Vec::new();
+            while !reader.is_empty() {
+                let batch: Vec<(Duration, TimelyEvent)> = ::bincode::deserialize_from(&mut reader).unwrap();
+                events.extend(batch.into_iter().map(|(_, event)| event));
+            }
+            assert!(events.iter().any(|event| matches!(event, TimelyEvent::Operates(_))));
+            assert!(events.iter().any(|event| matches!(event, TimelyEvent::Schedule(_))));
+        }
+    }
+
+    #[test]
+    fn logging_to_missing_directory() {
+        let path = std::env::temp_dir().join(format!("timely-execute-missing-{}", std::process::id())).join("log");
+        // The workers do not start if their log files cannot be created.
+        let error = execute_with_timely_logging_to_file(Config::process(2), &path, |_worker| {
+            panic!("worker started without a log file");
+        }).err().unwrap();
+        assert!(error.starts_with("failed to create log file"));
+        assert!(!worker_log_path(&path, 0).exists());
+    }
+}
@@ -15,6 +15,8 @@ pub type TimelySummaryEventBuilder<TS> = CapacityContainerBuilder<Vec<(Duration,
 /// Logger for timely dataflow operator summary events (the "timely/summary/*" log streams).
 pub type TimelySummaryLogger<TS> = crate::logging_core::Logger<TimelySummaryEventBuilder<TS>>;
 
+#[cfg(feature = "json-logging")]
+use std::io::Write;
 use std::time::Duration;
 use columnar::Columnar;
 use serde::{Deserialize, Serialize};
@@ -58,6 +60,76 @@ impl<P, C> Drop for BatchLogger<P, C> where P: EventPusher<Duration, C> {
     }
 }
 
+/// Writes logged events to a writer as newline-delimited JSON.
+///
+/// Each event is written as a JSON object with fields `time` and `event`, on a line of its own.
+/// The writer is flushed when the logger is flushed, and writers that are not buffered should be
+/// wrapped in a [`std::io::BufWriter`].
+///
+/// This type is only available if the `json-logging` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use timely::logging::{JsonLogger, TimelyEventBuilder};
+///
+/// // Installed as the action of a log stream.
+/// timely::execute_directly(|worker| {
+///     let mut logger = JsonLogger::new(std::io::sink());
+///     worker.log_register().insert::<TimelyEventBuilder, _>("timely", move |time, data| {
+///         logger.publish_batch(time, data).expect("failed to write events");
+///     });
+/// });
+///
+/// // Flushing does not write an empty line.
+/// let mut logger = JsonLogger::new(Vec::new());
+/// let time = Duration::from_secs(1);
+/// logger.publish_batch(&time, &mut Some(vec![(time, "a"), (time, "b")])).unwrap();
+/// logger.publish_batch(&time, &mut None::<Vec<(Duration, &str)>>).unwrap();
+/// let expected = "{\"time\":{\"secs\":1,\"nanos\":0},\"event\":\"a\"}\n{\"time\":{\"secs\":1,\"nanos\":0},\"event\":\"b\"}\n";
+/// assert_eq!(String::from_utf8(logger.into_inner()).unwrap(), expected);
+/// ```
+#[cfg(feature = "json-logging")]
+#[derive(Debug)]
+pub struct JsonLogger<W: Write> {
+    writer: W,
+}
+
+#[cfg(feature = "json-logging")]
+impl<W: Write> JsonLogger<W> {
+    /// Creates a new logger writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        JsonLogger { writer }
+    }
+    /// Writes a batch of logged events, or flushes the writer for `None`.
+    ///
+    /// Returns the first error from serializing or writing an event, or from flushing.
+    pub fn publish_batch<T: Serialize>(&mut self, _time: &Duration, data: &mut Option<Vec<(Duration, T)>>) -> std::io::Result<()> {
+        if let Some(data) = data {
+            for (time, event) in data.drain(..) {
+                serde_json::to_writer(&mut self.writer, &JsonEvent { time, event })?;
+                self.writer.write_all(b"\n")?;
+            }
+            Ok(())
+        }
+        else {
+            self.writer.flush()
+        }
+    }
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// A logged event, as written by [`JsonLogger`].
+#[cfg(feature = "json-logging")]
+#[derive(Serialize)]
+struct JsonEvent<T> {
+    time: Duration,
+    event: T,
+}
+
 #[derive(Serialize, Deserialize, Columnar, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 /// The creation of an `Operate` implementor.
 pub struct OperatesEvent {
@@ -318,3 +390,18 @@ impl From<InputEvent> for TimelyEvent {
 impl From<ParkEvent> for TimelyEvent {
     fn from(v: ParkEvent) -> TimelyEvent { TimelyEvent::Park(v) }
 }
+
+/// Registers a "timely" logger with `worker` that captures the events it logs.
+///
+/// The events are shared rather than returned, as they are logged while the worker runs.
+#[cfg(test)]
+pub(crate) fn capture_timely_events<W: crate::worker::AsWorker>(worker: &W) -> std::sync::Arc<std::sync::Mutex<Vec<TimelyEvent>>> {
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let captured = std::sync::Arc::clone(&events);
+    worker.log_register().insert::<TimelyEventBuilder,_>("timely", move |_time, data| {
+        if let Some(data) = data {
+            captured.lock().unwrap().extend(data.iter().map(|(_, event)| event.clone()));
+        }
+    });
+    events
+}
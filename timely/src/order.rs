@@ -91,6 +91,48 @@ mod product {
                 inner,
             }
         }
+
+        /// Returns the product with `by` added to the outer coordinate.
+        ///
+        /// The result is greater or equal to `self` when `by` is not negative. Overflow behaves as
+        /// addition of the coordinate type does, which for integers panics in debug builds.
+        ///
+        /// # Examples
+        /// ```
+        /// use timely::order::{PartialOrder, Product};
+        ///
+        /// let time = Product::new(3u64, 5u32);
+        /// assert_eq!(time.advance_outer(2), Product::new(5, 5));
+        /// assert!(time.less_equal(&time.advance_outer(2)));
+        /// ```
+        pub fn advance_outer<B>(&self, by: B) -> Self
+        where
+            TOuter: Clone + std::ops::Add<B, Output = TOuter>,
+            TInner: Clone,
+        {
+            Product::new(self.outer.clone() + by, self.inner.clone())
+        }
+
+        /// Returns the product with `by` added to the inner coordinate.
+        ///
+        /// The result is greater or equal to `self` when `by` is not negative. Overflow behaves as
+        /// addition of the coordinate type does, which for integers panics in debug builds.
+        ///
+        /// # Examples
+        /// ```
+        /// use timely::order::{PartialOrder, Product};
+        ///
+        /// let time = Product::new(3u64, 5u32);
+        /// assert_eq!(time.advance_inner(2), Product::new(3, 7));
+        /// assert!(time.less_equal(&time.advance_inner(2)));
+        /// ```
+        pub fn advance_inner<B>(&self, by: B) -> Self
+        where
+            TOuter: Clone,
+            TInner: Clone + std::ops::Add<B, Output = TInner>,
+        {
+            Product::new(self.outer.clone(), self.inner.clone() + by)
+        }
     }
 
     // Debug implementation to avoid seeing fully qualified path names.
@@ -259,7 +259,8 @@ where
     /// This method has similar a effect to calling `other.extend(self.drain())`, but has the
     /// opportunity to optimize this to a `::std::mem::swap(self, other)` when `other` is empty.
     /// As many uses of this method are to propagate updates, this optimization can be quite
-    /// handy.
+    /// handy. Otherwise the updates are appended to `other` and accumulated when `other` is next
+    /// compacted, so updates that accumulate to zero are discarded then.
     ///
     /// # Examples
     ///
@@ -271,6 +272,12 @@ where
     /// batch1.drain_into(&mut batch2);
     /// assert!(batch1.is_empty());
     /// assert!(!batch2.is_empty());
+    ///
+    /// // Updates that accumulate to zero cancel.
+    /// let mut batch3 = ChangeBatch::<usize>::new_from(17, -1);
+    /// batch3.drain_into(&mut batch2);
+    /// assert!(batch3.is_empty());
+    /// assert!(batch2.is_empty());
     ///```
     #[inline]
     pub fn drain_into(&mut self, other: &mut ChangeBatch<T, X>) where T: Clone {
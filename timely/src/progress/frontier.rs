@@ -238,6 +238,22 @@ impl<T> Antichain<T> {
     ///```
     pub fn clear(&mut self) { self.elements.clear() }
 
+    /// Retains only the elements for which `predicate` returns `true`.
+    ///
+    /// The retained elements remain an antichain, as any subset of an antichain is one.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::order::Product;
+    /// use timely::progress::frontier::Antichain;
+    ///
+    /// let mut frontier = Antichain::from(vec![Product::new(0, 3), Product::new(2, 1), Product::new(3, 0)]);
+    /// frontier.retain(|time| time.outer > 0);
+    /// assert_eq!(frontier.elements(), &[Product::new(2, 1), Product::new(3, 0)]);
+    ///```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) { self.elements.retain(|element| predicate(element)) }
+
     /// Sorts the elements so that comparisons between antichains can be made.
     pub fn sort(&mut self) where T: Ord { self.elements.sort() }
 
@@ -710,6 +710,17 @@ impl<T:Timestamp> Tracker<T> {
         &self.per_operator[index]
     }
 
+    /// The lower bound of all tracked pointstamps, at any location.
+    pub fn frontier(&self) -> Antichain<T> {
+        let mut frontier = Antichain::new();
+        for node in self.per_operator.iter() {
+            for port in node.targets.iter().chain(node.sources.iter()) {
+                frontier.extend(port.pointstamps.frontier().iter().cloned());
+            }
+        }
+        frontier
+    }
+
     /// Indicates if pointstamp is in the scope-wide frontier.
     ///
     /// Such a pointstamp would, if removed from `self.pointstamps`, cause a change
@@ -10,6 +10,8 @@ use std::cell::RefCell;
 use std::collections::BinaryHeap;
 use std::cmp::Reverse;
 
+use serde::{Deserialize, Serialize};
+
 use crate::logging::{TimelyLogger as Logger, TimelyProgressEventBuilder};
 use crate::logging::TimelySummaryLogger as SummaryLogger;
 
@@ -196,7 +198,16 @@ where
 
         let activations = worker.activations();
 
-        activations.borrow_mut().activate(&self.path[..]);
+        {   // Register the subgraph and its children, and activate the subgraph.
+            let mut activations = activations.borrow_mut();
+            activations.register(&self.path[..]);
+            for child in self.children.iter().skip(1) {
+                let mut child_path = self.path.to_vec();
+                child_path.push(child.index);
+                activations.register(&child_path);
+            }
+            activations.activate(&self.path[..]);
+        }
 
         Subgraph {
             name: self.name,
@@ -207,6 +218,7 @@ where
             incomplete_count,
             activations,
             temp_active: BinaryHeap::new(),
+            temp_prioritized: Vec::new(),
             maybe_shutdown: Vec::new(),
             children: self.children,
             input_messages: self.input_messages,
@@ -250,6 +262,8 @@ where
     // shared activations (including children).
     activations: Rc<RefCell<Activations>>,
     temp_active: BinaryHeap<Reverse<usize>>,
+    // active children with non-zero priority, by decreasing priority.
+    temp_prioritized: Vec<(Reverse<usize>, usize)>,
     maybe_shutdown: Vec<usize>,
 
     // shared state written to by the datapath, counting records entering this subgraph instance.
@@ -275,6 +289,60 @@ where
     progress_mode: ProgressMode,
 }
 
+impl<TOuter, TInner> Subgraph<TOuter, TInner>
+where
+    TOuter: Timestamp,
+    TInner: Timestamp+Refines<TOuter>,
+{
+    /// The lower bound of the timestamps of capabilities and messages within the subgraph.
+    ///
+    /// The frontier reflects the progress information the subgraph had incorporated when it
+    /// was last scheduled.
+    pub fn frontier(&self) -> Antichain<TInner> {
+        self.pointstamp_tracker.frontier()
+    }
+
+    /// Snapshots the progress-tracking state of each operator in the subgraph.
+    ///
+    /// The snapshot reflects the progress information the subgraph had incorporated when it was
+    /// last scheduled, and taking it does not change that information. Operators are listed by
+    /// index, and nested subgraphs are reported as single operators.
+    pub fn snapshot(&self) -> Vec<OperatorSnapshot<TInner>> {
+        let counts = |port: &reachability::PortInformation<TInner>| {
+            let mut pointstamps = port.pointstamps.clone();
+            pointstamps.updates().cloned().collect::<Vec<_>>()
+        };
+        self.children
+            .iter()
+            .skip(1)
+            .map(|child| {
+                let node = self.pointstamp_tracker.node_state(child.index);
+                OperatorSnapshot {
+                    path: self.path.iter().cloned().chain(Some(child.index)).collect(),
+                    name: child.name.clone(),
+                    messages: node.targets.iter().map(counts).collect(),
+                    capabilities: node.sources.iter().map(counts).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The progress-tracking state of an operator, as seen by its subgraph.
+///
+/// Intended for diagnosing computations that fail to make progress.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorSnapshot<T> {
+    /// The address of the operator from the root.
+    pub path: Vec<usize>,
+    /// The name of the operator.
+    pub name: String,
+    /// For each input, the numbers of messages outstanding at each time.
+    pub messages: Vec<Vec<(T, i64)>>,
+    /// For each output, the numbers of capabilities held at each time.
+    pub capabilities: Vec<Vec<(T, i64)>>,
+}
+
 impl<TOuter, TInner> Schedule for Subgraph<TOuter, TInner>
 where
     TOuter: Timestamp,
@@ -284,6 +352,14 @@ where
 
     fn path(&self) -> &[usize] { &self.path }
 
+    fn relax(&mut self) {
+        for child in self.children.iter_mut() {
+            if let Some(operator) = child.operator.as_mut() {
+                operator.relax();
+            }
+        }
+    }
+
     fn schedule(&mut self) -> bool {
 
         // This method performs several actions related to progress tracking
@@ -302,10 +378,28 @@ where
 
         {   // Enqueue active children; scoped to let borrow drop.
             let temp_active = &mut self.temp_active;
-            self.activations
-                .borrow_mut()
-                .for_extensions(&self.path[..], |index| temp_active.push(Reverse(index)));
+            let temp_prioritized = &mut self.temp_prioritized;
+            let activations = self.activations.borrow();
+            let mut child_path = activations.has_priorities().then(|| self.path.iter().cloned().chain(Some(0)).collect::<Vec<_>>());
+            activations.for_extensions(&self.path[..], |index| {
+                temp_active.push(Reverse(index));
+                if let Some(child_path) = child_path.as_mut() {
+                    *child_path.last_mut().unwrap() = index;
+                    let priority = activations.priority(child_path);
+                    if priority > 0 {
+                        temp_prioritized.push((Reverse(priority), index));
+                    }
+                }
+            });
+        }
+
+        // Schedule prioritized child operators first.
+        self.temp_prioritized.sort();
+        for position in 0 .. self.temp_prioritized.len() {
+            let (_, index) = self.temp_prioritized[position];
+            self.activate_child(index);
         }
+        self.temp_prioritized.sort_by_key(|(_, index)| *index);
 
         // Schedule child operators.
         //
@@ -316,10 +410,13 @@ where
             // De-duplicate, and don't revisit.
             if index > previous {
                 // TODO: This is a moment where a scheduling decision happens.
-                self.activate_child(index);
+                if self.temp_prioritized.binary_search_by_key(&index, |(_, index)| *index).is_err() {
+                    self.activate_child(index);
+                }
                 previous = index;
             }
         }
+        self.temp_prioritized.clear();
 
         // Transmit produced progress updates.
         self.send_progress();
@@ -149,3 +149,145 @@ mod refines {
 
     implement_refines_empty!(usize, u128, u64, u32, u16, u8, isize, i128, i64, i32, i16, i8, ::std::time::Duration,);
 }
+
+pub mod testing {
+    //! Checks of the laws that implementations of [`Timestamp`] and [`PathSummary`] must satisfy.
+
+    use crate::order::PartialOrder;
+    use super::{PathSummary, Timestamp};
+
+    /// Checks the laws relating sample timestamps and path summaries, and describes the first violation.
+    ///
+    /// Violations of these laws do not cause errors where they occur, but can cause progress
+    /// tracking to stall or to report incorrect frontiers. The laws checked are that:
+    ///
+    /// 1. `T::minimum()` is less or equal to each timestamp,
+    /// 2. the default summary leaves each timestamp unchanged,
+    /// 3. each summary advances each timestamp to a greater or equal timestamp,
+    /// 4. summaries respect the order of timestamps, in that if `t1` is less or equal to `t2`, then
+    ///    the result for `t1` is less or equal to the result for `t2`, and is only `None` if that for
+    ///    `t2` is,
+    /// 5. results respect the order of summaries, in that if `s1` is less or equal to `s2`, then the
+    ///    result of `s1` is less or equal to the result of `s2`, and is only `None` if that of `s2` is,
+    /// 6. the composition `s1.followed_by(s2)` advances timestamps no further than applying `s1`
+    ///    and then `s2`, and is only `None` if the latter is for each timestamp.
+    ///
+    /// Each law is checked for all timestamps and summaries supplied, and pairs of them, and so
+    /// the samples should include timestamps and summaries that are related and unrelated in
+    /// order, and near any limits of the types.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::order::Product;
+    /// use timely::progress::timestamp::testing::check_path_summaries;
+    ///
+    /// let times = [Product::new(0u64, 0u32), Product::new(1, 0), Product::new(0, 1), Product::new(u64::MAX, 3)];
+    /// let summaries = [Product::new(0u64, 1u32), Product::new(1, 0), Product::new(2, 2)];
+    /// assert_eq!(check_path_summaries(&times, &summaries), Ok(()));
+    /// ```
+    pub fn check_path_summaries<T, S>(times: &[T], summaries: &[S]) -> Result<(), String>
+    where
+        T: Timestamp,
+        S: PathSummary<T>,
+    {
+        let minimum = T::minimum();
+        for time in times.iter() {
+            if !minimum.less_equal(time) {
+                return Err(format!("minimum {:?} is not less or equal to {:?}", minimum, time));
+            }
+            let result = S::default().results_in(time);
+            if result.as_ref() != Some(time) {
+                return Err(format!("default summary {:?} results in {:?} for {:?}", S::default(), result, time));
+            }
+        }
+
+        for summary in summaries.iter() {
+            for time in times.iter() {
+                if let Some(result) = summary.results_in(time) {
+                    if !time.less_equal(&result) {
+                        return Err(format!("summary {:?} results in {:?} for {:?}, which is not greater or equal", summary, result, time));
+                    }
+                }
+            }
+        }
+
+        for summary in summaries.iter() {
+            for time1 in times.iter() {
+                for time2 in times.iter().filter(|time2| time1.less_equal(*time2)) {
+                    if !less_equal(&summary.results_in(time1), &summary.results_in(time2)) {
+                        return Err(format!("summary {:?} results in {:?} for {:?}, and {:?} for the greater {:?}", summary, summary.results_in(time1), time1, summary.results_in(time2), time2));
+                    }
+                }
+            }
+        }
+
+        for summary1 in summaries.iter() {
+            for summary2 in summaries.iter().filter(|summary2| summary1.less_equal(*summary2)) {
+                for time in times.iter() {
+                    if !less_equal(&summary1.results_in(time), &summary2.results_in(time)) {
+                        return Err(format!("summary {:?} results in {:?} for {:?}, and the greater {:?} results in {:?}", summary1, summary1.results_in(time), time, summary2, summary2.results_in(time)));
+                    }
+                }
+            }
+        }
+
+        for summary1 in summaries.iter() {
+            for summary2 in summaries.iter() {
+                let composed = summary1.followed_by(summary2);
+                for time in times.iter() {
+                    let sequenced = summary1.results_in(time).and_then(|time| summary2.results_in(&time));
+                    let result = match composed.as_ref() {
+                        Some(composed) => composed.results_in(time),
+                        None => None,
+                    };
+                    if !less_equal(&result, &sequenced) {
+                        return Err(format!("{:?} followed by {:?} is {:?}, which results in {:?} for {:?}, but applying each in turn results in {:?}", summary1, summary2, composed, result, time, sequenced));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compares optional results, where `None` is greater than any timestamp.
+    fn less_equal<T: PartialOrder>(result1: &Option<T>, result2: &Option<T>) -> bool {
+        match (result1, result2) {
+            (Some(result1), Some(result2)) => result1.less_equal(result2),
+            (_, None) => true,
+            (None, Some(_)) => false,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+
+        use crate::order::PartialOrder;
+        use crate::progress::PathSummary;
+        use super::check_path_summaries;
+
+        /// A summary that retreats timestamps, which is not allowed.
+        #[derive(Clone, Debug, Default, PartialEq, Eq)]
+        struct Retreat(u64);
+
+        impl PartialOrder for Retreat {
+            fn less_equal(&self, other: &Self) -> bool { self.0 <= other.0 }
+        }
+
+        impl PathSummary<u64> for Retreat {
+            fn results_in(&self, src: &u64) -> Option<u64> { src.checked_sub(self.0) }
+            fn followed_by(&self, other: &Self) -> Option<Self> { self.0.checked_add(other.0).map(Retreat) }
+        }
+
+        #[test]
+        fn integer_summaries() {
+            assert_eq!(check_path_summaries(&[0u64, 1, 5, u64::MAX - 1, u64::MAX], &[0u64, 1, 3, u64::MAX]), Ok(()));
+        }
+
+        #[test]
+        fn retreating_summaries() {
+            let result = check_path_summaries(&[0u64, 3], &[Retreat(1)]);
+            assert_eq!(result, Err("summary Retreat(1) results in 2 for 3, which is not greater or equal".to_string()));
+        }
+    }
+}
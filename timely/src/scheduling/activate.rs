@@ -3,7 +3,7 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::thread::Thread;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, BTreeSet, HashMap, HashSet};
 use std::time::{Duration, Instant};
 use std::cmp::Reverse;
 use crossbeam_channel::{Sender, Receiver};
@@ -50,6 +50,18 @@ pub struct Activations {
     // Delayed activations.
     timer: Instant,
     queue: BinaryHeap<Reverse<(Duration, Vec<usize>)>>,
+    // The pending deadline of each path; entries in `queue` that differ are stale.
+    deadlines: HashMap<Vec<usize>, Duration>,
+
+    // Number of activations of each path, for diagnostics, if counting is enabled.
+    counts: Option<HashMap<Vec<usize>, u64>>,
+
+    // Paths of registered operators, for activation by prefix.
+    registered: BTreeSet<Vec<usize>>,
+
+    // Priorities of the active paths and their prefixes, and of those activated since.
+    priorities: HashMap<Vec<usize>, usize>,
+    pending_priorities: HashMap<Vec<usize>, usize>,
 }
 
 impl Activations {
@@ -66,16 +78,129 @@ impl Activations {
             rx,
             timer,
             queue: BinaryHeap::new(),
+            deadlines: HashMap::new(),
+            counts: None,
+            registered: BTreeSet::new(),
+            priorities: HashMap::new(),
+            pending_priorities: HashMap::new(),
+        }
+    }
+
+    /// Registers the operator addressed by `path`, for activation by `activate_all_in_path`.
+    pub fn register(&mut self, path: &[usize]) {
+        self.registered.insert(path.to_vec());
+    }
+
+    /// Deregisters the operators whose paths start with `prefix`.
+    pub fn deregister_all_in_path(&mut self, prefix: &[usize]) {
+        let paths = self.registered_in_path(prefix).cloned().collect::<Vec<_>>();
+        for path in paths {
+            self.registered.remove(&path);
+        }
+    }
+
+    /// Activates each registered operator whose path starts with `prefix`.
+    ///
+    /// Operators register as their dataflows are built, and deregister when their dataflows are
+    /// dropped or complete. A prefix with no registered operators activates nothing, and paths
+    /// already activated since the last call to `advance` are not activated again.
+    pub fn activate_all_in_path(&mut self, prefix: &[usize]) {
+        let pending = self.bounds[self.clean ..]
+            .iter()
+            .map(|(offset, length)| &self.slices[*offset .. (*offset + *length)])
+            .collect::<HashSet<_>>();
+        let paths = self.registered_in_path(prefix)
+            .filter(|path| !pending.contains(&path[..]))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in paths {
+            self.activate(&path);
         }
     }
 
+    /// The registered paths that start with `prefix`, in order.
+    fn registered_in_path<'a>(&'a self, prefix: &'a [usize]) -> impl Iterator<Item=&'a Vec<usize>> + 'a {
+        self.registered
+            .range(prefix.to_vec() ..)
+            .take_while(move |path| path.starts_with(prefix))
+    }
+
     /// Activates the task addressed by `path`.
     pub fn activate(&mut self, path: &[usize]) {
         self.bounds.push((self.slices.len(), path.len()));
         self.slices.extend(path);
+        if let Some(counts) = self.counts.as_mut() {
+            match counts.get_mut(path) {
+                Some(count) => *count += 1,
+                None => { counts.insert(path.to_vec(), 1); },
+            }
+        }
+    }
+
+    /// Activates the task addressed by `path` with a priority.
+    ///
+    /// Among the children of a scope activated for the same step, those with greater priority are
+    /// scheduled first, and those with equal priority are scheduled in index order. The priority
+    /// of a scope is the greatest priority of its activated descendants, and `activate` uses a
+    /// priority of zero. The worker orders its dataflows of equal configured priority in the same
+    /// way, by the priorities of their activated operators. Priorities only order the work within
+    /// a step: every activated task is still scheduled in the step, and so tasks of low priority
+    /// are never starved.
+    pub fn activate_with_priority(&mut self, path: &[usize], priority: usize) {
+        self.activate(path);
+        if priority > 0 {
+            for length in 1 ..= path.len() {
+                let entry = self.pending_priorities.entry(path[.. length].to_vec()).or_insert(priority);
+                *entry = std::cmp::max(*entry, priority);
+            }
+        }
+    }
+
+    /// The priority of `path` among the active paths, which is zero unless set with `activate_with_priority`.
+    pub fn priority(&self, path: &[usize]) -> usize {
+        self.priorities.get(path).copied().unwrap_or(0)
+    }
+
+    /// Indicates whether any active path has a non-zero priority.
+    pub fn has_priorities(&self) -> bool {
+        !self.priorities.is_empty()
+    }
+
+    /// Enables or disables counting the activations of each path.
+    ///
+    /// Counting is disabled by default, as it costs a map update for each activation. Disabling
+    /// counting discards the counts.
+    pub fn set_activation_counting(&mut self, enabled: bool) {
+        match (enabled, self.counts.is_some()) {
+            (true, false) => { self.counts = Some(HashMap::new()); },
+            (false, true) => { self.counts = None; },
+            _ => { },
+        }
+    }
+
+    /// A snapshot of the number of activations of each path, which is empty unless counting is
+    /// enabled with `set_activation_counting`.
+    ///
+    /// Activations are counted when they take effect, so those from other threads or with a
+    /// delay are counted once they are presented by `advance`. Repeated activations of a path
+    /// before it is scheduled are each counted. Counts accumulate until reset with
+    /// `reset_activation_counts`.
+    pub fn activation_counts(&self) -> HashMap<Vec<usize>, u64> {
+        self.counts.clone().unwrap_or_default()
+    }
+
+    /// Resets the number of activations of each path to zero.
+    pub fn reset_activation_counts(&mut self) {
+        if let Some(counts) = self.counts.as_mut() {
+            counts.clear();
+        }
     }
 
     /// Schedules a future activation for the task addressed by `path`.
+    ///
+    /// Each path has at most one pending future activation. If the path already has one that
+    /// is no later than the new one, the new one is ignored, and otherwise it replaces it, so
+    /// that multiple future activations coalesce to the earliest.
     pub fn activate_after(&mut self, path: &[usize], delay: Duration) {
         // TODO: We could have a minimum delay and immediately schedule anything less than that delay.
         if delay == Duration::new(0, 0) {
@@ -83,7 +208,37 @@ impl Activations {
         }
         else {
             let moment = self.timer.elapsed() + delay;
-            self.queue.push(Reverse((moment, path.to_vec())));
+            if self.deadlines.get(path).map(|deadline| *deadline > moment).unwrap_or(true) {
+                self.set_deadline(path, moment);
+            }
+        }
+    }
+
+    /// Sets or cancels the future activation of the task addressed by `path`.
+    ///
+    /// Unlike `activate_after`, this replaces any pending future activation of the path, even
+    /// with a later one. A `delay` of `None` cancels any pending future activation.
+    pub fn set_timer(&mut self, path: &[usize], delay: Option<Duration>) {
+        match delay {
+            Some(delay) => {
+                let moment = self.timer.elapsed() + delay;
+                self.set_deadline(path, moment);
+            },
+            None => { self.deadlines.remove(path); },
+        }
+    }
+
+    /// Records `moment` as the pending deadline of `path`.
+    fn set_deadline(&mut self, path: &[usize], moment: Duration) {
+        self.deadlines.insert(path.to_vec(), moment);
+        self.queue.push(Reverse((moment, path.to_vec())));
+    }
+
+    /// Discards stale deadlines at the head of the queue.
+    fn discard_stale(&mut self) {
+        while let Some(Reverse((moment, path))) = self.queue.peek() {
+            if self.deadlines.get(path) == Some(moment) { break; }
+            self.queue.pop();
         }
     }
 
@@ -96,12 +251,17 @@ impl Activations {
         }
 
         // Drain timer-based activations.
+        self.discard_stale();
         if !self.queue.is_empty() {
             let now = self.timer.elapsed();
             while self.queue.peek().map(|Reverse((t,_))| t <= &now) == Some(true) {
-                let Reverse((_time, path)) = self.queue.pop().unwrap();
-                self.activate(&path[..]);
+                let Reverse((time, path)) = self.queue.pop().unwrap();
+                if self.deadlines.get(&path) == Some(&time) {
+                    self.deadlines.remove(&path);
+                    self.activate(&path[..]);
+                }
             }
+            self.discard_stale();
         }
 
         self.bounds.drain(.. self.clean);
@@ -121,6 +281,10 @@ impl Activations {
         ::std::mem::swap(&mut self.buffer, &mut self.slices);
 
         self.clean = self.bounds.len();
+
+        // Present the priorities of the paths activated since the last advance.
+        self.priorities.clear();
+        ::std::mem::swap(&mut self.priorities, &mut self.pending_priorities);
     }
 
     /// Maps a function across activated paths.
@@ -172,6 +336,9 @@ impl Activations {
     /// This method should be used before putting a worker thread to sleep, as it
     /// indicates the amount of time before the thread should be unparked for the
     /// next scheduled activation.
+    ///
+    /// A future activation cancelled or replaced since the last call to `advance` may
+    /// still be reported, which results at most in a spurious wake-up.
     pub fn empty_for(&self) -> Option<Duration> {
         if !self.bounds.is_empty() {
             Some(Duration::new(0,0))
@@ -239,6 +406,15 @@ impl Activator {
             .activate(&self.path[..]);
     }
 
+    /// Activates the associated path with a priority.
+    ///
+    /// See `Activations::activate_with_priority` for details.
+    pub fn activate_with_priority(&self, priority: usize) {
+        self.queue
+            .borrow_mut()
+            .activate_with_priority(&self.path[..], priority);
+    }
+
     /// Activates the associated path after a specified duration.
     pub fn activate_after(&self, delay: Duration) {
         if delay == Duration::new(0, 0) {
@@ -250,6 +426,15 @@ impl Activator {
                 .activate_after(&self.path[..], delay);
         }
     }
+
+    /// Sets or cancels the future activation of the associated path.
+    ///
+    /// See `Activations::set_timer` for details.
+    pub fn set_timer(&self, delay: Option<Duration>) {
+        self.queue
+            .borrow_mut()
+            .set_timer(&self.path[..], delay);
+    }
 }
 
 /// A thread-safe version of `Activator`.
@@ -322,3 +507,90 @@ impl<T> Drop for ActivateOnDrop<T> {
         self.activator.borrow_mut().activate(&self.address[..]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::{Duration, Instant};
+    use super::Activations;
+
+    #[test]
+    fn activation_counts() {
+        let mut activations = Activations::new(Instant::now());
+        // Activations are not counted unless counting is enabled.
+        activations.activate(&[0, 1]);
+        assert!(activations.activation_counts().is_empty());
+        activations.set_activation_counting(true);
+        activations.activate(&[0, 1]);
+        activations.activate(&[0, 1]);
+        activations.activate(&[0, 2]);
+        activations.activate_after(&[0, 3], Duration::from_secs(3600));
+        activations.sync().activate(vec![0, 2]).unwrap();
+        activations.advance();
+
+        let counts = activations.activation_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&vec![0, 1]], 2);
+        assert_eq!(counts[&vec![0, 2]], 2);
+
+        activations.reset_activation_counts();
+        assert!(activations.activation_counts().is_empty());
+
+        activations.activate(&[0, 1]);
+        activations.set_activation_counting(false);
+        activations.activate(&[0, 1]);
+        assert!(activations.activation_counts().is_empty());
+    }
+
+    #[test]
+    fn activate_all_in_path() {
+        let mut activations = Activations::new(Instant::now());
+        activations.set_activation_counting(true);
+        for path in [&[0][..], &[0, 1], &[0, 2], &[0, 2, 1], &[1, 1]] {
+            activations.register(path);
+        }
+        activations.activate(&[0, 2]);
+        activations.activate_all_in_path(&[0, 2]);
+        activations.activate_all_in_path(&[3]);
+        assert_eq!(activations.activation_counts().get(&vec![0, 2]), Some(&1));
+        assert_eq!(activations.activation_counts().get(&vec![0, 2, 1]), Some(&1));
+        assert_eq!(activations.activation_counts().len(), 2);
+        activations.advance();
+        assert_eq!(active(&activations), vec![2]);
+
+        activations.deregister_all_in_path(&[0]);
+        activations.activate_all_in_path(&[]);
+        assert_eq!(activations.activation_counts().get(&vec![1, 1]), Some(&1));
+        assert_eq!(activations.activation_counts().len(), 3);
+    }
+
+    /// The second elements of the active paths of the form `[0, x]`.
+    fn active(activations: &Activations) -> Vec<usize> {
+        let mut active = Vec::new();
+        activations.for_extensions(&[0], |x| active.push(x));
+        active
+    }
+
+    #[test]
+    fn timers() {
+        let mut activations = Activations::new(Instant::now());
+        // Timers coalesce to the earliest.
+        activations.activate_after(&[0, 1], Duration::from_millis(100));
+        activations.activate_after(&[0, 1], Duration::from_millis(10));
+        activations.activate_after(&[0, 1], Duration::from_millis(200));
+        // Timers can be cancelled, or moved later.
+        activations.activate_after(&[0, 2], Duration::from_millis(10));
+        activations.set_timer(&[0, 2], None);
+        activations.activate_after(&[0, 3], Duration::from_millis(10));
+        activations.set_timer(&[0, 3], Some(Duration::from_secs(3600)));
+
+        std::thread::sleep(Duration::from_millis(20));
+        activations.advance();
+        assert_eq!(active(&activations), vec![1]);
+
+        std::thread::sleep(Duration::from_millis(200));
+        activations.advance();
+        assert_eq!(active(&activations), Vec::<usize>::new());
+        assert!(activations.empty_for().unwrap() > Duration::from_secs(3000));
+    }
+}
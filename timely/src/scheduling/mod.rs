@@ -18,6 +18,11 @@ pub trait Schedule {
     /// The return value indicates whether `self` has outstanding
     /// work and would be upset if the computation terminated.
     fn schedule(&mut self) -> bool;
+    /// Indicates that the operator has been idle for a while, which is a good moment to release
+    /// resources it retains for reuse, such as the allocations of its output buffers.
+    ///
+    /// The default implementation does nothing.
+    fn relax(&mut self) { }
 }
 
 /// Methods for types which schedule fibers.
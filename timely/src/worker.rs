@@ -1,8 +1,8 @@
 //! The root of each single-threaded worker.
 
 use std::rc::Rc;
-use std::cell::{RefCell, RefMut};
-use std::any::Any;
+use std::cell::{Cell, RefCell, RefMut};
+use std::any::{Any, TypeId};
 use std::str::FromStr;
 use std::time::{Instant, Duration};
 use std::collections::HashMap;
@@ -10,10 +10,12 @@ use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
 use crate::communication::{Allocate, Exchangeable, Push, Pull};
+use crate::communication::allocator::Endpoints;
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
 use crate::scheduling::{Schedule, Scheduler, Activations};
 use crate::progress::timestamp::{Refines};
-use crate::progress::SubgraphBuilder;
+use crate::progress::{Antichain, SubgraphBuilder, Timestamp};
+use crate::progress::subgraph::{OperatorSnapshot, Subgraph};
 use crate::progress::operate::Operate;
 use crate::dataflow::scopes::Child;
 use crate::logging::TimelyLogger;
@@ -82,6 +84,14 @@ impl FromStr for ProgressMode {
 pub struct Config {
     /// The progress mode to use.
     pub(crate) progress_mode: ProgressMode,
+    /// The buffer size in bytes for container builders, if not the default.
+    pub(crate) buffer_size_bytes: Option<usize>,
+    /// Whether to report operators that hold capabilities when their dataflow is dropped.
+    pub(crate) report_capability_leaks: bool,
+    /// The number of consumers a stream may have, if limited.
+    pub(crate) tee_consumer_limit: Option<usize>,
+    /// How long a dataflow must be idle before its operators are relaxed, if they are.
+    pub(crate) relax_after: Option<Duration>,
     /// A map from parameter name to typed parameter values.
     registry: HashMap<String, Arc<dyn Any + Send + Sync>>,
 }
@@ -99,6 +109,10 @@ impl Config {
     #[cfg(feature = "getopts")]
     pub fn install_options(opts: &mut getopts_dep::Options) {
         opts.optopt("", "progress-mode", "progress tracking mode (eager or demand)", "MODE");
+        opts.optopt("", "buffer-size", "buffer size in bytes for container builders", "BYTES");
+        opts.optflag("", "report-capability-leaks", "report operators holding capabilities when dataflows are dropped");
+        opts.optopt("", "tee-consumer-limit", "maximum number of consumers of each stream", "COUNT");
+        opts.optopt("", "relax-after", "relax the operators of dataflows idle for this many milliseconds", "MILLIS");
     }
 
     /// Instantiates a configuration based upon the parsed options in `matches`.
@@ -113,7 +127,24 @@ impl Config {
     pub fn from_matches(matches: &getopts_dep::Matches) -> Result<Config, String> {
         let progress_mode = matches
             .opt_get_default("progress-mode", ProgressMode::Eager)?;
-        Ok(Config::default().progress_mode(progress_mode))
+        let buffer_size_bytes = matches
+            .opt_get("buffer-size")
+            .map_err(|e: std::num::ParseIntError| e.to_string())?;
+        let report_capability_leaks = matches.opt_present("report-capability-leaks");
+        let tee_consumer_limit = matches
+            .opt_get("tee-consumer-limit")
+            .map_err(|e: std::num::ParseIntError| e.to_string())?;
+        let relax_after = matches
+            .opt_get("relax-after")
+            .map_err(|e: std::num::ParseIntError| e.to_string())?
+            .map(Duration::from_millis);
+        Ok(Config {
+            buffer_size_bytes,
+            report_capability_leaks,
+            tee_consumer_limit,
+            relax_after,
+            ..Config::default().progress_mode(progress_mode)
+        })
     }
 
     /// Sets the progress mode to `progress_mode`.
@@ -122,6 +153,91 @@ impl Config {
         self
     }
 
+    /// Sets the buffer size in bytes for container builders to `buffer_size_bytes`.
+    ///
+    /// Container builders, such as the ones that batch the outputs of operators and the data
+    /// sent along channels, size their containers for buffers of this many bytes rather than
+    /// [`crate::container::buffer::BUFFER_SIZE_BYTES`]. The size is installed for the worker's
+    /// thread when the worker is constructed, and the previous size is restored once the worker
+    /// is dropped. Each builder reads the size once when it is constructed, typically when its
+    /// operator is built.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let config = timely::Config {
+    ///     communication: timely::CommunicationConfig::Process(2),
+    ///     worker: timely::WorkerConfig::default().buffer_size_bytes(1 << 16),
+    /// };
+    /// timely::execute(config, |worker| {
+    ///    assert_eq!(timely::container::buffer::buffer_size_bytes(), 1 << 16);
+    /// }).unwrap();
+    /// ```
+    pub fn buffer_size_bytes(mut self, buffer_size_bytes: usize) -> Self {
+        self.buffer_size_bytes = Some(buffer_size_bytes);
+        self
+    }
+
+    /// Sets whether to report operators that hold capabilities when their dataflow is dropped.
+    ///
+    /// A dataflow that is dropped before it completes, either by `Worker::drop_dataflow` or
+    /// because the worker shuts down, usually has some operator that still holds a capability.
+    /// When enabled, each such operator is reported as a `TimelyEvent::Text` on the "timely" log
+    /// stream, with its name, its path, and the times at which it holds capabilities, which are
+    /// those the dataflow is stuck at. Operators within nested scopes are reported as the scope
+    /// that contains them. Reports are only produced if a "timely" logger is registered when the
+    /// dataflow is constructed.
+    ///
+    /// Reporting is disabled by default, and has no cost when disabled.
+    pub fn report_capability_leaks(mut self, report_capability_leaks: bool) -> Self {
+        self.report_capability_leaks = report_capability_leaks;
+        self
+    }
+
+    /// Limits the number of consumers each stream may have to `limit`.
+    ///
+    /// A stream sends its data to each operator it is connected to, and all but one of them
+    /// receive a copy of the data. Connecting a stream to more than `limit` operators panics,
+    /// naming the operator and output that produce the stream. This guards against fan-out that
+    /// is accidentally expensive, and dataflows that intend high fan-out can raise the limit.
+    ///
+    /// The number of consumers is not limited by default.
+    ///
+    /// # Examples
+    /// ```rust,should_panic
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    ///
+    /// let config = timely::Config {
+    ///     communication: timely::CommunicationConfig::Thread,
+    ///     worker: timely::WorkerConfig::default().tee_consumer_limit(2),
+    /// };
+    /// timely::execute(config, |worker| {
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         let stream = (0..10).to_stream(scope);
+    ///         for _ in 0 .. 3 {
+    ///             stream.inspect(|x| println!("seen: {:?}", x));
+    ///         }
+    ///     });
+    /// }).unwrap();
+    /// ```
+    pub fn tee_consumer_limit(mut self, limit: usize) -> Self {
+        self.tee_consumer_limit = Some(limit);
+        self
+    }
+
+    /// Relaxes the operators of dataflows that have not been scheduled for `after`.
+    ///
+    /// Relaxing an operator releases resources it retains for reuse, such as the spare allocations
+    /// of the container builders of its outputs, which lets workers that host many mostly idle
+    /// dataflows give memory back. Each operator with outputs is scheduled once to relax, and its
+    /// outputs allocate again when next used. A dataflow is relaxed once each time it falls idle,
+    /// and workers wake from parking to relax dataflows.
+    ///
+    /// Dataflows are not relaxed by default.
+    pub fn relax_after(mut self, after: Duration) -> Self {
+        self.relax_after = Some(after);
+        self
+    }
+
     /// Sets a typed configuration parameter for the given `key`.
     ///
     /// It is recommended to install a single configuration struct using a key
@@ -187,11 +303,23 @@ pub trait AsWorker : Scheduler {
     /// Most commonly, this would be the address of the *target* of the
     /// channel.
     fn allocate<T: Exchangeable>(&mut self, identifier: usize, address: Rc<[usize]>) -> (Vec<Box<dyn Push<T>>>, Box<dyn Pull<T>>);
+    /// Allocates a new channel for a type that cannot be serialized, as `allocate` does otherwise.
+    ///
+    /// Elements are moved between workers rather than serialized, which is only possible when all
+    /// workers share an address space and the allocator moves typed data between them. Otherwise,
+    /// for example when workers communicate over the network, an error is returned.
+    fn allocate_local<T: Send+Any>(&mut self, identifier: usize, _address: Rc<[usize]>) -> Result<Endpoints<T>, String> {
+        Err(format!("channel {} carries data that cannot be serialized, which this worker does not support", identifier))
+    }
     /// Constructs a pipeline channel from the worker to itself.
     ///
     /// By default this method uses the native channel allocation mechanism, but the expectation is
     /// that this behavior will be overridden to be more efficient.
     fn pipeline<T: 'static>(&mut self, identifier: usize, address: Rc<[usize]>) -> (ThreadPusher<T>, ThreadPuller<T>);
+    /// Indicates that messages on the channel `identifier` should be sent ahead of other messages.
+    ///
+    /// This is a hint for the communication layer, which may ignore it.
+    fn prioritize(&mut self, _identifier: usize) { }
 
     /// Allocates a new worker-unique identifier.
     fn new_identifier(&mut self) -> usize;
@@ -222,6 +350,12 @@ pub struct Worker<A: Allocate> {
     // Temporary storage for channel identifiers during dataflow construction.
     // These are then associated with a dataflow once constructed.
     temp_channel_ids: Rc<RefCell<Vec<usize>>>,
+
+    // Restores the thread's buffer size once the worker and all its clones are dropped.
+    buffer_size_guard: Option<Rc<crate::container::buffer::BufferSizeGuard>>,
+
+    // The earliest time at which some dataflow may have been idle long enough to relax.
+    relax_at: Rc<Cell<Option<Instant>>>,
 }
 
 impl<A: Allocate> AsWorker for Worker<A> {
@@ -235,6 +369,13 @@ impl<A: Allocate> AsWorker for Worker<A> {
         self.temp_channel_ids.borrow_mut().push(identifier);
         self.allocator.borrow_mut().allocate(identifier)
     }
+    fn allocate_local<D: Send+Any>(&mut self, identifier: usize, address: Rc<[usize]>) -> Result<Endpoints<D>, String> {
+        if address.is_empty() { panic!("Unacceptable address: Length zero"); }
+        let mut paths = self.paths.borrow_mut();
+        paths.insert(identifier, address);
+        self.temp_channel_ids.borrow_mut().push(identifier);
+        self.allocator.borrow_mut().allocate_local(identifier)
+    }
     fn pipeline<T: 'static>(&mut self, identifier: usize, address: Rc<[usize]>) -> (ThreadPusher<T>, ThreadPuller<T>) {
         if address.is_empty() { panic!("Unacceptable address: Length zero"); }
         let mut paths = self.paths.borrow_mut();
@@ -242,6 +383,9 @@ impl<A: Allocate> AsWorker for Worker<A> {
         self.temp_channel_ids.borrow_mut().push(identifier);
         self.allocator.borrow_mut().pipeline(identifier)
     }
+    fn prioritize(&mut self, identifier: usize) {
+        self.allocator.borrow_mut().prioritize(identifier)
+    }
 
     fn new_identifier(&mut self) -> usize { self.new_identifier() }
     fn peek_identifier(&self) -> usize { self.peek_identifier() }
@@ -260,6 +404,8 @@ impl<A: Allocate> Worker<A> {
     /// Allocates a new `Worker` bound to a channel allocator.
     pub fn new(config: Config, c: A) -> Worker<A> {
         let now = Instant::now();
+        let buffer_size_guard = config.buffer_size_bytes
+            .map(|bytes| Rc::new(crate::container::buffer::override_buffer_size_bytes(bytes)));
         Worker {
             config,
             timer: now,
@@ -272,6 +418,8 @@ impl<A: Allocate> Worker<A> {
             activations: Rc::new(RefCell::new(Activations::new(now))),
             active_dataflows: Default::default(),
             temp_channel_ids:  Default::default(),
+            buffer_size_guard,
+            relax_at: Default::default(),
         }
     }
 
@@ -310,6 +458,10 @@ impl<A: Allocate> Worker<A> {
     /// `None` allows the worker to park indefinitely, whereas a value of
     /// `Some(Duration::new(0, 0))` will return without parking the thread.
     ///
+    /// The worker only parks if no operator is activated. A parked worker is
+    /// woken by incoming messages, by activations from other threads through
+    /// a `SyncActivator`, and in time for delayed activations.
+    ///
     /// # Examples
     ///
     /// ```
@@ -351,6 +503,9 @@ impl<A: Allocate> Worker<A> {
             }
         }
 
+        // Relax idle dataflows, which activates their operators, and determine when to next check.
+        let relax_in = self.config.relax_after.and_then(|after| self.relax_idle(after));
+
         // Organize activations.
         self.activations
             .borrow_mut()
@@ -359,10 +514,7 @@ impl<A: Allocate> Worker<A> {
         // Consider parking only if we have no pending events, some dataflows, and a non-zero duration.
         let empty_for = self.activations.borrow().empty_for();
         // Determine the minimum park duration, where `None` are an absence of a constraint.
-        let delay = match (duration, empty_for) {
-            (Some(x), Some(y)) => Some(std::cmp::min(x,y)),
-            (x, y) => x.or(y),
-        };
+        let delay = [duration, empty_for, relax_in].into_iter().flatten().min();
 
         if delay != Some(Duration::new(0,0)) {
 
@@ -387,16 +539,41 @@ impl<A: Allocate> Worker<A> {
                 .for_extensions(&[], |index| active_dataflows.push(index));
 
             let mut dataflows = self.dataflows.borrow_mut();
+
+            // Schedule higher priority dataflows first, and among dataflows of equal priority those
+            // with operators activated at higher priority; the sort is stable and retains index order.
+            let activations = self.activations.borrow();
+            active_dataflows.sort_by_key(|index| {
+                let priority = dataflows.get(index).map(|dataflow| dataflow.priority).unwrap_or(0);
+                std::cmp::Reverse((priority, activations.priority(&[*index])))
+            });
+            drop(activations);
+
+            // The time of scheduling, if idle dataflows are relaxed.
+            let now = self.config.relax_after.map(|after| (Instant::now(), after));
+
             for index in active_dataflows.drain(..) {
                 // Step dataflow if it exists, remove if not incomplete.
                 if let Entry::Occupied(mut entry) = dataflows.entry(index) {
                     // TODO: This is a moment at which a scheduling decision is being made.
                     let incomplete = entry.get_mut().step();
+                    if let Some((now, after)) = now {
+                        // Scheduling a dataflow to relax does not end its idle period.
+                        let wrapper = entry.get_mut();
+                        wrapper.idle = match wrapper.idle {
+                            Idle::Relaxing => Idle::Relaxed,
+                            _ => {
+                                self.relax_at.set(Some(self.relax_at.get().map_or(now + after, |at| at.min(now + after))));
+                                Idle::Since(now)
+                            },
+                        };
+                    }
                     if !incomplete {
                         let mut paths = self.paths.borrow_mut();
                         for channel in entry.get_mut().channel_ids.drain(..) {
                             paths.remove(&channel);
                         }
+                        self.activations.borrow_mut().deregister_all_in_path(&[index]);
                         entry.remove_entry();
                     }
                 }
@@ -411,10 +588,15 @@ impl<A: Allocate> Worker<A> {
 
     /// Calls `self.step()` as long as `func` evaluates to `true`.
     ///
+    /// The predicate is re-checked after each step, and the method returns as
+    /// soon as it evaluates to `false`, which may be before any step is taken.
+    ///
     /// This method will continually execute even if there is not work
     /// for the worker to perform. Consider using the similar method
     /// `Self::step_or_park_while(duration)` to allow the worker to yield
-    /// control if that is appropriate.
+    /// control if that is appropriate; `step_or_park_while(None, func)` parks
+    /// the worker whenever no operator is activated, rather than spinning.
+    /// Either method runs indefinitely if the predicate never becomes `false`.
     ///
     /// # Examples
     ///
@@ -445,6 +627,11 @@ impl<A: Allocate> Worker<A> {
     /// information about that method and its behavior. In particular, the method
     /// can park the worker indefinitely, if no new work re-awakens the worker.
     ///
+    /// The predicate is re-checked after each step, including steps that end a
+    /// park because the worker was woken. A predicate that depends on state that
+    /// does not activate the worker when it changes (for example, a flag set by
+    /// another thread without a `SyncActivator`) may not be re-checked promptly.
+    ///
     /// # Examples
     ///
     /// ```
@@ -593,6 +780,46 @@ impl<A: Allocate> Worker<A> {
         self.dataflow_core(name, logging, Box::new(()), |_, child| func(child))
     }
 
+    /// Construct a new dataflow with a scheduling priority.
+    ///
+    /// In each step, the worker schedules active dataflows in order of decreasing priority,
+    /// with dataflows of equal priority scheduled in order of construction. Dataflows built
+    /// by other methods have priority zero. The priority only orders dataflows within a step:
+    /// each active dataflow is still scheduled in every step, so that a dataflow with a low
+    /// priority cannot be starved by those with higher priorities, but it will observe any
+    /// effects of their work in the same step. Dataflows of equal priority are ordered by the
+    /// priorities their operators were activated with, as by [`Activations::activate_with_priority`].
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     // Operators in this dataflow are scheduled before those of the other.
+    ///     worker.dataflow_with_priority::<usize,_,_>(1, |scope| {
+    ///
+    ///         // uses of `scope` to build dataflow
+    ///
+    ///     });
+    ///     worker.dataflow::<usize,_,_>(|scope| {
+    ///
+    ///         // uses of `scope` to build dataflow
+    ///
+    ///     });
+    /// });
+    /// ```
+    pub fn dataflow_with_priority<T, R, F>(&mut self, priority: usize, func: F) -> R
+    where
+        T: Refines<()>,
+        F: FnOnce(&mut Child<Self, T>)->R,
+    {
+        let index = self.next_dataflow_index();
+        let result = self.dataflow(func);
+        if let Some(dataflow) = self.dataflows.borrow_mut().get_mut(&index) {
+            dataflow.priority = priority;
+        }
+        result
+    }
+
     /// Construct a new dataflow with specific configurations.
     ///
     /// This method constructs a new dataflow, using a name, logger, and additional
@@ -661,12 +888,21 @@ impl<A: Allocate> Worker<A> {
         let mut temp_channel_ids = self.temp_channel_ids.borrow_mut();
         let channel_ids = temp_channel_ids.drain(..).collect::<Vec<_>>();
 
+        let now = Instant::now();
+        if let Some(after) = self.config.relax_after {
+            self.relax_at.set(Some(self.relax_at.get().map_or(now + after, |at| at.min(now + after))));
+        }
+
         let wrapper = Wrapper {
             logging,
             identifier,
             operate: Some(Box::new(operator)),
             resources: Some(Box::new(resources)),
             channel_ids,
+            priority: 0,
+            timestamp: TypeId::of::<T>(),
+            report_capability_leaks: self.config.report_capability_leaks,
+            idle: Idle::Since(now),
         };
         self.dataflows.borrow_mut().insert(dataflow_index, wrapper);
 
@@ -676,17 +912,42 @@ impl<A: Allocate> Worker<A> {
 
     /// Drops an identified dataflow.
     ///
-    /// This method removes the identified dataflow, which will no longer be scheduled.
+    /// This method removes the identified dataflow, which will no longer be scheduled, and drops
+    /// its operators, progress tracking state, and resources. Messages in flight to or within the
+    /// dataflow are discarded, and the dataflow's probes and other handles will not observe further
+    /// progress. Other dataflows are unaffected. The method returns an error if there is no
+    /// installed dataflow with the identifier.
+    ///
     /// Various other resources will be cleaned up, though the method is currently in
     /// public beta rather than expected to work. Please report all crashes and unmet
     /// expectations!
-    pub fn drop_dataflow(&mut self, dataflow_identifier: usize) {
-        if let Some(mut entry) = self.dataflows.borrow_mut().remove(&dataflow_identifier) {
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{Input, Probe};
+    ///
+    ///     let index = worker.next_dataflow_index();
+    ///     let _input = worker.dataflow::<usize,_,_>(|scope| scope.new_input::<()>().0);
+    ///
+    ///     assert!(worker.drop_dataflow(index).is_ok());
+    ///     assert!(worker.drop_dataflow(index).is_err());
+    /// });
+    /// ```
+    pub fn drop_dataflow(&mut self, dataflow_identifier: usize) -> Result<(), String> {
+        let removed = self.dataflows.borrow_mut().remove(&dataflow_identifier);
+        if let Some(mut entry) = removed {
             // Garbage collect channel_id to path information.
             let mut paths = self.paths.borrow_mut();
             for channel in entry.channel_ids.drain(..) {
                 paths.remove(&channel);
             }
+            self.activations.borrow_mut().deregister_all_in_path(&[dataflow_identifier]);
+            Ok(())
+        }
+        else {
+            Err(format!("no dataflow with identifier {}", dataflow_identifier))
         }
     }
 
@@ -703,11 +964,130 @@ impl<A: Allocate> Worker<A> {
         self.dataflows.borrow().keys().cloned().collect()
     }
 
+    /// Lists the installed dataflows with timestamp `T`, and their frontiers.
+    ///
+    /// Each dataflow is reported by its index, as in `installed_dataflows()`, with the lower bound
+    /// of the timestamps of capabilities and messages within it, as of when it was last scheduled.
+    /// A dataflow that has completed but not yet been removed has an empty frontier. Dataflows
+    /// with other timestamp types are not reported. The dataflows are listed in index order.
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{Input, Probe};
+    ///
+    ///     let mut input = worker.dataflow::<usize,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input::<()>();
+    ///         stream.probe();
+    ///         input
+    ///     });
+    ///
+    ///     input.advance_to(5);
+    ///     worker.step();
+    ///     for (index, frontier) in worker.dataflows::<usize>() {
+    ///         println!("dataflow {}: {:?}", index, frontier);
+    ///     }
+    /// });
+    /// ```
+    pub fn dataflows<T: Timestamp>(&self) -> Vec<(usize, Antichain<T>)> {
+        let dataflows = self.dataflows.borrow();
+        let mut result = dataflows
+            .iter()
+            .filter(|(_, dataflow)| dataflow.timestamp == TypeId::of::<T>())
+            .map(|(index, dataflow)| {
+                let frontier = dataflow.operate.as_ref().map(|operate| {
+                    *operate.frontier().downcast::<Antichain<T>>().expect("dataflow frontier type mismatch")
+                });
+                (*index, frontier.unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|(index, _)| *index);
+        result
+    }
+
+    /// Snapshots the progress-tracking state of the operators of installed dataflows with timestamp `T`.
+    ///
+    /// Each dataflow is reported by its index, as in `installed_dataflows()`, with the outstanding
+    /// messages and held capabilities of each of its operators, as of when it was last scheduled.
+    /// Operators of nested scopes are not reported individually. Taking the snapshot does not
+    /// change the progress state. The dataflows are listed in index order.
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{Input, Probe};
+    ///
+    ///     let mut input = worker.dataflow::<usize,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input::<()>();
+    ///         stream.probe();
+    ///         input
+    ///     });
+    ///
+    ///     input.advance_to(5);
+    ///     worker.step();
+    ///     for (index, operators) in worker.progress_snapshots::<usize>() {
+    ///         for operator in operators {
+    ///             println!("dataflow {}: {:?}", index, operator);
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn progress_snapshots<T: Timestamp>(&self) -> Vec<(usize, Vec<OperatorSnapshot<T>>)> {
+        let dataflows = self.dataflows.borrow();
+        let mut result = dataflows
+            .iter()
+            .filter(|(_, dataflow)| dataflow.timestamp == TypeId::of::<T>())
+            .map(|(index, dataflow)| {
+                let snapshot = dataflow.operate.as_ref().map(|operate| {
+                    *operate.snapshot().downcast::<Vec<OperatorSnapshot<T>>>().expect("dataflow timestamp type mismatch")
+                });
+                (*index, snapshot.unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|(index, _)| *index);
+        result
+    }
+
+    /// Relaxes the dataflows that have been idle for `after`, and returns the time until others may be.
+    fn relax_idle(&mut self, after: Duration) -> Option<Duration> {
+        let now = Instant::now();
+        let relax_at = self.relax_at.get()?;
+        if now < relax_at {
+            return Some(relax_at - now);
+        }
+        let mut relax_at: Option<Instant> = None;
+        for dataflow in self.dataflows.borrow_mut().values_mut() {
+            if let Idle::Since(since) = dataflow.idle {
+                if now >= since + after {
+                    dataflow.idle = Idle::Relaxing;
+                    if let Some(operate) = dataflow.operate.as_mut() {
+                        operate.relax();
+                    }
+                }
+                else {
+                    relax_at = Some(relax_at.map_or(since + after, |at| at.min(since + after)));
+                }
+            }
+        }
+        self.relax_at.set(relax_at);
+        relax_at.map(|at| at - now)
+    }
+
     /// Returns `true` if there is at least one dataflow under management.
     pub fn has_dataflows(&self) -> bool {
         !self.dataflows.borrow().is_empty()
     }
 
+    /// The number of sent elements the worker holds because their channels are full.
+    ///
+    /// Workers that use bounded channels should keep stepping until this is zero before they exit,
+    /// as peers may need the held elements to complete their dataflows. See [`Allocate::backlog`].
+    pub fn backlog(&self) -> usize {
+        self.allocator.borrow().backlog()
+    }
+
     // Acquire a new distinct dataflow identifier.
     fn allocate_dataflow_index(&mut self) -> usize {
         *self.dataflow_counter.borrow_mut() += 1;
@@ -729,16 +1109,68 @@ impl<A: Allocate> Clone for Worker<A> {
             activations: self.activations.clone(),
             active_dataflows: Vec::new(),
             temp_channel_ids: self.temp_channel_ids.clone(),
+            buffer_size_guard: self.buffer_size_guard.clone(),
+            relax_at: self.relax_at.clone(),
         }
     }
 }
 
+/// A schedulable dataflow, whose progress can be inspected.
+trait Dataflow: Schedule {
+    /// The frontier of the dataflow, as an `Antichain` of its timestamp type.
+    fn frontier(&self) -> Box<dyn Any>;
+    /// Snapshots of the dataflow's operators, as a `Vec<OperatorSnapshot>` of its timestamp type.
+    fn snapshot(&self) -> Box<dyn Any>;
+    /// Descriptions of the operators that hold capabilities, and the times they hold them at.
+    fn capability_leaks(&self) -> Vec<String>;
+}
+
+impl<T: Timestamp+Refines<()>> Dataflow for Subgraph<(), T> {
+    fn frontier(&self) -> Box<dyn Any> {
+        Box::new(Subgraph::frontier(self))
+    }
+    fn snapshot(&self) -> Box<dyn Any> {
+        Box::new(Subgraph::snapshot(self))
+    }
+    fn capability_leaks(&self) -> Vec<String> {
+        let mut leaks = Vec::new();
+        for operator in Subgraph::snapshot(self) {
+            for (port, counts) in operator.capabilities.iter().enumerate() {
+                let times = counts.iter().filter(|(_, count)| *count > 0).map(|(time, _)| time).collect::<Vec<_>>();
+                if !times.is_empty() {
+                    leaks.push(format!("operator {:?} at path {:?} holds capabilities on output {} at times {:?}", operator.name, operator.path, port, times));
+                }
+            }
+        }
+        leaks
+    }
+}
+
 struct Wrapper {
     logging: Option<TimelyLogger>,
     identifier: usize,
-    operate: Option<Box<dyn Schedule>>,
+    operate: Option<Box<dyn Dataflow>>,
     resources: Option<Box<dyn Any>>,
     channel_ids: Vec<usize>,
+    /// Scheduling priority, where larger values are scheduled first.
+    priority: usize,
+    /// The type of the dataflow's timestamp.
+    timestamp: TypeId,
+    /// Whether to report operators holding capabilities when dropped.
+    report_capability_leaks: bool,
+    /// Whether the dataflow is idle, if the worker relaxes idle dataflows.
+    idle: Idle,
+}
+
+/// The idleness of a dataflow, which the worker relaxes once it has been idle for long enough.
+#[derive(Clone, Copy, Debug)]
+enum Idle {
+    /// The dataflow was last scheduled at this time, other than to relax.
+    Since(Instant),
+    /// The dataflow has been asked to relax, and is next scheduled to do so.
+    Relaxing,
+    /// The dataflow has relaxed, and has not been scheduled since.
+    Relaxed,
 }
 
 impl Wrapper {
@@ -773,9 +1205,387 @@ impl Drop for Wrapper {
     fn drop(&mut self) {
         if let Some(l) = self.logging.as_mut() {
             l.log(crate::logging::ShutdownEvent { id: self.identifier });
+            // A dataflow that has not completed may have operators that retain capabilities.
+            if self.report_capability_leaks {
+                if let Some(operate) = self.operate.as_ref() {
+                    for leak in operate.capability_leaks() {
+                        let text = format!("dataflow {} dropped while {}", self.identifier, leak);
+                        l.log(crate::logging::TimelyEvent::Text(text));
+                    }
+                    l.flush();
+                }
+            }
         }
         // ensure drop order
         self.operate = None;
         self.resources = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    use crate::container::CapacityContainerBuilder;
+    use crate::dataflow::Scope;
+    use crate::dataflow::operators::generic::operator::source;
+    use crate::scheduling::Scheduler;
+
+    /// Builds a source that records `name` in `log` each time it is scheduled, and that is
+    /// saturated: it activates itself with `priority` after each of its first nine rounds.
+    fn saturated_source<G: Scope<Timestamp=u64>>(scope: &G, name: &'static str, priority: usize, log: &Rc<RefCell<Vec<&'static str>>>) {
+        let log = Rc::clone(log);
+        source::<_, CapacityContainerBuilder<Vec<()>>, _, _>(scope, name, |capability, info| {
+            let activator = scope.activator_for(info.address);
+            let mut capability = Some(capability);
+            let mut rounds = 0;
+            move |_output| {
+                log.borrow_mut().push(name);
+                rounds += 1;
+                if rounds < 10 { activator.activate_with_priority(priority); }
+                else { capability.take(); }
+            }
+        });
+    }
+
+    #[test]
+    fn dataflow_priority() {
+        crate::execute::execute_directly(|worker| {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            // Build the low priority dataflow first, so that it would otherwise be scheduled first.
+            for (name, priority) in [("low", 0), ("high", 1)] {
+                worker.dataflow_with_priority::<u64,_,_>(priority, |scope| saturated_source(scope, name, 0, &log));
+            }
+
+            for _ in 0 .. 10 {
+                log.borrow_mut().clear();
+                worker.step();
+                // Both make progress in each step, but the high priority dataflow goes first.
+                assert_eq!(*log.borrow(), vec!["high", "low"]);
+            }
+        });
+    }
+
+    #[test]
+    fn operator_priority() {
+        crate::execute::execute_directly(|worker| {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            worker.dataflow::<u64,_,_>(|scope| {
+                // The first source has the lower index, and would otherwise be scheduled first.
+                for (name, priority) in [("low", 0), ("high", 1)] {
+                    saturated_source(scope, name, priority, &log);
+                }
+            });
+
+            // Both are scheduled when first built, in index order, and then by priority.
+            worker.step();
+            assert_eq!(*log.borrow(), vec!["low", "high", "high", "low"]);
+            for _ in 0 .. 7 {
+                log.borrow_mut().clear();
+                worker.step();
+                // Both run in each step, but the high priority operator goes first.
+                assert_eq!(*log.borrow(), vec!["high", "low"]);
+            }
+        });
+    }
+
+    #[test]
+    fn dataflow_activation_priority() {
+        crate::execute::execute_directly(|worker| {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            // Build the low priority dataflow first, so that it would otherwise be scheduled first.
+            for (name, priority) in [("low", 0), ("high", 1)] {
+                worker.dataflow::<u64,_,_>(|scope| saturated_source(scope, name, priority, &log));
+            }
+
+            // Both are scheduled when first built, in index order, and then by priority.
+            worker.step();
+            assert_eq!(*log.borrow(), vec!["low", "high", "high", "low"]);
+            for _ in 0 .. 7 {
+                log.borrow_mut().clear();
+                worker.step();
+                // The dataflow whose operator was activated with a higher priority goes first.
+                assert_eq!(*log.borrow(), vec!["high", "low"]);
+            }
+        });
+    }
+
+    #[test]
+    fn buffer_size_bytes() {
+        use crate::container::{buffer, ContainerBuilder, PushInto};
+        let config = crate::WorkerConfig::default().buffer_size_bytes(64);
+        let _worker = crate::worker::Worker::new(config, crate::communication::allocator::Thread::default());
+        let mut builder = CapacityContainerBuilder::<Vec<u64>>::default();
+        // The builder keeps the buffer size it read when constructed.
+        buffer::set_buffer_size_bytes(None);
+        for item in 0 .. 8u64 {
+            assert!(builder.extract().is_none());
+            builder.push_into(item);
+        }
+        assert_eq!(builder.extract().map(|c| c.len()), Some(8));
+    }
+
+    #[test]
+    fn buffer_size_bytes_restored() {
+        use crate::communication::allocator::Thread;
+        use crate::container::buffer;
+        buffer::set_buffer_size_bytes(Some(128));
+        // A worker without a buffer size leaves the thread's buffer size alone.
+        drop(crate::worker::Worker::new(crate::WorkerConfig::default(), Thread::default()));
+        assert_eq!(buffer::buffer_size_bytes(), 128);
+        // A worker with a buffer size installs it until it and its clones are dropped.
+        let config = crate::WorkerConfig::default().buffer_size_bytes(64);
+        let worker = crate::worker::Worker::new(config, Thread::default());
+        let clone = worker.clone();
+        assert_eq!(buffer::buffer_size_bytes(), 64);
+        drop(worker);
+        assert_eq!(buffer::buffer_size_bytes(), 64);
+        drop(clone);
+        assert_eq!(buffer::buffer_size_bytes(), 128);
+        buffer::set_buffer_size_bytes(None);
+    }
+
+    #[test]
+    fn progress_snapshots() {
+        use crate::dataflow::operators::{Input, Probe};
+        crate::execute::execute_directly(|worker| {
+            let (mut input, probe) = worker.dataflow::<u64,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<()>();
+                (input, stream.probe())
+            });
+            input.advance_to(5);
+            worker.step_while(|| probe.less_than(&5));
+            let snapshots = worker.progress_snapshots::<u64>();
+            assert_eq!(snapshots.len(), 1);
+            let (_, operators) = &snapshots[0];
+            let input_op = operators.iter().find(|op| op.name == "Input").unwrap();
+            assert_eq!(input_op.capabilities, vec![vec![(5, 1)]]);
+            assert_eq!(input_op.path.len(), 2);
+            let probe_op = operators.iter().find(|op| op.name == "Probe").unwrap();
+            assert_eq!(probe_op.messages, vec![vec![]]);
+            assert!(worker.progress_snapshots::<usize>().is_empty());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "has 3 consumers, more than the limit of 2")]
+    fn tee_consumer_limit() {
+        use crate::dataflow::operators::{ToStream, Inspect};
+        let config = crate::WorkerConfig::default().tee_consumer_limit(2);
+        let mut worker = crate::worker::Worker::new(config, crate::communication::allocator::Thread::default());
+        worker.dataflow::<u64,_,_>(|scope| {
+            let stream = (0 .. 10).to_stream(scope);
+            for _ in 0 .. 3 {
+                stream.inspect(|_| { });
+            }
+        });
+    }
+
+    #[test]
+    fn capability_leaks() {
+        use crate::dataflow::operators::{Input, Probe};
+        use crate::logging::{TimelyEvent, capture_timely_events};
+        let config = crate::WorkerConfig::default().report_capability_leaks(true);
+        let mut worker = crate::worker::Worker::new(config, crate::communication::allocator::Thread::default());
+        let events = capture_timely_events(&worker);
+        let index = worker.next_dataflow_index();
+        let (mut input, probe) = worker.dataflow::<u64,_,_>(|scope| {
+            let (input, stream) = scope.new_input::<()>();
+            (input, stream.probe())
+        });
+        input.advance_to(3);
+        worker.step_while(|| probe.less_than(&3));
+        {
+            let dataflows = worker.dataflows.borrow();
+            let leaks = dataflows[&index].operate.as_ref().unwrap().capability_leaks();
+            assert_eq!(leaks.len(), 1);
+            assert!(leaks[0].contains("\"Input\""));
+            assert!(leaks[0].contains("times [3]"));
+        }
+        // Dropping the dataflow reports its leaks, and completes nonetheless.
+        assert_eq!(worker.drop_dataflow(index), Ok(()));
+        assert!(!worker.has_dataflows());
+        let events = events.lock().unwrap();
+        let texts = events.iter().filter_map(|event| match event {
+            TimelyEvent::Text(text) => Some(text),
+            _ => None,
+        }).collect::<Vec<_>>();
+        assert_eq!(texts.len(), 1);
+        assert!(texts[0].starts_with(&format!("dataflow {} dropped while", index)));
+        assert!(texts[0].contains("times [3]"));
+    }
+
+    #[test]
+    fn dataflows() {
+        use crate::dataflow::operators::{Input, Probe};
+        use crate::progress::Antichain;
+
+        crate::execute::execute_directly(|worker| {
+            let (mut input, probe) = worker.dataflow::<usize,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<()>();
+                (input, stream.probe())
+            });
+            let mut other = worker.dataflow::<u64,_,_>(|scope| scope.new_input::<()>().0);
+
+            input.advance_to(5);
+            worker.step_while(|| probe.less_than(&5));
+            assert_eq!(worker.dataflows::<usize>(), vec![(0, Antichain::from_elem(5))]);
+            assert_eq!(worker.dataflows::<u64>(), vec![(1, Antichain::from_elem(0))]);
+
+            // A completed dataflow is reported with an empty frontier until it is removed.
+            input.close();
+            other.advance_to(3);
+            worker.step_while(|| !probe.done());
+            assert!(worker.dataflows::<usize>().iter().all(|(_, frontier)| frontier.is_empty()));
+            worker.step();
+            assert_eq!(worker.dataflows::<usize>(), vec![]);
+            assert_eq!(worker.dataflows::<u64>(), vec![(1, Antichain::from_elem(3))]);
+        });
+    }
+
+    #[test]
+    fn drop_dataflow() {
+        use crate::dataflow::operators::{Input, Probe};
+
+        crate::execute::execute_directly(|worker| {
+            let index = worker.next_dataflow_index();
+            let (mut input0, probe0) = worker.dataflow::<usize,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<usize>();
+                (input, stream.probe())
+            });
+            let (mut input1, probe1) = worker.dataflow::<usize,_,_>(|scope| {
+                let (input, stream) = scope.new_input::<usize>();
+                (input, stream.probe())
+            });
+
+            input0.send(0);
+            input0.advance_to(1);
+            assert_eq!(worker.drop_dataflow(index), Ok(()));
+            assert!(worker.drop_dataflow(index).is_err());
+            assert!(worker.drop_dataflow(index + 2).is_err());
+            assert_eq!(worker.installed_dataflows(), vec![index + 1]);
+
+            // The remaining dataflow continues, while the dropped one observes no progress.
+            for round in 1 .. 4 {
+                input0.send(round);
+                input0.advance_to(round + 1);
+                input1.send(round);
+                input1.advance_to(round + 1);
+                worker.step_while(|| probe1.less_than(&(round + 1)));
+            }
+            assert!(probe0.less_equal(&0));
+        });
+    }
+
+    #[test]
+    fn park_wakes_on_activation() {
+        use std::cell::Cell;
+        use std::time::Duration;
+
+        crate::execute::execute_directly(|worker| {
+            let scheduled = Rc::new(Cell::new(0));
+            let activator = Rc::new(RefCell::new(None));
+            let index = worker.next_dataflow_index();
+            worker.dataflow::<u64,_,_>(|scope| {
+                let scheduled = Rc::clone(&scheduled);
+                let activator = Rc::clone(&activator);
+                source::<_, CapacityContainerBuilder<Vec<()>>, _, _>(scope, "Parked", |capability, info| {
+                    *activator.borrow_mut() = Some(scope.sync_activator_for(info.address.to_vec()));
+                    move |_output| {
+                        // Hold the capability, but do no work unless activated.
+                        let _ = &capability;
+                        scheduled.set(scheduled.get() + 1);
+                    }
+                });
+            });
+
+            // Run until there is no more work, so that parking would block.
+            while worker.activations().borrow().empty_for().is_some() {
+                worker.step();
+            }
+            let before = scheduled.get();
+
+            let activator = activator.borrow_mut().take().unwrap();
+            let thread = std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                activator.activate().unwrap();
+            });
+            while scheduled.get() == before {
+                worker.step_or_park(None);
+            }
+            thread.join().unwrap();
+            worker.drop_dataflow(index).unwrap();
+        });
+    }
+
+    #[test]
+    fn relax_idle_dataflows() {
+        use std::cell::Cell;
+        use std::time::Duration;
+        use crate::communication::allocator::Thread;
+        use crate::container::{ContainerBuilder, PushInto};
+        use crate::dataflow::InputHandle;
+        use crate::dataflow::channels::pact::Pipeline;
+        use crate::dataflow::operators::{Input, Inspect, Probe};
+        use crate::dataflow::operators::generic::Operator;
+
+        thread_local! {
+            static RELAXED: Cell<usize> = const { Cell::new(0) };
+        }
+
+        /// A container builder that counts the times builders on this thread are relaxed.
+        #[derive(Default)]
+        struct Counting(CapacityContainerBuilder<Vec<u64>>);
+
+        impl PushInto<u64> for Counting {
+            fn push_into(&mut self, item: u64) { self.0.push_into(item) }
+        }
+
+        impl ContainerBuilder for Counting {
+            type Container = Vec<u64>;
+            fn extract(&mut self) -> Option<&mut Vec<u64>> { self.0.extract() }
+            fn finish(&mut self) -> Option<&mut Vec<u64>> { self.0.finish() }
+            fn relax(&mut self) {
+                RELAXED.with(|relaxed| relaxed.set(relaxed.get() + 1));
+                self.0.relax();
+            }
+        }
+
+        let config = crate::WorkerConfig::default().relax_after(Duration::from_millis(10));
+        let mut worker = crate::worker::Worker::new(config, Thread::default());
+        let outputs = Rc::new(RefCell::new(Vec::new()));
+        let mut input = InputHandle::new();
+        let probe = worker.dataflow::<u64,_,_>(|scope| {
+            let outputs = Rc::clone(&outputs);
+            scope
+                .input_from(&mut input)
+                .unary::<Counting,_,_,_>(Pipeline, "Relaxing", |_capability, _info| |input, output| {
+                    input.for_each(|time, data| {
+                        output.session_with_builder(&time).give_iterator(data.drain(..));
+                    });
+                })
+                .inspect(move |x| outputs.borrow_mut().push(*x))
+                .probe()
+        });
+
+        for round in 0 .. 2 {
+            let relaxed = RELAXED.with(Cell::get);
+            input.send(round);
+            input.advance_to(round + 1);
+            worker.step_while(|| probe.less_than(input.time()));
+            // The worker wakes to relax the dataflow once it is idle.
+            while RELAXED.with(Cell::get) == relaxed {
+                worker.step_or_park(None);
+            }
+            // It is relaxed only once however long it remains idle.
+            for _ in 0 .. 2 {
+                worker.step_or_park(Some(Duration::from_millis(20)));
+            }
+            assert_eq!(RELAXED.with(Cell::get), relaxed + 1);
+        }
+        // The relaxed output allocates again when next used.
+        assert_eq!(*outputs.borrow(), vec![0, 1]);
+    }
+}
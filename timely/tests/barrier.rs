@@ -7,6 +7,7 @@ use timely::container::CapacityContainerBuilder;
 #[test] fn barrier_sync_1w() { barrier_sync_helper(CommunicationConfig::Thread); }
 #[test] fn barrier_sync_2w() { barrier_sync_helper(CommunicationConfig::Process(2)); }
 #[test] fn barrier_sync_3w() { barrier_sync_helper(CommunicationConfig::Process(3)); }
+#[test] fn barrier_sync_2w_bounded() { barrier_sync_helper(CommunicationConfig::ProcessBounded { threads: 2, capacity: 1 }); }
 
 // This method asserts that each round of execution is notified of at most one time.
 fn barrier_sync_helper(comm_config: ::timely::CommunicationConfig) {